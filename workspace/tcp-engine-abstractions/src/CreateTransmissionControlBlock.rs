@@ -7,11 +7,11 @@ pub trait CreateTransmissionControlBlock<Address: InternetProtocolAddress>
 {
 	/// Creates a Transmission Control Block (TCB) suitable for an outbound client connection.
 	#[inline(always)]
-	fn new_for_closed_to_synchronize_sent(key: TransmissionControlBlockKey<Address>, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, congestion_control: CongestionControl, ISS: WrappingSequenceNumber) -> Self;
+	fn new_for_closed_to_synchronize_sent(key: TransmissionControlBlockKey<Address>, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, reassembly_queue: ReassemblyQueue, congestion_control: CongestionControl, ISS: WrappingSequenceNumber) -> Self;
 	
 	/// Creates a Transmission Control Block (TCB) suitable for an inbound server (listener) connection.
 	#[inline(always)]
-	fn new_for_sychronize_received_to_established(key: TransmissionControlBlockKey<Address>, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, congestion_control: CongestionControl, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie) -> Self;
+	fn new_for_sychronize_received_to_established(key: TransmissionControlBlockKey<Address>, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, reassembly_queue: ReassemblyQueue, congestion_control: CongestionControl, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie) -> Self;
 	
 	/// A key that identifies this connection; composed of the remote internet protocol address, remote port and local port.
 	#[inline(always)]
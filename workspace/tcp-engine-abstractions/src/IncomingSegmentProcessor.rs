@@ -142,10 +142,12 @@ impl IncomingSegmentProcessor
 		// RFC 3360 Section 2.1: "... the Reserved field should be zero when sent and ignored when received, unless specified otherwise by future standards actions".
 		//
 		// We VIOLATE the RFC here.
-		if unlikely!(SEG.are_reserved_bits_set_or_has_historic_nonce_sum_flag())
+		//
+		// The historic Nonce Sum (NS) flag is not checked as RFC 9768 reassigns it as the Accurate ECN (AE) flag.
+		if unlikely!(SEG.are_reserved_bits_set())
 		{
 			// RFC 3360 Section 2.1: "... the phrasing in RFC 793 does not permit sending resets in response to TCP	packets with a non-zero Reserved field, as is explained in the section above".
-			drop!(self, packet, "TCP reserved bits are set or have historic Nonce Sum (NS) flag set")
+			drop!(self, packet, "TCP reserved bits are set")
 		}
 		
 		if unlikely!(all_flags.has_urgent_flag())
@@ -20,6 +20,22 @@ trait PayloadWriter
 	fn write(&self, segment_payload_starts_at_pointer: NonNull<u8>, maximum_payload_size_unless_a_zero_window_probe: u32) -> usize;
 }
 
+/// Writes the leading part of a slice of data, as used when sending and retransmitting data.
+struct DataPayloadWriter<'a>(&'a [u8]);
+
+impl<'a> PayloadWriter for DataPayloadWriter<'a>
+{
+	#[inline(always)]
+	fn write(&self, segment_payload_starts_at_pointer: NonNull<u8>, maximum_payload_size_unless_a_zero_window_probe: u32) -> usize
+	{
+		let payload_size = min(self.0.len(), maximum_payload_size_unless_a_zero_window_probe as usize);
+		
+		unsafe { copy_nonoverlapping(self.0.as_ptr(), segment_payload_starts_at_pointer.as_ptr(), payload_size) };
+		
+		payload_size
+	}
+}
+
 trait NetworkPacketSender
 {
 	type Packet: NetworkPacket;
@@ -83,6 +99,33 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 	
 	/// Send an initial SYN segment.
 	pub fn send_synchronize(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp)
+	{
+		let ISS = transmission_control_block.SND_UNA();
+		let (payload_size, flags) = self.write_synchronize(packet, our_tcp_segment, transmission_control_block, now);
+		
+		transmission_control_block.transmitted(now, ISS, payload_size as u32, flags);
+		
+		self.send_packet(packet);
+	}
+	
+	/// Retransmits our initial SYN segment.
+	///
+	/// The options are written afresh, and the ECT(0) code point is only set if we have not fallen back to not-ECT.
+	///
+	/// Neither `SND.NXT` nor the retransmission queue are changed.
+	pub fn retransmit_synchronize(&self, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
+	{
+		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
+		
+		self.write_synchronize(packet, our_tcp_segment, transmission_control_block, now);
+		
+		self.send_packet(packet);
+		Ok(())
+	}
+	
+	/// Writes a SYN segment; returns a tuple of `(payload_size, flags)`.
+	#[inline(always)]
+	fn write_synchronize(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> (usize, Flags)
 	{
 		let remote_internet_protocol_address = transmission_control_block.remote_internet_protocol_address();
 		let explicit_congestion_notification_supported = transmission_control_block.explicit_congestion_notification_supported();
@@ -107,19 +150,25 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 		
 		let layer_4_packet_size = TcpSegment::layer_4_packet_size(padded_options_size, payload_size);
 		
+		let flags = if explicit_congestion_notification_supported
+		{
+			Flags::SynchronizeExplicitCongestionEchoCongestionWindowReduced
+		}
+		else
+		{
+			Flags::Synchronize
+		};
+		
 		{
-			let flags = if explicit_congestion_notification_supported
-			{
-				Flags::SynchronizeExplicitCongestionEchoCongestionWindowReduced
-			}
-			else
-			{
-				Flags::Synchronize
-			};
-			
 			let ISS = transmission_control_block.SND_UNA();
 			our_tcp_segment.set_for_send(transmission_control_block.remote_port_local_port(), ISS, WrappingSequenceNumber::Zero, padded_options_size, flags, InitialWindowSize::Segment);
 			
+			// RFC 9768: An AccECN setup Synchronize has the AE, CWR and ECE flags all set; a server which only supports classic ECN (RFC 3168) ignores the AE flag.
+			if transmission_control_block.accurate_explicit_congestion_notification_supported()
+			{
+				our_tcp_segment.set_accurate_explicit_congestion_echo_field(AccurateExplicitCongestionEchoField::AccurateExplicitCongestionNotificationSynchronize);
+			}
+			
 			self.calculate_in_software_and_set_if_required(our_tcp_segment, layer_4_packet_size, &self.local_internet_protocol_address, transmission_control_block.remote_internet_protocol_address());
 		}
 		
@@ -128,15 +177,23 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			md5_authentication_key.deref().write_md5_option_into_previously_reserved_space(&self.local_internet_protocol_address, remote_internet_protocol_address, padded_options_size, payload_size, our_tcp_segment, previously_reserved_space_options_data_pointer);
 		}
 		
-		packet.set_layer_4_payload_length(layer_4_packet_size);
+		// Generalized ECN: An AccECN setup Synchronize may be sent ECN-capable, as a server which supports Accurate ECN reflects the IP ECN field in its SynchronizeAcknowledgment; if it is lost, we fall back to not-ECT.
+		if transmission_control_block.may_set_capable_transport_on_synchronize()
+		{
+			packet.set_explicit_congestion_notification_state_ect_0();
+		}
 		
-		transmission_control_block.transmitted(now, ISS, payload_size as u32, flags);
+		packet.set_layer_4_payload_length(layer_4_packet_size);
 		
-		self.send_packet(packet);
+		(payload_size, flags)
 	}
 	
 	/// Sends a SYN-ACK segment.
-	pub fn send_synchronize_acknowledgment(&self, now: MonotonicMillisecondTimestamp, packet: Packet, remote_internet_protocol_address: &Address, SEG: &ParsedTcpSegment, their_maximum_segment_size: Option<MaximumSegmentSizeOption>, their_window_scale: Option<WindowScaleOption>, their_selective_acknowledgment_permitted: bool, their_timestamp: Option<TimestampsOption>, explicit_congestion_notification_supported: bool, md5_authentication_key: Option<&Rc<Md5PreSharedSecretKey>>)
+	///
+	/// If `accurate_explicit_congestion_echo_field` is present, Accurate ECN (RFC 9768) is being negotiated, and it replaces the ECE flag.
+	///
+	/// If `capable_transport` is `true`, the SynchronizeAcknowledgment is sent with the ECT(0) code point (RFC 5562); this must only be the case if explicit congestion notification is supported.
+	pub fn send_synchronize_acknowledgment(&self, now: MonotonicMillisecondTimestamp, packet: Packet, remote_internet_protocol_address: &Address, SEG: &ParsedTcpSegment, their_maximum_segment_size: Option<MaximumSegmentSizeOption>, their_window_scale: Option<WindowScaleOption>, their_selective_acknowledgment_permitted: bool, their_timestamp: Option<TimestampsOption>, explicit_congestion_notification_supported: bool, accurate_explicit_congestion_echo_field: Option<AccurateExplicitCongestionEchoField>, capable_transport: bool, md5_authentication_key: Option<&Rc<Md5PreSharedSecretKey>>)
 	{
 		let mut our_tcp_segment = self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet);
 		
@@ -188,6 +245,11 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			
 			our_tcp_segment.set_for_send(SEG.remote_port_local_port(), syncookie, SEG.SEQ + 1, padded_options_size, flags, InitialWindowSize::Segment);
 			
+			if let Some(accurate_explicit_congestion_echo_field) = accurate_explicit_congestion_echo_field
+			{
+				our_tcp_segment.set_accurate_explicit_congestion_echo_field(accurate_explicit_congestion_echo_field);
+			}
+			
 			self.calculate_in_software_and_set_if_required(our_tcp_segment, layer_4_packet_size, &self.local_internet_protocol_address, transmission_control_block.remote_internet_protocol_address());
 		}
 		
@@ -196,6 +258,13 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			md5_authentication_key.deref().write_md5_option_into_previously_reserved_space(&self.local_internet_protocol_address, remote_internet_protocol_address, padded_options_size, payload_size, our_tcp_segment, previously_reserved_space_options_data_pointer);
 		}
 		
+		if capable_transport
+		{
+			debug_assert!(explicit_congestion_notification_supported, "explicit congestion notification is not supported");
+			
+			packet.set_explicit_congestion_notification_state_ect_0();
+		}
+		
 		packet.set_layer_4_payload_length(layer_4_packet_size);
 		
 		// TODO: transmission_control_block.transmitted(packet, our_tcp_segment, payload_size, now) if using TCP fast-open
@@ -208,7 +277,7 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 	pub fn send_final_acknowledgment_of_three_way_handshake(&self, packet: Packet, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp, mut flags: Flags, SEQ: WrappingSequenceNumber, ACK: WrappingSequenceNumber)
 	{
 		transmission_control_block.update_Last_ACK_sent(ACK);
-		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, flags, SEQ, ACK, None);
+		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, flags, SEQ, ACK);
 	}
 	
 	/// Sends an acknowledgment.
@@ -218,7 +287,21 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 		let flags = transmission_control_block.add_explicit_congestion_echo_flag_to_acknowledgment_if_appropriate(flags);
 		
 		transmission_control_block.update_Last_ACK_sent(ACK);
-		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, flags, SEQ, ACK, None);
+		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, flags, SEQ, ACK);
+	}
+	
+	/// Sends an acknowledgment, eg one which was delayed.
+	#[inline(always)]
+	pub fn send_acknowledgment_without_packet_to_reuse(&self, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
+	{
+		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
+		
+		let flags = transmission_control_block.add_explicit_congestion_echo_flag_to_acknowledgment_if_appropriate(Flags::Acknowledgment);
+		let ACK = transmission_control_block.RCV_NXT();
+		
+		transmission_control_block.update_Last_ACK_sent(ACK);
+		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, flags, transmission_control_block.SND_NXT(), ACK);
+		Ok(())
 	}
 	
 	/// Sends a keep-alive probe.
@@ -235,7 +318,7 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 	pub fn send_keep_alive_probe_without_packet_to_reuse(&self, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
 	{
 		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
-		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, Flags::PushAcknowledgment, transmission_control_block.SND_UNA_less_one(), transmission_control_block.RCV_NXT(), None);
+		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, Flags::PushAcknowledgment, transmission_control_block.SND_UNA_less_one(), transmission_control_block.RCV_NXT());
 		Ok(())
 	}
 	
@@ -243,7 +326,7 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 	#[inline(always)]
 	pub fn send_challenge_acknowledgment(&self, packet: Packet, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp)
 	{
-		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, Flags::Acknowledgment, transmission_control_block.SND_NXT(), transmission_control_block.RCV_NXT(), None);
+		self.send_empty(packet, self.reuse_reversing_source_and_destination_addresses_for_tcp_segment(packet), transmission_control_block, now, Flags::Acknowledgment, transmission_control_block.SND_NXT(), transmission_control_block.RCV_NXT());
 	}
 	
 	/// Sends a Reset.
@@ -268,7 +351,7 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 		// Strictly speaking, it is valid to send a Reset before establishing synchronized state, but it is nearly always a security vulnerability.
 		transmission_control_block.debug_assert_action_is_only_valid_in_synchronized_states();
 		
-		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, Flags::Reset, SEQ, transmission_control_block.RCV_NXT(), None);
+		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, Flags::Reset, SEQ, transmission_control_block.RCV_NXT());
 	}
 	
 	/// Sends a zero-window (persist) probe.
@@ -293,43 +376,80 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 		debug_assert!(transmission_control_block.send_window_is_zero(), "SND.WND is not zero");
 		
 		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
-		self.send(packet, our_tcp_segment, transmission_control_block, now, Flags::AcknowledgmentPush, transmission_control_block.SND_NXT(), transmission_control_block.RCV_NXT(), None, ZeroWindowProbePayloadWriter);
+		self.send(packet, our_tcp_segment, transmission_control_block, now, Flags::AcknowledgmentPush, transmission_control_block.SND_NXT(), transmission_control_block.RCV_NXT(), ZeroWindowProbePayloadWriter);
 		Ok(())
 	}
 	
 	/// Sends data.
+	///
+	/// At most one segment is sent; its payload is the leading part of `buffer` up to the maximum payload size for the transmission control block.
+	///
+	/// Returns how many bytes of `buffer` were put into the segment.
 	pub fn send_data(&self, buffer: &[u8], transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<usize, ()>
 	{
-		struct DataPayloadWriter(buffer);
+		debug_assert!(transmission_control_block.send_window_is_non_zero(), "SND.WND is zero");
+		debug_assert_ne!(buffer.len(), 0, "buffer is empty");
+		
+		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
+		
+		let SEQ = transmission_control_block.SND_NXT();
+		let payload_size = self.send(packet, our_tcp_segment, transmission_control_block, now, Flags::Acknowledgment, SEQ, transmission_control_block.RCV_NXT(), DataPayloadWriter(buffer));
 		
-		impl PayloadWriter for DataPayloadWriter
+		transmission_control_block.transmitted(now, SEQ, payload_size as u32, Flags::Acknowledgment);
+		
+		Ok(payload_size)
+	}
+	
+	/// Retransmits a previously transmitted segment starting at `SEQ`.
+	///
+	/// If `flags` contains `Finish` then `buffer` must be empty, as a `FIN` is always transmitted in a segment of its own; otherwise at most one segment is sent, its payload being the leading part of `buffer` up to the maximum payload size for the transmission control block.
+	///
+	/// Neither `SND.NXT` nor the retransmission queue are changed.
+	///
+	/// Returns how many bytes of `buffer` were put into the segment.
+	pub fn retransmit_data(&self, buffer: &[u8], SEQ: WrappingSequenceNumber, flags: Flags, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<usize, ()>
+	{
+		debug_assert!(SEQ < transmission_control_block.SND_NXT(), "SEQ has not been transmitted");
+		
+		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
+		
+		if flags.contains(Flags::Finish)
 		{
-			#[inline(always)]
-			fn write(&self, segment_payload_starts_at_pointer: NonNull<u8>, maximum_payload_size_unless_a_zero_window_probe: u32) -> usize
-			{
-				let maximum_payload_size_unless_a_zero_window_probe = maximum_payload_size_unless_a_zero_window_probe as usize;
-				if (maximum_payload_size_unless_a_zero_window_probe) > self.0.len()
-				{
-					self.0.len()
-				}
-				else
-				{
-					maximum_payload_size_unless_a_zero_window_probe
-				}
-			}
+			debug_assert_eq!(buffer.len(), 0, "a FIN is always transmitted in a segment of its own");
+			
+			self.send_empty(packet, our_tcp_segment, transmission_control_block, now, flags, SEQ, transmission_control_block.RCV_NXT());
+			Ok(0)
 		}
-		
-		debug_assert!(transmission_control_block.send_window_is_not_zero(), "SND.WND is zero");
+		else
+		{
+			debug_assert_ne!(buffer.len(), 0, "buffer is empty");
+			
+			Ok(self.send(packet, our_tcp_segment, transmission_control_block, now, flags, SEQ, transmission_control_block.RCV_NXT(), DataPayloadWriter(buffer)))
+		}
+	}
+	
+	/// Sends a `FIN`.
+	///
+	/// The `FIN` is sent in a segment of its own, after all data.
+	pub fn send_finish(&self, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
+	{
+		const FinishSequenceNumberLength: u32 = 1;
 		
 		let (packet, our_tcp_segment) = self.create_for_tcp_segment(transmission_control_block.remote_internet_protocol_address())?;
-		self.send(packet, our_tcp_segment, transmission_control_block, now, Flags::Acknowledgment, transmission_control_block.SND_NXT(), transmission_control_block.RCV_NXT(), None, DataPayloadWriter(buffer));
-		Ok(XXXX)
-		// TODO: Return how many bytes were put into the segment. Code needs to keep buffer pointer.
+		
+		let SEQ = transmission_control_block.SND_NXT();
+		self.send_empty(packet, our_tcp_segment, transmission_control_block, now, Flags::FinishAcknowledgment, SEQ, transmission_control_block.RCV_NXT());
+		
+		// RFC 793 Section 3.3 Page 26 Final Paragraph: "... the FIN is considered to occur after the last actual data octet in a segment in which it occurs".
+		transmission_control_block.increment_SND_NXT(FinishSequenceNumberLength);
+		transmission_control_block.transmitted(now, SEQ, 0, Flags::FinishAcknowledgment);
+		
+		Ok(())
 	}
 	
 	/// Sends an empty TCP segment (one without any data, but possibly containing a SYN or FIN control).
 	#[inline(always)]
-	fn send_empty(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp, flags: Flags, SEQ: WrappingSequenceNumber, ACK: WrappingSequenceNumber, selective_acknowledgment_block: Option<SelectiveAcknowledgmentBlock>)
+	fn send_empty(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp, flags: Flags, SEQ: WrappingSequenceNumber, ACK: WrappingSequenceNumber)
 	{
 		struct EmptyPayloadWriter;
 		
@@ -342,11 +462,11 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			}
 		}
 		
-		self.send(packet, our_tcp_segment, transmission_control_block, now, flags, SEQ, ACK, selective_acknowledgment_block, EmptyPayloadWriter);
+		self.send(packet, our_tcp_segment, transmission_control_block, now, flags, SEQ, ACK, EmptyPayloadWriter);
 	}
 	
 	/// Sends a TCP segment.
-	fn send(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp, mut flags: Flags, SEQ: WrappingSequenceNumber, ACK: WrappingSequenceNumber, selective_acknowledgment_block: Option<SelectiveAcknowledgmentBlock>, payload_writer: impl PayloadWriter) -> usize
+	fn send(&self, packet: Packet, our_tcp_segment: &mut TcpSegment, transmission_control_block: &mut impl SendPacketTransmissionControlBlock<Address>, now: MonotonicMillisecondTimestamp, mut flags: Flags, SEQ: WrappingSequenceNumber, ACK: WrappingSequenceNumber, payload_writer: impl PayloadWriter) -> usize
 	{
 		let start_of_options_data_pointer = our_tcp_segment.options_data_pointer();
 		
@@ -360,9 +480,30 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			
 			let (mut options_data_pointer, previously_reserved_space_options_data_pointer) = Self::reserve_space_for_m5_option(options_data_pointer, md5_authentication_key);
 			
-			if let Some(selective_acknowledgment_block) = selective_acknowledgment_block
+			// RFC 2018 Section 4: "If sent at all, SACK options SHOULD be included in all ACKs which do not ACK the highest sequence number in the data receiver's queue".
+			//
+			// A SACK option is never sent on a `SYN` or `RST`.
+			if flags.contains(Flags::Acknowledgment) && flags.does_not_contain(Flags::Synchronize) && flags.does_not_contain(Flags::Reset)
 			{
-				options_data_pointer = TcpSegment::write_selective_acknowledgments_option(options_data_pointer, selective_acknowledgment_block)
+				let maximum_number_of_blocks = SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(options_data_pointer - start_of_options_data_pointer);
+				if let Some(selective_acknowledgment_option) = transmission_control_block.selective_acknowledgment_option(maximum_number_of_blocks)
+				{
+					options_data_pointer = TcpSegment::write_selective_acknowledgments_option(options_data_pointer, &selective_acknowledgment_option)
+				}
+			}
+			
+			// RFC 9768: The AccECN option is optional and, as it is lower priority than a SACK option, is only sent if space remains.
+			if flags.contains(Flags::Acknowledgment) && flags.does_not_contain(Flags::Synchronize) && flags.does_not_contain(Flags::Reset)
+			{
+				const MaximumOptionsSize: usize = 40;
+				
+				if (options_data_pointer - start_of_options_data_pointer) + AccurateExplicitCongestionNotificationOption::MaximumLength <= MaximumOptionsSize
+				{
+					if let Some(accurate_explicit_congestion_notification_option) = transmission_control_block.accurate_explicit_congestion_notification_option()
+					{
+						options_data_pointer = TcpSegment::write_accurate_explicit_congestion_notification_option(options_data_pointer, &accurate_explicit_congestion_notification_option)
+					}
+				}
 			}
 			
 			(options_data_pointer, previously_reserved_space_options_data_pointer)
@@ -371,14 +512,19 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 		let padded_options_size = TcpSegment::round_up_options_size_to_multiple_of_four_and_set_padding_to_zero(start_of_options_data_pointer, end_of_options_data_pointer);
 		
 		let SND_NXT_old = transmission_control_block.SND_NXT();
-		let maximum_payload_size = transmission_control_block.maximum_payload_size_excluding_synchronize_and_finish();
-		let payload_size = payload_writer(unsafe { NonNull::new_unchecked(start_of_options_data_pointer + padded_options_size) }, maximum_payload_size);
-		transmission_control_block.increment_SND_NXT(payload_size as u32);
+		let maximum_payload_size = transmission_control_block.maximum_payload_size_excluding_synchronize_and_finish(now, padded_options_size);
+		let payload_size = payload_writer.write(unsafe { NonNull::new_unchecked(start_of_options_data_pointer + padded_options_size) }, maximum_payload_size);
+		
+		// A segment starting before SND.NXT is a retransmission, and so does not advance SND.NXT.
+		let is_a_retransmission = payload_size != 0 && SEQ != SND_NXT_old;
+		if !is_a_retransmission
+		{
+			transmission_control_block.increment_SND_NXT(payload_size as u32);
+		}
 		
 		let layer_4_packet_size = TcpSegment::layer_4_packet_size(padded_options_size, payload_size);
 		
 		let is_a_zero_window_probe = (payload_size == 1 && transmission_control_block.send_window_is_zero() && SEQ == SND_NXT_old);
-		let is_a_retransmission = false; // we'll handle this specially.
 		
 		let is_a_data_payload_and_is_its_first_transmission = payload_size != 0 && !is_a_zero_window_probe && !is_a_retransmission;
 		
@@ -396,13 +542,21 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 					// * The connection state is synchronized (ie has reached Established or later);
 					// * We are sending a data packet (payload_size != 0);
 					//   * which is not a zero window probe (RFC 3168 Section 6.1.6);
-					// * We are not re-transmitting a packet.
-					packet.set_explicit_congestion_notification_state_ect_0();
+					// * We are not re-transmitting a packet;
+					// * We have not fallen back to not-ECT because the path drops or mangles ECN-capable packets.
+					//
+					// With generalized ECN, other packets may also be sent ECN-capable; see below.
+					if explicit_congestion_notification_state.may_set_capable_transport_on_new_data_packet()
+					{
+						packet.set_explicit_congestion_notification_state_ect_0();
+					}
 					
 					// RFC 3168 Section 6.1.2 Page 19 Paragraph 2: "... the CWR bit in the TCP header SHOULD NOT be set on retransmitted packets".
 					//
 					// RFC 3168 Section 6.1.2 Page 19 Paragraph 3: "When the TCP data sender is ready to set the CWR bit after reducing the congestion window, it SHOULD set the CWR bit only on the first new data packet that it transmits".
-					if explicit_congestion_notification_state.set_congestion_window_reduced_on_first_new_data_packet_and_turn_off_signalling()
+					//
+					// With Accurate ECN (RFC 9768), the CWR flag is part of the ACE field instead.
+					if !explicit_congestion_notification_state.is_accurate() && explicit_congestion_notification_state.set_congestion_window_reduced_on_first_new_data_packet_and_turn_off_signalling()
 					{
 						flags |= Flags::CongestionWindowReduced;
 					}
@@ -410,11 +564,37 @@ impl<Packet: NetworkPacket, Address: InternetProtocolAddress> SendTcpSegments<Pa
 			}
 		}
 		
+		// Generalized ECN: Retransmissions, zero window probes, `FIN`s and, if Accurate ECN is in use, pure acknowledgments may also be sent ECN-capable (RFC 8311 permits this as an experiment); a Reset never is.
+		//
+		// The CWR flag is still only ever set on new data packets.
+		if !is_a_data_payload_and_is_its_first_transmission && flags.does_not_contain(Flags::Reset)
+		{
+			let is_state_synchronized = transmission_control_block.is_state_synchronized();
+			let is_a_pure_acknowledgment = payload_size == 0 && flags.does_not_contain(Flags::Finish);
+			
+			if let Some(explicit_congestion_notification_state) = transmission_control_block.explicit_congestion_notification_state_reference()
+			{
+				if is_state_synchronized && explicit_congestion_notification_state.may_set_capable_transport_on_control_or_retransmitted_packet(is_a_pure_acknowledgment)
+				{
+					packet.set_explicit_congestion_notification_state_ect_0();
+				}
+			}
+		}
+		
 		let space_available_for_writing_payload = transmission_control_block.maximum_segment_payload_size(padded_options_size) - packet.internet_protocol_options_or_extension_headers_additional_overhead();
 		
 		{
 			our_tcp_segment.set_for_send(transmission_control_block.remote_port_local_port(), SEQ, ACK, padded_options_size, flags, transmission_control_block.receive_segment_window_size());
 			
+			// RFC 9768: Once Accurate ECN has been negotiated, every segment with the ACK flag set carries the ACE field in place of the CWR and ECE flags.
+			if flags.contains(Flags::Acknowledgment) && flags.does_not_contain(Flags::Synchronize)
+			{
+				if let Some(accurate_explicit_congestion_echo_field) = transmission_control_block.accurate_explicit_congestion_echo_field()
+				{
+					our_tcp_segment.set_accurate_explicit_congestion_echo_field(accurate_explicit_congestion_echo_field);
+				}
+			}
+			
 			self.calculate_in_software_and_set_if_required(our_tcp_segment, layer_4_packet_size, &self.local_internet_protocol_address, transmission_control_block.remote_internet_protocol_address());
 		}
 		
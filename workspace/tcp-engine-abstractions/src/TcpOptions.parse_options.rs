@@ -293,7 +293,7 @@ macro_rules! parse_options
 								let second_block = parse_selective_acknowledgment_block!($interface, $packet, pointer_to_second_block);
 								let pointer_to_third_block = pointer_to_second_block + SelectiveAcknowledgmentOption::BlockLength;
 								let third_block = parse_selective_acknowledgment_block!($interface, $packet, pointer_to_third_block);
-								let pointer_to_fourth_block = pointer_to_second_block + SelectiveAcknowledgmentOption::BlockLength;
+								let pointer_to_fourth_block = pointer_to_third_block + SelectiveAcknowledgmentOption::BlockLength;
 								let fourth_block = parse_selective_acknowledgment_block!($interface, $packet, pointer_to_fourth_block);
 								Some(SelectiveAcknowledgmentOption::four_blocks(first_block, second_block, third_block, fourth_block))
 							}
@@ -504,6 +504,33 @@ macro_rules! parse_options
 					// Permits data carried in the Synchronize and SynchronizeAcknowledgment handshake to be passed to the application immediately, rather than on completion of the three-way handshake.
 					34 => parse_unsupported_or_unknown_option!($interface, $packet, pointer_to_option_kind, end_pointer, duplicate_unknown_options, 34),
 					
+					// Accurate ECN (AccECN), order 0 and order 1.
+					//
+					// Definition in RFC 9768.
+					//
+					// The two option kinds differ only in the order of their 24-bit byte counter fields; trailing fields may be omitted.
+					// Only one AccECN option is permitted in a segment.
+					AccurateExplicitCongestionNotificationOption::Order0Kind | AccurateExplicitCongestionNotificationOption::Order1Kind =>
+					{
+						if unlikely!(tcp_options.has_accurate_explicit_congestion_notification())
+						{
+							drop!($interface, $packet, "TCP option accurate explicit congestion notification was duplicated")
+						}
+						
+						let length = parse_option_variable_length_including_option_kind_and_length_fields!($interface, $packet, pointer_to_option_kind, end_pointer) as usize;
+						
+						if unlikely!(!AccurateExplicitCongestionNotificationOption::is_valid_length(length))
+						{
+							drop!($interface, $packet, "TCP option accurate explicit congestion notification had a length other than 2, 5, 8 or 11")
+						}
+						
+						let option_kind = TcpOptions::parse_option_kind_without_checks(pointer_to_option_kind);
+						let pointer_to_data = pointer_to_option_kind + TcpOptions::LengthOverhead;
+						tcp_options.accurate_explicit_congestion_notification = Some(AccurateExplicitCongestionNotificationOption::parse(option_kind, pointer_to_data, length));
+						
+						length
+					}
+					
 					// Unsupported: Draft TCP Jumbo Options: https://www.imperialviolet.org/binary/jumbo-tcp-options.html
 					// 42 or 43
 					
@@ -24,4 +24,27 @@ pub trait TransmissionControlBlockEventsReceiver
 	/// * `aborted()` may occur instead of this.
 	/// * Does not occur for server listeners.
 	fn entered_state_established(&self);
+	
+	
+	// Data
+	
+	/// In-order data has been received.
+	///
+	/// `data` is either a zero-copy view of the payload of a received packet or, for data which arrived out-of-order, a slice of the connection's reassembly buffer.
+	/// It is only valid for the duration of this call.
+	///
+	/// * May occur more than once for a single received segment.
+	/// * Never occurs with empty `data`.
+	fn received(&self, data: &[u8]);
+	
+	/// The remote has acknowledged data, freeing space in the send buffer.
+	///
+	/// `send_buffer_space_available` is the number of bytes that can now be buffered by `SEND`.
+	fn writable(&self, send_buffer_space_available: usize);
+	
+	/// The remote has finished sending (a `FIN` has been received).
+	///
+	/// * Always occurs after the last `received()`.
+	/// * No further `received()` occurs.
+	fn peer_finished(&self);
 }
@@ -8,6 +8,7 @@ pub struct TransmissionControlBlocks<TCBA: TransmissionControlBlockAbstractions,
 {
 	transmission_control_blocks: UnsafeCell<BoundedHashMap<TransmissionControlBlockKey<TCBA::Address>, TCB>>,
 	transmission_control_blocks_send_buffers: Rc<MagicRingBuffersArena>,
+	transmission_control_blocks_reassembly_buffers: Rc<ReassemblyBuffersArena>,
 	source_port_chooser: SourcePortChooser<TCBA::Address>,
 	recent_connections_congestion_data: RecentConnectionDataCache<TCBA::Address>,
 	initial_sequence_number_generator: InitialSequenceNumberGenerator,
@@ -16,15 +17,20 @@ pub struct TransmissionControlBlocks<TCBA: TransmissionControlBlockAbstractions,
 impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlBlock<TCBA::Address> + ConnectionIdentification<TCBA::Address> + RecentConnectionDataProvider<TCBA::Address>> TransmissionControlBlocks<TCBA, TCB>
 {
 	/// Creates a new instance.
+	///
+	/// `reassembly_buffers_capacity` is the number of connections which can hold out-of-order data at any one time; it is the hard limit on memory used for reassembly by this Interface.
 	#[inline(always)]
-	pub fn new(transmission_control_blocks_map_capacity: usize, maximum_recent_connections_capacity: usize) -> Self
+	pub fn new(transmission_control_blocks_map_capacity: usize, maximum_recent_connections_capacity: usize, reassembly_buffers_capacity: usize) -> Self
 	{
 		const SendBufferSize: usize = 256 * 1024;
 		
+		const ReassemblyBufferSize: usize = 64 * 1024;
+		
 		Self
 		{
 			transmission_control_blocks: UnsafeCell::new(BoundedHashMap::new(transmission_control_blocks_map_capacity)),
 			transmission_control_blocks_send_buffers: MagicRingBuffersArena::new(transmission_control_blocks_map_capacity, SendBufferSize).expect("Could not allocate memory for send buffers"),
+			transmission_control_blocks_reassembly_buffers: ReassemblyBuffersArena::new(reassembly_buffers_capacity, ReassemblyBufferSize),
 			source_port_chooser: SourcePortChooser::new(maximum_recent_connections_capacity),
 			recent_connections_congestion_data: RecentConnectionDataCache::new(maximum_recent_connections_capacity, SourcePortChooser::<TCBA::Address>::OutboundConnectionExpiryPeriodIsRfc793DoubleMaximumSegmentLifetime),
 			initial_sequence_number_generator: InitialSequenceNumberGenerator::default(),
@@ -39,6 +45,13 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 		self.transmission_control_blocks_mutable_reference().get_mut(&key)
 	}
 	
+	/// Find a transmission control block by its key, eg for a user call such as CLOSE.
+	#[inline(always)]
+	pub fn find_transmission_control_block(&self, key: &TransmissionControlBlockKey<TCBA::Address>) -> Option<&mut TCB>
+	{
+		self.transmission_control_blocks_mutable_reference().get_mut(key)
+	}
+	
 	/// Are we at maximum capacity?
 	#[inline(always)]
 	pub fn at_maximum_capacity(&self) -> bool
@@ -48,7 +61,7 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 	
 	/// Create a new transmission control block for an outgoing (client) connection.
 	#[inline(always)]
-	pub fn new_transmission_control_block_for_outgoing_client_connection(&self, remote_internet_protocol_address: TCBA::Address, remote_port: NetworkEndianU16, now: MonotonicMillisecondTimestamp, explicit_congestion_notification_supported: bool, connection_time_out: MillisecondDuration, listening_server_port_combination_validity: &PortCombinationValidity, authentication_pre_shared_secret_keys: &AuthenticationPreSharedSecretKeys<TCBA::Address>, maximum_segment_size_table: &MaximumSegmentSizeTable<TCBA::Address, TCBA::PMTUTable>, local_internet_protocol_address: &TCBA::Address) -> Result<(), ()>
+	pub fn new_transmission_control_block_for_outgoing_client_connection(&self, remote_internet_protocol_address: TCBA::Address, remote_port: NetworkEndianU16, now: MonotonicMillisecondTimestamp, explicit_congestion_notification_supported: bool, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor, connection_time_out: MillisecondDuration, listening_server_port_combination_validity: &PortCombinationValidity, authentication_pre_shared_secret_keys: &AuthenticationPreSharedSecretKeys<TCBA::Address>, maximum_segment_size_table: &MaximumSegmentSizeTable<TCBA::Address, TCBA::PMTUTable>, local_internet_protocol_address: &TCBA::Address) -> Result<(), ()>
 	{
 		self.debug_assert_not_at_maximum_capacity();
 		
@@ -66,11 +79,12 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 			let recent_connection_data = self.recent_connection_data(now, &remote_internet_protocol_address);
 			let md5_authentication_key = authentication_pre_shared_secret_keys.find_md5_authentication_key(&remote_internet_protocol_address, remote_port_local_port.remote_port()).map(|key_reference| key_reference.clone());
 			let magic_ring_buffer = self.allocate_a_send_buffer();
-			let congestion_control = Self::congestion_control(explicit_congestion_notification_supported, now, maximum_segment_size_to_send_to_remote, recent_connection_data);
+			let reassembly_queue = self.reassembly_queue();
+			let congestion_control = Self::congestion_control(explicit_congestion_notification_supported, congestion_control_algorithm_constructor, now, maximum_segment_size_to_send_to_remote, recent_connection_data);
 			
 			let ISS = self.generate_initial_sequence_number(local_internet_protocol_address, &remote_internet_protocol_address, remote_port_local_port);
 			
-			TCB::new_for_closed_to_synchronize_sent(key, now, maximum_segment_size_to_send_to_remote, recent_connection_data, md5_authentication_key, magic_ring_buffer, congestion_control, ISS)
+			TCB::new_for_closed_to_synchronize_sent(key, now, maximum_segment_size_to_send_to_remote, recent_connection_data, md5_authentication_key, magic_ring_buffer, reassembly_queue, congestion_control, ISS)
 		});
 		
 		// TODO: Schedule alarms (use connection_time_out).
@@ -80,7 +94,7 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 	
 	/// Create a new transmission control block for an incoming (server) connection.
 	#[inline(always)]
-	pub fn new_transmission_control_block_for_incoming_segment(&self, source_internet_protocol_address: &TCBA::Address, SEG: &TcpSegment, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie, now: MonotonicMillisecondTimestamp, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, maximum_segment_size_table: &MaximumSegmentSizeTable<TCBA::Address, TCBA::PMTUTable>) -> &mut TCB
+	pub fn new_transmission_control_block_for_incoming_segment(&self, source_internet_protocol_address: &TCBA::Address, SEG: &TcpSegment, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie, now: MonotonicMillisecondTimestamp, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor, maximum_segment_size_table: &MaximumSegmentSizeTable<TCBA::Address, TCBA::PMTUTable>) -> &mut TCB
 	{
 		self.debug_assert_not_at_maximum_capacity();
 		
@@ -94,9 +108,10 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 			let recent_connection_data = self.recent_connection_data(now, remote_internet_protocol_address);
 			let md5_authentication_key = md5_authentication_key.map(|rc| rc.clone());
 			let magic_ring_buffer = self.allocate_a_send_buffer();
-			let congestion_control = Self::congestion_control(parsed_syncookie.explicit_congestion_notification_supported, now, maximum_segment_size_to_send_to_remote, recent_connection_data);
+			let reassembly_queue = self.reassembly_queue();
+			let congestion_control = Self::congestion_control(parsed_syncookie.explicit_congestion_notification_supported, congestion_control_algorithm_constructor, now, maximum_segment_size_to_send_to_remote, recent_connection_data);
 			
-			TCB::new_for_sychronize_received_to_established(key, now, maximum_segment_size_to_send_to_remote, recent_connection_data, md5_authentication_key, magic_ring_buffer, congestion_control, SEG_WND, tcp_options, parsed_syncookie)
+			TCB::new_for_sychronize_received_to_established(key, now, maximum_segment_size_to_send_to_remote, recent_connection_data, md5_authentication_key, magic_ring_buffer, reassembly_queue, congestion_control, SEG_WND, tcp_options, parsed_syncookie)
 		});
 		
 		// TODO: Schedule alarms.
@@ -104,6 +119,20 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 		transmission_control_block
 	}
 	
+	/// Generalized ECN: Should packets to this remote be sent not-ECT, because a recent connection found that the path drops or mangles ECN-capable packets?
+	#[inline(always)]
+	pub fn explicit_congestion_notification_fall_back(&self, now: MonotonicMillisecondTimestamp, remote_internet_protocol_address: &TCBA::Address) -> bool
+	{
+		self.recent_connection_data(now, remote_internet_protocol_address).explicit_congestion_notification_fall_back()
+	}
+	
+	/// Generalized ECN: A connection to this remote has fallen back to sending not-ECT; later connections will do likewise until the recent connection data expires.
+	#[inline(always)]
+	pub fn explicit_congestion_notification_fell_back(&self, now: MonotonicMillisecondTimestamp, remote_internet_protocol_address: &TCBA::Address)
+	{
+		self.recent_connections_congestion_data.explicit_congestion_notification_fell_back(now, remote_internet_protocol_address)
+	}
+	
 	/// Removes a transmission control block from the managed index.
 	///
 	/// A caller is responsible for freeing the data within the transmission control block (typicaly this involves freeing alarms).
@@ -154,11 +183,17 @@ impl<TCBA: TransmissionControlBlockAbstractions, TCB: CreateTransmissionControlB
 	}
 	
 	#[inline(always)]
-	fn congestion_control(explicit_congestion_notification_supported: bool, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData) -> CongestionControl
+	fn reassembly_queue(&self) -> ReassemblyQueue
+	{
+		ReassemblyQueue::new(self.transmission_control_blocks_reassembly_buffers.clone())
+	}
+	
+	#[inline(always)]
+	fn congestion_control(explicit_congestion_notification_supported: bool, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData) -> CongestionControl
 	{
 		const InitialCongestionWindowAlgorithm: InitialCongestionWindowAlgorithm = InitialCongestionWindowAlgorithm::RFC_6928;
 		
-		CongestionControl::new(explicit_congestion_notification_supported, InitialCongestionWindowAlgorithm, now, maximum_segment_size_to_send_to_remote, recent_connection_data)
+		CongestionControl::new(explicit_congestion_notification_supported, InitialCongestionWindowAlgorithm, congestion_control_algorithm_constructor, now, maximum_segment_size_to_send_to_remote, recent_connection_data)
 	}
 	
 	#[inline(always)]
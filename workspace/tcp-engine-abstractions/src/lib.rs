@@ -30,6 +30,7 @@ use ::std::marker::PhantomData;
 use ::std::mem::size_of;
 use ::std::mem::zeroed;
 use ::std::ops::Index;
+use ::std::ptr::copy_nonoverlapping;
 use ::std::ptr::NonNull;
 use ::std::rc::Rc;
 use ::tcp_engine_collections::BoundedHashMap;
@@ -43,6 +44,7 @@ use ::tcp_engine_tcp::congestion_control::*;
 use ::tcp_engine_tcp::recent_connection_data::*;
 use ::tcp_engine_tcp::syn_cookies::*;
 use ::tcp_engine_tcp_domain::*;
+use ::tcp_engine_tcp_domain::reassembly::*;
 use ::tcp_engine_tcp_domain::tcp_options::*;
 use ::tcp_engine_time::*;
 
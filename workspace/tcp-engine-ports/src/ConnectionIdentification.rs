@@ -17,6 +17,12 @@ pub trait ConnectionIdentification<Address: InternetProtocolAddress>
 	#[inline(always)]
 	fn we_are_the_listener(&self) -> bool;
 	
+	/// Can the local port of an outbound client connection be reused as soon as the connection is closed?
+	///
+	/// This is true if either we have already spent 2 x MSL in the TimeWait state or the connection used timestamps (so that the remote can use RFC 6191 to accept a new `SYN` whilst it is in the TimeWait state).
+	#[inline(always)]
+	fn source_port_may_be_recycled_immediately(&self) -> bool;
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	fn we_are_the_client(&self) -> bool
@@ -12,6 +12,8 @@ pub struct SourcePortChooser<Address: InternetProtocolAddress>
 impl<Address: InternetProtocolAddress> SourcePortChooser<Address>
 {
 	/// A four-minute value.
+	///
+	/// Source ports of connections which either completed TimeWait or used timestamps (and so can be reused by the remote using RFC 6191) are recycled immediately rather than after this period.
 	pub const OutboundConnectionExpiryPeriodIsRfc793DoubleMaximumSegmentLifetime: MillisecondDuration = MillisecondDuration::FourMinutes;
 	
 	/// Create a new instance.
@@ -68,6 +70,8 @@ impl<Address: InternetProtocolAddress> SourcePortChooser<Address>
 	}
 	
 	/// Update recent outgoing connection source port information.
+	///
+	/// If a connection's source port may be recycled immediately (see `ConnectionIdentification.source_port_may_be_recycled_immediately()`) it is made available again now rather than after `OutboundConnectionExpiryPeriodIsRfc793DoubleMaximumSegmentLifetime`.
 	#[inline(always)]
 	pub fn update(&self, transmission_control_block: &impl ConnectionIdentification<Address>, now: MonotonicMillisecondTimestamp)
 	{
@@ -83,6 +87,15 @@ impl<Address: InternetProtocolAddress> SourcePortChooser<Address>
 		
 		let key = (*remote_internet_protocol_address, remote_port_local_port.remote_port());
 		
+		if transmission_control_block.source_port_may_be_recycled_immediately()
+		{
+			if let Some(source_ports_port_bit_set) = self.cache().get_mut(now, &key)
+			{
+				source_ports_port_bit_set.remove(source_port)
+			}
+			return
+		}
+		
 		if let Some(source_ports_port_bit_set) = self.cache().get_mut(now, &key)
 		{
 			source_ports_port_bit_set.insert(source_port)
@@ -0,0 +1,162 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// The three bit ACE field of Accurate ECN (AccECN), RFC 9768.
+///
+/// It overlays the Accurate ECN (AE), Congestion Window Reduced (CWR) and Explicit Congestion Echo (ECE) flags, in that order from most to least significant bit.
+///
+/// During the three-way handshake the field is a code point which negotiates AccECN and reflects the IP ECN field of the segment being replied to; thereafter it is the least significant three bits of a count of CE marked packets.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct AccurateExplicitCongestionEchoField(u8);
+
+impl Into<u8> for AccurateExplicitCongestionEchoField
+{
+	#[inline(always)]
+	fn into(self) -> u8
+	{
+		self.0
+	}
+}
+
+impl AccurateExplicitCongestionEchoField
+{
+	const AccurateExplicitCongestionNotificationBit: u8 = 0b100;
+	
+	const CongestionWindowReducedBit: u8 = 0b010;
+	
+	const ExplicitCongestionEchoBit: u8 = 0b001;
+	
+	const Mask: u8 = 0b111;
+	
+	/// All flags clear.
+	///
+	/// On a SynchronizeAcknowledgment, indicates that the server does not support any form of ECN.
+	///
+	/// After the handshake, an AccECN peer never sends this on the first segment which completes the handshake, so it is used to distinguish a classic ECN (or non-ECN) peer.
+	pub const Zero: Self = AccurateExplicitCongestionEchoField(0b000);
+	
+	/// On a SynchronizeAcknowledgment, indicates that the server supports only classic ECN (RFC 3168).
+	pub const ClassicExplicitCongestionNotificationSynchronizeAcknowledgment: Self = AccurateExplicitCongestionEchoField(Self::ExplicitCongestionEchoBit);
+	
+	/// On a Synchronize, requests Accurate ECN (and offers classic ECN as a fallback).
+	pub const AccurateExplicitCongestionNotificationSynchronize: Self = AccurateExplicitCongestionEchoField(Self::Mask);
+	
+	/// The initial value of the packet counters (`r.cep` and `s.cep`) is five (5), so that a zero ACE field is never sent by an AccECN peer at the start of a connection.
+	pub const InitialCongestionExperiencedPacketsCounter: u32 = 5;
+	
+	/// Creates from the Accurate ECN (AE) flag and the CWR and ECE flags.
+	#[inline(always)]
+	pub fn new(accurate_explicit_congestion_notification_flag: bool, flags: Flags) -> Self
+	{
+		let mut value = 0;
+		if accurate_explicit_congestion_notification_flag
+		{
+			value |= Self::AccurateExplicitCongestionNotificationBit
+		}
+		if flags.contains(Flags::CongestionWindowReduced)
+		{
+			value |= Self::CongestionWindowReducedBit
+		}
+		if flags.contains(Flags::ExplicitCongestionEcho)
+		{
+			value |= Self::ExplicitCongestionEchoBit
+		}
+		AccurateExplicitCongestionEchoField(value)
+	}
+	
+	/// The least significant three bits of a packet counter (eg `r.cep`).
+	#[inline(always)]
+	pub fn from_counter(counter: u32) -> Self
+	{
+		AccurateExplicitCongestionEchoField((counter as u8) & Self::Mask)
+	}
+	
+	/// The code point sent on a SynchronizeAcknowledgment (by a server) or on the first acknowledgment completing the handshake (by a client) to reflect the IP ECN field of the Synchronize or SynchronizeAcknowledgment received.
+	///
+	/// Any value other than `Zero` or `ClassicExplicitCongestionNotificationSynchronizeAcknowledgment` also signals that Accurate ECN has been negotiated.
+	#[inline(always)]
+	pub fn handshake_reflection_of(explicit_congestion_notification: ExplicitCongestionNotification) -> Self
+	{
+		use self::ExplicitCongestionNotification::*;
+		
+		let value = match explicit_congestion_notification
+		{
+			NotCapableTransport => 0b010,
+			CapableTransportEctOne => 0b011,
+			CapableTransportEctZero => 0b100,
+			CongestionEncountered => 0b110,
+		};
+		AccurateExplicitCongestionEchoField(value)
+	}
+	
+	/// Does this handshake code point show that the IP ECN field of the Synchronize or SynchronizeAcknowledgment we sent, `explicit_congestion_notification_sent`, was changed on the path other than by being CE marked?
+	///
+	/// Code points which do not reflect an IP ECN field are never considered mangled.
+	#[inline(always)]
+	pub fn handshake_reflection_is_mangled(self, explicit_congestion_notification_sent: ExplicitCongestionNotification) -> bool
+	{
+		use self::ExplicitCongestionNotification::*;
+		
+		let explicit_congestion_notification_received = match self.0
+		{
+			0b010 => NotCapableTransport,
+			0b011 => CapableTransportEctOne,
+			0b100 => CapableTransportEctZero,
+			0b110 => CongestionEncountered,
+			_ => return false,
+		};
+		
+		match explicit_congestion_notification_received
+		{
+			// A router only CE marks a packet which was sent ECN-capable.
+			CongestionEncountered => explicit_congestion_notification_sent == NotCapableTransport,
+			
+			_ => explicit_congestion_notification_received != explicit_congestion_notification_sent,
+		}
+	}
+	
+	/// Is this the handshake code point reflecting an IP ECN field of CE?
+	#[inline(always)]
+	pub fn handshake_reflection_is_congestion_experienced(self) -> bool
+	{
+		self == Self::handshake_reflection_of(ExplicitCongestionNotification::CongestionEncountered)
+	}
+	
+	/// Is this the zero (all flags clear) code point?
+	#[inline(always)]
+	pub fn is_zero(self) -> bool
+	{
+		self == Self::Zero
+	}
+	
+	/// The Accurate ECN (AE) flag.
+	#[inline(always)]
+	pub fn accurate_explicit_congestion_notification_flag(self) -> bool
+	{
+		self.0 & Self::AccurateExplicitCongestionNotificationBit != 0
+	}
+	
+	/// The Congestion Window Reduced (CWR) and Explicit Congestion Echo (ECE) flags this field overlays.
+	#[inline(always)]
+	pub fn flags(self) -> Flags
+	{
+		let mut flags = Flags::empty();
+		if self.0 & Self::CongestionWindowReducedBit != 0
+		{
+			flags.insert(Flags::CongestionWindowReduced)
+		}
+		if self.0 & Self::ExplicitCongestionEchoBit != 0
+		{
+			flags.insert(Flags::ExplicitCongestionEcho)
+		}
+		flags
+	}
+	
+	/// The increase, modulo eight (8), of the remote peer's packet counter since `previous_counter`.
+	#[inline(always)]
+	pub fn delta(self, previous_counter: u32) -> u32
+	{
+		(self.0 as u32).wrapping_sub(previous_counter) & (Self::Mask as u32)
+	}
+}
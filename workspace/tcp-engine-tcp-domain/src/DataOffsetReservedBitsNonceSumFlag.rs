@@ -5,6 +5,8 @@
 /// The Nonce Sum (NS) flag was introduced in the experimental RFC 3540.
 ///
 /// It is now listed as historic by IANA as of RFC 8311.
+///
+/// RFC 9768 (Accurate ECN, AccECN) reassigns this bit as the Accurate ECN (AE) flag, the most significant bit of the three bit ACE field.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct DataOffsetReservedBitsNonceSumFlag(u8);
 
@@ -42,6 +44,34 @@ impl DataOffsetReservedBitsNonceSumFlag
 		self.0 & Self::ReservedBitsAndNonceSumBitMask != 0
 	}
 	
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn are_reserved_bits_set(self) -> bool
+	{
+		self.0 & Self::ReservedBitsBitMask != 0
+	}
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn has_accurate_explicit_congestion_notification_flag(self) -> bool
+	{
+		self.0 & Self::NonceSumFlagBit != 0
+	}
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn set_accurate_explicit_congestion_notification_flag(&mut self, accurate_explicit_congestion_notification_flag: bool)
+	{
+		if accurate_explicit_congestion_notification_flag
+		{
+			self.0 |= Self::NonceSumFlagBit
+		}
+		else
+		{
+			self.0 &= !Self::NonceSumFlagBit
+		}
+	}
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	pub fn from_padded_options_size(padded_options_size: usize) -> Self
@@ -87,6 +87,12 @@ bitflags!
 		/// Synchronize and Acknowledgment (SYNACK) explict congestion notification support signalled.
 		const SynchronizeAcknowledgmentExplicitCongestionEcho = Self::Synchronize.bits | Self::ExplicitCongestionEcho.bits;
 		
+		/// Synchronize and Acknowledgment (SYNACK) with the CWR flag set, which, with or without the Accurate ECN (AE) flag, is an Accurate ECN (RFC 9768) SynchronizeAcknowledgment ACE field code point.
+		const SynchronizeAcknowledgmentCongestionWindowReduced = Self::Synchronize.bits | Self::Acknowledgment.bits | Self::CongestionWindowReduced.bits;
+		
+		/// Synchronize and Acknowledgment (SYNACK) with the ECE and CWR flags set, which, with or without the Accurate ECN (AE) flag, is an Accurate ECN (RFC 9768) SynchronizeAcknowledgment ACE field code point.
+		const SynchronizeAcknowledgmentExplicitCongestionEchoCongestionWindowReduced = Self::Synchronize.bits | Self::Acknowledgment.bits | Self::ExplicitCongestionEcho.bits | Self::CongestionWindowReduced.bits;
+		
 		/// Synchronize and Finish (SYNFIN)
 		///
 		/// A now invalid combination originally specified in RFC 1644 (T/TCP).
@@ -145,6 +145,29 @@ impl TcpSegment
 		self.tcp_fixed_header.data_offset_reserved_bits_nonce_sum_flag.are_reserved_bits_set_or_has_historic_nonce_sum_flag()
 	}
 
+	/// Are the TCP reserved bits set?
+	///
+	/// Unlike `are_reserved_bits_set_or_has_historic_nonce_sum_flag()`, ignores the historic Nonce Sum (NS) flag, which RFC 9768 reassigns as the Accurate ECN (AE) flag.
+	#[inline(always)]
+	pub fn are_reserved_bits_set(&self) -> bool
+	{
+		self.tcp_fixed_header.data_offset_reserved_bits_nonce_sum_flag.are_reserved_bits_set()
+	}
+	
+	/// Is the Accurate ECN (AE) flag (formerly the Nonce Sum (NS) flag) set?
+	#[inline(always)]
+	pub fn has_accurate_explicit_congestion_notification_flag(&self) -> bool
+	{
+		self.tcp_fixed_header.data_offset_reserved_bits_nonce_sum_flag.has_accurate_explicit_congestion_notification_flag()
+	}
+	
+	/// The Accurate ECN (AccECN) ACE field, made up of the AE, CWR and ECE flags.
+	#[inline(always)]
+	pub fn accurate_explicit_congestion_echo_field(&self) -> AccurateExplicitCongestionEchoField
+	{
+		AccurateExplicitCongestionEchoField::new(self.has_accurate_explicit_congestion_notification_flag(), self.all_flags())
+	}
+	
 	/// Is the urgent pointer set?
 	#[inline(always)]
 	pub fn urgent_pointer_if_URG_flag_set_is_not_zero(&self) -> bool
@@ -179,6 +202,17 @@ impl TcpSegment
 	{
 		self.tcp_fixed_header.flags.remove(Flags::CongestionWindowReduced)
 	}
+	
+	/// Replaces the AE, CWR and ECE flags with the Accurate ECN (AccECN) ACE field.
+	///
+	/// Must be called after `set_for_send()`.
+	#[inline(always)]
+	pub fn set_accurate_explicit_congestion_echo_field(&mut self, accurate_explicit_congestion_echo_field: AccurateExplicitCongestionEchoField)
+	{
+		self.tcp_fixed_header.flags.remove(Flags::CongestionWindowReduced | Flags::ExplicitCongestionEcho);
+		self.tcp_fixed_header.flags.insert(accurate_explicit_congestion_echo_field.flags());
+		self.tcp_fixed_header.data_offset_reserved_bits_nonce_sum_flag.set_accurate_explicit_congestion_notification_flag(accurate_explicit_congestion_echo_field.accurate_explicit_congestion_notification_flag())
+	}
 
 	/// Pointer to start of TCP options data.
 	#[inline(always)]
@@ -223,12 +257,78 @@ impl TcpSegment
 	}
 
 	/// Write the TCP Selective Acknowledgments (SACK) option.
+	///
+	/// RFC 2018 Section 3: "Each contiguous block of data queued at the data receiver is defined in the SACK option by two 32-bit unsigned integers in network byte order".
 	#[inline(always)]
-	pub fn write_selective_acknowledgments_option(options_data_pointer: usize, selective_acknowledgments_block: SelectiveAcknowledgmentBlock) -> usize
+	pub fn write_selective_acknowledgments_option(options_data_pointer: usize, selective_acknowledgment_option: &SelectiveAcknowledgmentOption) -> usize
 	{
-		Self::write_option(options_data_pointer, SelectiveAcknowledgmentOption::Kind, SelectiveAcknowledgmentOption::OneBlockLength, selective_acknowledgments_block)
+		#[inline(always)]
+		fn write_block(block_pointer: usize, block: SelectiveAcknowledgmentBlock) -> usize
+		{
+			let block: (NetworkEndianU32, NetworkEndianU32) = (block.left_edge_of_block.into(), block.right_edge_of_block.into());
+			unsafe { (block_pointer as *mut (NetworkEndianU32, NetworkEndianU32)).write_unaligned(block) };
+			block_pointer + SelectiveAcknowledgmentOption::BlockLength
+		}
+		
+		let option_length = selective_acknowledgment_option.option_length();
+		let end_of_option_pointer = Self::write_option(options_data_pointer, SelectiveAcknowledgmentOption::Kind, option_length, ());
+		
+		let mut block_pointer = options_data_pointer + TcpOptions::LengthOverhead;
+		let number_of_blocks = selective_acknowledgment_option.number_of_blocks();
+		block_pointer = write_block(block_pointer, selective_acknowledgment_option.first_block());
+		if number_of_blocks >= 2
+		{
+			block_pointer = write_block(block_pointer, selective_acknowledgment_option.second_block());
+		}
+		if number_of_blocks >= 3
+		{
+			block_pointer = write_block(block_pointer, selective_acknowledgment_option.third_block());
+		}
+		if number_of_blocks == 4
+		{
+			block_pointer = write_block(block_pointer, selective_acknowledgment_option.fourth_block());
+		}
+		
+		debug_assert_eq!(block_pointer, end_of_option_pointer, "SACK option length mismatch");
+		end_of_option_pointer
 	}
 
+	/// Write the Accurate ECN (AccECN) TCP option.
+	///
+	/// Always written using the order 0 option kind.
+	#[inline(always)]
+	pub fn write_accurate_explicit_congestion_notification_option(options_data_pointer: usize, accurate_explicit_congestion_notification_option: &AccurateExplicitCongestionNotificationOption) -> usize
+	{
+		#[inline(always)]
+		fn write_field(field_pointer: usize, counter: Option<u32>) -> usize
+		{
+			let counter = counter.unwrap_or(0) & AccurateExplicitCongestionNotificationOption::CounterMask;
+			let field_pointer_u8 = field_pointer as *mut u8;
+			unsafe
+			{
+				field_pointer_u8.write((counter >> 16) as u8);
+				field_pointer_u8.offset(1).write((counter >> 8) as u8);
+				field_pointer_u8.offset(2).write(counter as u8);
+			}
+			field_pointer + AccurateExplicitCongestionNotificationOption::FieldLength
+		}
+		
+		let option_length = accurate_explicit_congestion_notification_option.option_length();
+		let end_of_option_pointer = Self::write_option(options_data_pointer, AccurateExplicitCongestionNotificationOption::Order0Kind, option_length, ());
+		
+		let mut field_pointer = options_data_pointer + TcpOptions::LengthOverhead;
+		for counter in [accurate_explicit_congestion_notification_option.ect_zero_bytes, accurate_explicit_congestion_notification_option.congestion_experienced_bytes, accurate_explicit_congestion_notification_option.ect_one_bytes].iter()
+		{
+			if field_pointer == end_of_option_pointer
+			{
+				break
+			}
+			field_pointer = write_field(field_pointer, *counter);
+		}
+		
+		end_of_option_pointer
+	}
+	
 	#[inline(always)]
 	fn options_size_is_not_a_multiple_of_four(options_size: usize) -> bool
 	{
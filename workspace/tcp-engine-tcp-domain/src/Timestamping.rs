@@ -24,6 +24,20 @@ impl Timestamping
 		WrappingTimestamp::from(SEG_TSval) < WrappingTimestamp::from(self.TS_Recent)
 	}
 	
+	#[allow(missing_docs)]
+	#[inline(always)]
+	pub fn is_TS_Recent_less_than(&self, SEG_TSval: NetworkEndianU32) -> bool
+	{
+		WrappingTimestamp::from(self.TS_Recent) < WrappingTimestamp::from(SEG_TSval)
+	}
+	
+	#[allow(missing_docs)]
+	#[inline(always)]
+	pub fn is_TS_Recent_equal_to(&self, SEG_TSval: NetworkEndianU32) -> bool
+	{
+		self.TS_Recent == SEG_TSval
+	}
+	
 	/// The definition of TS.Recent being invalid is in RFC 7323, Section 5.5: "We therefore require that an implementation of PAWS include a mechanism to "invalidate" the TS.Recent value when a connection is idle for more than 24 days. An alternative solution to the problem of outdated timestamps would be to send keep-alive segments at a very low rate, but still more often than the wrap-around time for timestamps, e.g., once a day".
 	///
 	/// Since we do not, through our keep-alive logic, permit a connection to be idle for more than 24 days, TS.Recent will always be valid.
@@ -144,6 +158,15 @@ impl Timestamping
 		}
 	}
 	
+	/// RFC 3522 Section 2: Is the timestamp echoed in `TSecr` earlier than the timestamp of a segment transmitted at `transmitted_at`?
+	///
+	/// If so, the acknowledgment was for a segment transmitted before then.
+	#[inline(always)]
+	pub fn timestamp_echo_reply_is_earlier_than(&self, timestamps_option: &TimestampsOption, transmitted_at: MonotonicMillisecondTimestamp) -> bool
+	{
+		WrappingTimestamp::from(timestamps_option.TSecr) < WrappingTimestamp::from(self.our_subsequent_timestamp(transmitted_at))
+	}
+	
 	/// "TSval".
 	#[inline(always)]
 	fn our_initial_timestamp(now: MonotonicMillisecondTimestamp) -> NetworkEndianU32
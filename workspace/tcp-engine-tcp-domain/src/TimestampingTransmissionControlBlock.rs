@@ -92,6 +92,20 @@ pub trait TimestampingTransmissionControlBlock<Address: InternetProtocolAddress>
 		None
 	}
 	
+	/// RFC 3522 Section 2: Does the acknowledgment echo the timestamp of a segment transmitted before `transmitted_at`?
+	///
+	/// `None` if timestamping is not in use.
+	#[inline(always)]
+	fn timestamp_echo_reply_is_earlier_than(&self, timestamps_option: Option<&TimestampsOption>, transmitted_at: MonotonicMillisecondTimestamp) -> Option<bool>
+	{
+		match (self.timestamping_reference(), timestamps_option)
+		{
+			(Some(timestamping), Some(timestamps_option)) => Some(timestamping.timestamp_echo_reply_is_earlier_than(timestamps_option, transmitted_at)),
+			
+			_ => None,
+		}
+	}
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	fn get_field_timestamping(&self) -> &Option<Timestamping>;
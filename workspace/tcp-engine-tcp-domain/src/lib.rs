@@ -26,6 +26,7 @@ extern crate tcp_engine_ports;
 
 use self::tcp_options::*;
 use ::hyper_thread_random::generate_hyper_thread_safe_random_u32;
+use ::std::cell::UnsafeCell;
 use ::std::cmp::max;
 use ::std::cmp::min;
 use ::std::cmp::Ordering;
@@ -42,7 +43,9 @@ use ::std::ops::Shl;
 use ::std::ops::Shr;
 use ::std::ops::Sub;
 use ::std::ops::SubAssign;
+use ::std::ptr::copy_nonoverlapping;
 use ::std::ptr::NonNull;
+use ::std::rc::Rc;
 use ::std::slice::from_raw_parts;
 use ::tcp_engine_authentication::*;
 use ::tcp_engine_check_sum::Rfc1141CompliantCheckSum;
@@ -60,9 +63,14 @@ pub mod tcp_options;
 pub mod retransmission;
 
 
+/// Reassembly of out-of-order segments.
+pub mod reassembly;
+
+
 include!("WrappingSequenceNumber.adjust_comparison_for_wrap_around.rs");
 
 
+include!("AccurateExplicitCongestionEchoField.rs");
 include!("DataOffsetReservedBitsNonceSumFlag.rs");
 include!("Flags.rs");
 include!("InitialWindowSize.rs");
@@ -0,0 +1,91 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// A reassembly buffer taken from a `ReassemblyBuffersArena`; it is returned to the arena when dropped.
+///
+/// Data is stored at an offset which is its sequence number modulo the buffer size, so data does not need to be moved as `RCV.NXT` advances.
+#[derive(Debug)]
+pub struct ReassemblyBuffer
+{
+	arena: Rc<ReassemblyBuffersArena>,
+	index: usize,
+}
+
+impl Drop for ReassemblyBuffer
+{
+	#[inline(always)]
+	fn drop(&mut self)
+	{
+		self.arena.free(self.index)
+	}
+}
+
+impl ReassemblyBuffer
+{
+	#[inline(always)]
+	fn new(arena: Rc<ReassemblyBuffersArena>, index: usize) -> Self
+	{
+		Self
+		{
+			arena,
+			index,
+		}
+	}
+	
+	/// Writes `data` which starts at the sequence number `starts_at`.
+	#[inline(always)]
+	pub fn write(&mut self, starts_at: WrappingSequenceNumber, data: &[u8])
+	{
+		debug_assert!(data.len() <= self.size(), "data is larger than buffer");
+		
+		let (offset, first_length) = self.offset_and_length_before_end(starts_at, data.len());
+		let pointer = self.pointer();
+		
+		unsafe
+		{
+			copy_nonoverlapping(data.as_ptr(), pointer.as_ptr().add(offset), first_length);
+			copy_nonoverlapping(data.as_ptr().add(first_length), pointer.as_ptr(), data.len() - first_length);
+		}
+	}
+	
+	/// Reads `length` bytes which start at the sequence number `starts_at`.
+	///
+	/// `deliver` is called once, or twice if the data wraps around the end of the buffer.
+	#[inline(always)]
+	pub fn read<Deliver: FnMut(&[u8])>(&self, starts_at: WrappingSequenceNumber, length: usize, deliver: &mut Deliver)
+	{
+		debug_assert!(length <= self.size(), "length is larger than buffer");
+		
+		let (offset, first_length) = self.offset_and_length_before_end(starts_at, length);
+		let pointer = self.pointer();
+		
+		deliver(unsafe { from_raw_parts(pointer.as_ptr().add(offset), first_length) });
+		
+		let second_length = length - first_length;
+		if unlikely!(second_length != 0)
+		{
+			deliver(unsafe { from_raw_parts(pointer.as_ptr(), second_length) });
+		}
+	}
+	
+	#[inline(always)]
+	fn offset_and_length_before_end(&self, starts_at: WrappingSequenceNumber, length: usize) -> (usize, usize)
+	{
+		let starts_at: u32 = starts_at.into();
+		let offset = (starts_at as usize) & (self.size() - 1);
+		(offset, min(length, self.size() - offset))
+	}
+	
+	#[inline(always)]
+	fn size(&self) -> usize
+	{
+		self.arena.buffer_size()
+	}
+	
+	#[inline(always)]
+	fn pointer(&self) -> NonNull<u8>
+	{
+		self.arena.buffer_pointer(self.index)
+	}
+}
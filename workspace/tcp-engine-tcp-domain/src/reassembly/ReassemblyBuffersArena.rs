@@ -0,0 +1,74 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// A fixed-size arena of reassembly buffers, allocated once at start-up.
+///
+/// The number of buffers multiplied by their size is the hard limit on the number of out-of-order bytes an Interface will hold across all of its connections; the size of one buffer is the hard limit for one connection.
+///
+/// Buffers are only taken from the arena whilst a connection is holding out-of-order data, so the number of buffers can be (much) smaller than the number of connections.
+#[derive(Debug)]
+pub struct ReassemblyBuffersArena
+{
+	memory: UnsafeCell<Vec<u8>>,
+	buffer_size: usize,
+	free_buffer_indices: UnsafeCell<Vec<usize>>,
+}
+
+impl ReassemblyBuffersArena
+{
+	/// Creates a new instance.
+	///
+	/// `buffer_size` must be a power of two so that sequence numbers can be mapped to offsets within a buffer even when they wrap around.
+	#[inline(always)]
+	pub fn new(number_of_buffers: usize, buffer_size: usize) -> Rc<Self>
+	{
+		debug_assert!(buffer_size.is_power_of_two(), "buffer_size '{}' is not a power of two", buffer_size);
+		debug_assert!(buffer_size <= WindowSize::Maximum.value() as usize, "buffer_size '{}' exceeds the maximum window size", buffer_size);
+		
+		Rc::new
+		(
+			Self
+			{
+				memory: UnsafeCell::new(vec![0; number_of_buffers * buffer_size]),
+				buffer_size,
+				free_buffer_indices: UnsafeCell::new((0 .. number_of_buffers).rev().collect()),
+			}
+		)
+	}
+	
+	/// Allocates a buffer.
+	///
+	/// Returns `None` if all buffers are in use.
+	#[inline(always)]
+	pub fn allocate(this: &Rc<Self>) -> Option<ReassemblyBuffer>
+	{
+		this.free_buffer_indices_mutable_reference().pop().map(|index| ReassemblyBuffer::new(this.clone(), index))
+	}
+	
+	/// The size of each buffer; this is the per-connection limit on the number of out-of-order bytes held.
+	#[inline(always)]
+	pub fn buffer_size(&self) -> usize
+	{
+		self.buffer_size
+	}
+	
+	#[inline(always)]
+	fn free(&self, index: usize)
+	{
+		self.free_buffer_indices_mutable_reference().push(index)
+	}
+	
+	#[inline(always)]
+	fn buffer_pointer(&self, index: usize) -> NonNull<u8>
+	{
+		let memory = unsafe { &mut * self.memory.get() };
+		unsafe { NonNull::new_unchecked(memory.as_mut_ptr().add(index * self.buffer_size)) }
+	}
+	
+	#[inline(always)]
+	fn free_buffer_indices_mutable_reference(&self) -> &mut Vec<usize>
+	{
+		unsafe { &mut * self.free_buffer_indices.get() }
+	}
+}
@@ -0,0 +1,306 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// An inbound out-of-order (reassembly) queue.
+///
+/// RFC 793 Page 69: "Segments with higher begining sequence numbers may be held for later processing".
+///
+/// Holds payload which arrived to the right of `RCV.NXT` until the hole(s) before it are filled.
+/// Overlapping and adjacent ranges are merged; where data overlaps, the data which arrived first is kept.
+///
+/// Memory is only taken from the `ReassemblyBuffersArena` whilst data is held; it is returned as soon as the queue empties.
+#[derive(Debug)]
+pub struct ReassemblyQueue
+{
+	arena: Rc<ReassemblyBuffersArena>,
+	buffer: Option<ReassemblyBuffer>,
+	ranges: [ReassemblyRange; ReassemblyQueue::MaximumRanges],
+	number_of_ranges: usize,
+	
+	/// The sequence numbers of the most recently held segments, most recent first; used to order SACK blocks.
+	recently_held: [WrappingSequenceNumber; ReassemblyQueue::MaximumRanges],
+	number_recently_held: usize,
+}
+
+impl ReassemblyQueue
+{
+	/// Maximum number of discontiguous ranges (ie holes after `RCV.NXT`) that are held.
+	pub const MaximumRanges: usize = 8;
+	
+	/// Creates a new instance.
+	#[inline(always)]
+	pub fn new(arena: Rc<ReassemblyBuffersArena>) -> Self
+	{
+		Self
+		{
+			arena,
+			buffer: None,
+			ranges: [ReassemblyRange::Unused; ReassemblyQueue::MaximumRanges],
+			number_of_ranges: 0,
+			recently_held: [WrappingSequenceNumber::Zero; ReassemblyQueue::MaximumRanges],
+			number_recently_held: 0,
+		}
+	}
+	
+	/// Is empty?
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.number_of_ranges == 0
+	}
+	
+	/// Ranges currently held, in ascending order of sequence number.
+	#[inline(always)]
+	pub fn ranges(&self) -> &[ReassemblyRange]
+	{
+		&self.ranges[.. self.number_of_ranges]
+	}
+	
+	/// Holds out-of-order data which starts at `SEG_SEQ`, which must be after `RCV_NXT`.
+	///
+	/// Data at or beyond `RCV_NXT + RCV_WND`, or more than the size of a reassembly buffer after `RCV_NXT`, is not held.
+	///
+	/// Returns `false` if the data could not be held, because either:-
+	///
+	/// * it lies entirely beyond the receive window or the per-connection limit;
+	/// * doing so would exceed `MaximumRanges`;
+	/// * the per-Interface limit has been reached (ie there is no free reassembly buffer).
+	#[inline(always)]
+	pub fn hold(&mut self, RCV_NXT: WrappingSequenceNumber, RCV_WND: WindowSize, SEG_SEQ: WrappingSequenceNumber, payload: &[u8]) -> bool
+	{
+		debug_assert!(SEG_SEQ > RCV_NXT, "SEG.SEQ '{:?}' is not after RCV.NXT '{:?}'", SEG_SEQ, RCV_NXT);
+		debug_assert_ne!(payload.len(), 0, "payload is empty");
+		
+		// RFC 793 Page 69: "Segments with higher begining sequence numbers may be held for later processing"; only data within the window is acceptable, so data beyond it is trimmed.
+		let limit = RCV_NXT + min(RCV_WND.value() as usize, self.arena.buffer_size());
+		if unlikely!(SEG_SEQ >= limit)
+		{
+			return false
+		}
+		let ends_at = min(SEG_SEQ + payload.len(), limit);
+		
+		let number_of_ranges = self.number_of_ranges;
+		
+		let first_index_to_merge = self.ranges().iter().position(|range| range.ends_at >= SEG_SEQ).unwrap_or(number_of_ranges);
+		let mut after_last_index_to_merge = first_index_to_merge;
+		while after_last_index_to_merge < number_of_ranges && self.ranges[after_last_index_to_merge].starts_at <= ends_at
+		{
+			after_last_index_to_merge += 1;
+		}
+		let number_of_ranges_to_merge = after_last_index_to_merge - first_index_to_merge;
+		
+		if unlikely!(number_of_ranges_to_merge == 0 && number_of_ranges == Self::MaximumRanges)
+		{
+			return false
+		}
+		
+		if self.buffer.is_none()
+		{
+			match ReassemblyBuffersArena::allocate(&self.arena)
+			{
+				None => return false,
+				
+				buffer @ Some(_) => self.buffer = buffer,
+			}
+		}
+		
+		// Only write into the holes between existing ranges, so that data which arrived first is kept.
+		{
+			let buffer = self.buffer.as_mut().unwrap();
+			let mut cursor = SEG_SEQ;
+			for range in self.ranges[first_index_to_merge .. after_last_index_to_merge].iter()
+			{
+				if cursor < range.starts_at
+				{
+					let hole_ends_at = min(range.starts_at, ends_at);
+					let offset = (cursor - SEG_SEQ) as usize;
+					buffer.write(cursor, &payload[offset .. offset + (hole_ends_at - cursor) as usize]);
+				}
+				cursor = max(cursor, range.ends_at);
+			}
+			if cursor < ends_at
+			{
+				let offset = (cursor - SEG_SEQ) as usize;
+				buffer.write(cursor, &payload[offset .. offset + (ends_at - cursor) as usize]);
+			}
+		}
+		
+		if number_of_ranges_to_merge == 0
+		{
+			let mut index = number_of_ranges;
+			while index > first_index_to_merge
+			{
+				self.ranges[index] = self.ranges[index - 1];
+				index -= 1;
+			}
+			self.ranges[first_index_to_merge] = ReassemblyRange
+			{
+				starts_at: SEG_SEQ,
+				ends_at,
+			};
+			self.number_of_ranges += 1;
+		}
+		else
+		{
+			let merged = ReassemblyRange
+			{
+				starts_at: min(SEG_SEQ, self.ranges[first_index_to_merge].starts_at),
+				ends_at: max(ends_at, self.ranges[after_last_index_to_merge - 1].ends_at),
+			};
+			self.ranges[first_index_to_merge] = merged;
+			self.remove_ranges(first_index_to_merge + 1, number_of_ranges_to_merge - 1);
+		}
+		
+		self.record_recently_held(SEG_SEQ);
+		
+		true
+	}
+	
+	/// Creates a SACK option with at most `maximum_number_of_blocks` blocks describing the data held.
+	///
+	/// RFC 2018 Section 4: "The first SACK block (i.e., the one immediately following the kind and length fields in the option) MUST specify the contiguous block of data containing the segment which triggered this ACK, unless that segment advanced the Acknowledgment Number field in the header.
+	/// This assures that the ACK with the SACK option reflects the most recent change in the data receiver's buffer queue".
+	///
+	/// RFC 2018 Section 4: "The SACK option SHOULD be filled out by repeating the most recently reported SACK blocks (based on first SACK blocks in previous SACK options) that are not subsets of a SACK block already included in the SACK option being constructed".
+	///
+	/// Blocks are ordered by how recently data was held in them; any remaining space is filled with the highest blocks not already included.
+	///
+	/// RFC 2883 Section 4: a duplicate selective acknowledgment (D-SACK) block, reporting the duplicate segment which triggered this acknowledgment, is always the first block.
+	/// Any `duplicate_selective_acknowledgment_block` is followed by the blocks for the out-of-order data held.
+	///
+	/// Returns `None` if there is no duplicate selective acknowledgment block and no data held, or if `maximum_number_of_blocks` is zero.
+	#[inline(always)]
+	pub fn selective_acknowledgment_option(&self, duplicate_selective_acknowledgment_block: Option<SelectiveAcknowledgmentBlock>, maximum_number_of_blocks: usize) -> Option<SelectiveAcknowledgmentOption>
+	{
+		const MaximumNumberOfBlocks: usize = 4;
+		
+		let maximum_number_of_blocks = min(maximum_number_of_blocks, MaximumNumberOfBlocks);
+		
+		const Unused: SelectiveAcknowledgmentBlock = SelectiveAcknowledgmentBlock
+		{
+			left_edge_of_block: WrappingSequenceNumber::Zero,
+			right_edge_of_block: WrappingSequenceNumber::Zero,
+		};
+		
+		let mut blocks = [Unused; MaximumNumberOfBlocks];
+		let mut number_of_blocks = 0;
+		
+		if let Some(duplicate_selective_acknowledgment_block) = duplicate_selective_acknowledgment_block
+		{
+			if maximum_number_of_blocks != 0
+			{
+				blocks[0] = duplicate_selective_acknowledgment_block;
+				number_of_blocks = 1;
+			}
+		}
+		
+		let mut included = [false; ReassemblyQueue::MaximumRanges];
+		
+		{
+			let mut include = |index: usize, range: &ReassemblyRange|
+			{
+				if number_of_blocks == maximum_number_of_blocks || included[index]
+				{
+					return
+				}
+				
+				included[index] = true;
+				blocks[number_of_blocks] = SelectiveAcknowledgmentBlock
+				{
+					left_edge_of_block: range.starts_at,
+					right_edge_of_block: range.ends_at,
+				};
+				number_of_blocks += 1;
+			};
+			
+			for recently_held in self.recently_held[ .. self.number_recently_held].iter()
+			{
+				let recently_held = *recently_held;
+				if let Some(index) = self.ranges().iter().position(|range| range.starts_at <= recently_held && recently_held < range.ends_at)
+				{
+					include(index, &self.ranges[index]);
+				}
+			}
+			
+			for (index, range) in self.ranges().iter().enumerate().rev()
+			{
+				include(index, range);
+			}
+		}
+		
+		SelectiveAcknowledgmentOption::from_blocks(&blocks[ .. number_of_blocks])
+	}
+	
+	/// Releases data which has become contiguous now that `RCV.NXT` has advanced to `RCV_NXT`; data before `RCV_NXT` is discarded.
+	///
+	/// `deliver` is called for each contiguous slice of released data, in order.
+	///
+	/// Returns the new value of `RCV.NXT`.
+	#[inline(always)]
+	pub fn release<Deliver: FnMut(&[u8])>(&mut self, RCV_NXT: WrappingSequenceNumber, mut deliver: Deliver) -> WrappingSequenceNumber
+	{
+		let mut RCV_NXT = RCV_NXT;
+		
+		let mut number_of_ranges_released = 0;
+		while number_of_ranges_released < self.number_of_ranges
+		{
+			let range = self.ranges[number_of_ranges_released];
+			if range.starts_at > RCV_NXT
+			{
+				break
+			}
+			
+			if range.ends_at > RCV_NXT
+			{
+				self.buffer.as_ref().unwrap().read(RCV_NXT, (range.ends_at - RCV_NXT) as usize, &mut deliver);
+				RCV_NXT = range.ends_at;
+			}
+			
+			number_of_ranges_released += 1;
+		}
+		
+		self.remove_ranges(0, number_of_ranges_released);
+		
+		if self.is_empty()
+		{
+			self.buffer = None;
+			self.number_recently_held = 0;
+		}
+		
+		RCV_NXT
+	}
+	
+	/// Entries which no longer fall within a held range are harmless, and are discarded as newer entries are recorded.
+	#[inline(always)]
+	fn record_recently_held(&mut self, SEG_SEQ: WrappingSequenceNumber)
+	{
+		let number_to_keep = min(self.number_recently_held, Self::MaximumRanges - 1);
+		
+		let mut index = number_to_keep;
+		while index > 0
+		{
+			self.recently_held[index] = self.recently_held[index - 1];
+			index -= 1;
+		}
+		self.recently_held[0] = SEG_SEQ;
+		self.number_recently_held = number_to_keep + 1;
+	}
+	
+	#[inline(always)]
+	fn remove_ranges(&mut self, from_index: usize, count: usize)
+	{
+		if count == 0
+		{
+			return
+		}
+		
+		let mut index = from_index;
+		while index + count < self.number_of_ranges
+		{
+			self.ranges[index] = self.ranges[index + count];
+			index += 1;
+		}
+		self.number_of_ranges -= count;
+	}
+}
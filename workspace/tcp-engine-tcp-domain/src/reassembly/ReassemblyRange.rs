@@ -0,0 +1,30 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// A contiguous range of out-of-order data held in a reassembly queue.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReassemblyRange
+{
+	/// Sequence number of the first byte.
+	pub starts_at: WrappingSequenceNumber,
+	
+	/// Sequence number after the last byte (ie this is exclusive).
+	pub ends_at: WrappingSequenceNumber,
+}
+
+impl ReassemblyRange
+{
+	const Unused: Self = ReassemblyRange
+	{
+		starts_at: WrappingSequenceNumber::Zero,
+		ends_at: WrappingSequenceNumber::Zero,
+	};
+	
+	/// Length in bytes.
+	#[inline(always)]
+	pub fn length(&self) -> u32
+	{
+		self.ends_at - self.starts_at
+	}
+}
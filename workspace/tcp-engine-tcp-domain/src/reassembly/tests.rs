@@ -0,0 +1,307 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+use super::*;
+
+
+const BufferSize: usize = 32;
+
+/// Larger than `BufferSize`, so that only the per-connection limit applies.
+const LargeWindow: WindowSize = WindowSize::new(1024);
+
+fn reassembly_queue(number_of_buffers: usize) -> ReassemblyQueue
+{
+	ReassemblyQueue::new(ReassemblyBuffersArena::new(number_of_buffers, BufferSize))
+}
+
+fn sequence_number(value: u32) -> WrappingSequenceNumber
+{
+	WrappingSequenceNumber::from(value)
+}
+
+fn range(starts_at: u32, ends_at: u32) -> ReassemblyRange
+{
+	ReassemblyRange
+	{
+		starts_at: sequence_number(starts_at),
+		ends_at: sequence_number(ends_at),
+	}
+}
+
+/// Returns the new value of `RCV.NXT` and the data released, concatenated.
+#[allow(non_snake_case)]
+fn release(reassembly_queue: &mut ReassemblyQueue, RCV_NXT: WrappingSequenceNumber) -> (WrappingSequenceNumber, Vec<u8>)
+{
+	let mut released = Vec::new();
+	let RCV_NXT = reassembly_queue.release(RCV_NXT, |data| released.extend_from_slice(data));
+	(RCV_NXT, released)
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn adjacent_data_is_coalesced_and_released_once_contiguous()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(100);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(101), b"b"));
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(102), b"cd"));
+	assert_eq!(reassembly_queue.ranges(), &[range(101, 104)]);
+	
+	// A hole remains before the held data, so nothing is released.
+	assert_eq!(release(&mut reassembly_queue, RCV_NXT), (RCV_NXT, vec![]));
+	assert!(!reassembly_queue.is_empty());
+	
+	// The hole is filled by in-order data, so `RCV.NXT` has advanced to the start of the held data.
+	assert_eq!(release(&mut reassembly_queue, sequence_number(101)), (sequence_number(104), b"bcd".to_vec()));
+	assert!(reassembly_queue.is_empty());
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn overlapping_data_keeps_the_data_which_arrived_first()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(0);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(2), b"XX"));
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(1), b"abcde"));
+	assert_eq!(reassembly_queue.ranges(), &[range(1, 6)]);
+	
+	assert_eq!(release(&mut reassembly_queue, sequence_number(1)), (sequence_number(6), b"aXXde".to_vec()));
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn data_before_the_advanced_RCV_NXT_is_discarded_on_release()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(0);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(2), b"cdefg"));
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(10), b"k"));
+	
+	// In-order data has advanced `RCV.NXT` part way into the first range.
+	assert_eq!(release(&mut reassembly_queue, sequence_number(4)), (sequence_number(7), b"efg".to_vec()));
+	assert_eq!(reassembly_queue.ranges(), &[range(10, 11)]);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn data_which_fills_a_hole_between_ranges_merges_them()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(0);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(6), b"g"));
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(2), b"c"));
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(10), b"k"));
+	assert_eq!(reassembly_queue.ranges(), &[range(2, 3), range(6, 7), range(10, 11)], "ranges are not in ascending order");
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(3), b"def"));
+	assert_eq!(reassembly_queue.ranges(), &[range(2, 7), range(10, 11)]);
+	
+	assert_eq!(release(&mut reassembly_queue, sequence_number(2)), (sequence_number(7), b"cdefg".to_vec()));
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn no_more_than_the_maximum_number_of_ranges_are_held()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(0);
+	
+	for index in 0 .. ReassemblyQueue::MaximumRanges as u32
+	{
+		assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(2 + index * 2), b"x"));
+	}
+	assert_eq!(reassembly_queue.ranges().len(), ReassemblyQueue::MaximumRanges);
+	
+	let after_last_range = 2 + (ReassemblyQueue::MaximumRanges as u32) * 2;
+	assert!(!reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(after_last_range), b"y"), "a new range was held beyond MaximumRanges");
+	
+	// Data which merges with existing ranges needs no new range.
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(3), b"z"));
+	assert_eq!(reassembly_queue.ranges().len(), ReassemblyQueue::MaximumRanges - 1);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(after_last_range), b"y"));
+	assert_eq!(reassembly_queue.ranges().len(), ReassemblyQueue::MaximumRanges);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn data_beyond_the_size_of_a_reassembly_buffer_is_not_held()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(0);
+	
+	assert!(!reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(BufferSize as u32), b"x"));
+	assert!(reassembly_queue.is_empty());
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(BufferSize as u32 - 2), b"abcd"));
+	assert_eq!(reassembly_queue.ranges(), &[range(BufferSize as u32 - 2, BufferSize as u32)], "data was not trimmed to the per-connection limit");
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn data_beyond_the_receive_window_is_not_held()
+{
+	const RCV_WND: WindowSize = WindowSize::new(8);
+	
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(1000);
+	
+	assert!(!reassembly_queue.hold(RCV_NXT, RCV_WND, sequence_number(1008), b"x"));
+	assert!(reassembly_queue.is_empty());
+	
+	assert!(reassembly_queue.hold(RCV_NXT, RCV_WND, sequence_number(1006), b"abcd"));
+	assert_eq!(reassembly_queue.ranges(), &[range(1006, 1008)], "data was not trimmed to the receive window");
+	
+	assert!(!reassembly_queue.hold(RCV_NXT, WindowSize::Zero, sequence_number(1001), b"x"), "data was held despite a zero window");
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn a_reassembly_buffer_is_only_taken_whilst_data_is_held()
+{
+	let mut first = reassembly_queue(1);
+	let mut second = ReassemblyQueue::new(first.arena.clone());
+	let RCV_NXT = sequence_number(0);
+	
+	assert!(first.hold(RCV_NXT, LargeWindow, sequence_number(1), b"a"));
+	assert!(!second.hold(RCV_NXT, LargeWindow, sequence_number(1), b"a"), "the per-Interface limit was exceeded");
+	assert!(second.is_empty());
+	
+	assert_eq!(release(&mut first, sequence_number(1)), (sequence_number(2), b"a".to_vec()));
+	assert!(second.hold(RCV_NXT, LargeWindow, sequence_number(1), b"a"), "the reassembly buffer was not returned once the queue emptied");
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn sequence_numbers_which_wrap_around_are_held_and_released_in_order()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	let RCV_NXT = sequence_number(::std::u32::MAX - 2);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(::std::u32::MAX - 1), b"abcd"));
+	assert_eq!(reassembly_queue.ranges(), &[range(::std::u32::MAX - 1, 2)]);
+	
+	assert!(reassembly_queue.hold(RCV_NXT, LargeWindow, sequence_number(2), b"ef"));
+	assert_eq!(reassembly_queue.ranges(), &[range(::std::u32::MAX - 1, 4)]);
+	
+	assert_eq!(release(&mut reassembly_queue, sequence_number(::std::u32::MAX - 1)), (sequence_number(4), b"abcdef".to_vec()));
+}
+
+fn block(left_edge_of_block: u32, right_edge_of_block: u32) -> SelectiveAcknowledgmentBlock
+{
+	SelectiveAcknowledgmentBlock
+	{
+		left_edge_of_block: sequence_number(left_edge_of_block),
+		right_edge_of_block: sequence_number(right_edge_of_block),
+	}
+}
+
+fn blocks(selective_acknowledgment_option: SelectiveAcknowledgmentOption) -> Vec<SelectiveAcknowledgmentBlock>
+{
+	let mut blocks = vec![selective_acknowledgment_option.first_block()];
+	let number_of_blocks = selective_acknowledgment_option.number_of_blocks();
+	if number_of_blocks >= 2
+	{
+		blocks.push(selective_acknowledgment_option.second_block())
+	}
+	if number_of_blocks >= 3
+	{
+		blocks.push(selective_acknowledgment_option.third_block())
+	}
+	if number_of_blocks == 4
+	{
+		blocks.push(selective_acknowledgment_option.fourth_block())
+	}
+	blocks
+}
+
+#[allow(non_snake_case)]
+fn hold_at(reassembly_queue: &mut ReassemblyQueue, SEG_SEQ: u32)
+{
+	assert!(reassembly_queue.hold(sequence_number(0), LargeWindow, sequence_number(SEG_SEQ), b"x"));
+}
+
+#[test]
+fn no_selective_acknowledgment_option_is_created_when_nothing_is_held()
+{
+	let reassembly_queue = reassembly_queue(1);
+	
+	assert_eq!(reassembly_queue.selective_acknowledgment_option(None, 4), None);
+}
+
+#[test]
+fn no_selective_acknowledgment_option_is_created_when_no_blocks_fit()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	hold_at(&mut reassembly_queue, 10);
+	
+	assert_eq!(reassembly_queue.selective_acknowledgment_option(Some(block(1, 2)), 0), None);
+}
+
+#[test]
+fn selective_acknowledgment_blocks_are_most_recently_held_first()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	hold_at(&mut reassembly_queue, 10);
+	hold_at(&mut reassembly_queue, 20);
+	hold_at(&mut reassembly_queue, 30);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(None, 4).unwrap();
+	assert_eq!(blocks(selective_acknowledgment_option), vec![block(30, 31), block(20, 21), block(10, 11)]);
+}
+
+#[test]
+fn the_first_selective_acknowledgment_block_contains_the_segment_which_triggered_the_acknowledgment()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	hold_at(&mut reassembly_queue, 10);
+	hold_at(&mut reassembly_queue, 20);
+	hold_at(&mut reassembly_queue, 30);
+	
+	// Extends the lowest block, which then contains the most recently held segment.
+	hold_at(&mut reassembly_queue, 11);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(None, 4).unwrap();
+	assert_eq!(blocks(selective_acknowledgment_option), vec![block(10, 12), block(30, 31), block(20, 21)]);
+}
+
+#[test]
+fn only_the_most_recently_held_selective_acknowledgment_blocks_are_reported_when_not_all_fit()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	hold_at(&mut reassembly_queue, 10);
+	hold_at(&mut reassembly_queue, 20);
+	hold_at(&mut reassembly_queue, 30);
+	hold_at(&mut reassembly_queue, 40);
+	hold_at(&mut reassembly_queue, 50);
+	hold_at(&mut reassembly_queue, 21);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(None, 3).unwrap();
+	assert_eq!(blocks(selective_acknowledgment_option), vec![block(20, 22), block(50, 51), block(40, 41)]);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(None, 8).unwrap();
+	assert_eq!(selective_acknowledgment_option.number_of_blocks(), 4, "more than the four blocks permitted by RFC 2018 were reported");
+}
+
+#[test]
+fn a_duplicate_selective_acknowledgment_block_is_always_first()
+{
+	let mut reassembly_queue = reassembly_queue(1);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(Some(block(1, 2)), 4).unwrap();
+	assert_eq!(blocks(selective_acknowledgment_option), vec![block(1, 2)]);
+	
+	hold_at(&mut reassembly_queue, 10);
+	hold_at(&mut reassembly_queue, 20);
+	hold_at(&mut reassembly_queue, 30);
+	hold_at(&mut reassembly_queue, 40);
+	
+	let selective_acknowledgment_option = reassembly_queue.selective_acknowledgment_option(Some(block(1, 2)), 4).unwrap();
+	assert_eq!(blocks(selective_acknowledgment_option), vec![block(1, 2), block(40, 41), block(30, 31), block(20, 21)]);
+}
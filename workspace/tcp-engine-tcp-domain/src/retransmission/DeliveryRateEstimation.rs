@@ -0,0 +1,124 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Delivery rate estimation, as specified by draft-cheng-iccrg-delivery-rate-estimation, using the transmission timestamps of the segments in the retransmission queue.
+///
+/// Data is counted as delivered when it is cumulatively acknowledged.
+///
+/// Segments which have been retransmitted are counted as delivered but are not used to generate a rate sample, as (like Karn's algorithm for round trip times) it is ambiguous which transmission was acknowledged.
+#[derive(Default, Debug)]
+pub(crate) struct DeliveryRateEstimation
+{
+	/// "C.delivered": The total amount of data, in bytes, delivered so far.
+	delivered: u64,
+	
+	/// "C.delivered_time": The time at which `delivered` was last updated.
+	delivered_time: Option<MonotonicMillisecondTimestamp>,
+	
+	/// "C.first_sent_time": The transmission time of the segment most recently acknowledged; the start of the 'send' phase of the current sample.
+	first_sent_time: Option<MonotonicMillisecondTimestamp>,
+	
+	/// "C.app_limited": If non-zero, the value of `delivered` at which the sender will no longer be application limited.
+	application_limited: u64,
+	
+	/// The transmission time and snapshot of the most recently transmitted segment delivered by the acknowledgment being processed.
+	most_recently_transmitted_segment_delivered: Option<(MonotonicMillisecondTimestamp, DeliveryRateSnapshot)>,
+}
+
+impl DeliveryRateEstimation
+{
+	/// draft-cheng-iccrg-delivery-rate-estimation Section 3.2 "Transmitting a data packet".
+	#[inline(always)]
+	pub(crate) fn transmitted(&mut self, now: MonotonicMillisecondTimestamp, nothing_in_flight: bool) -> DeliveryRateSnapshot
+	{
+		// "If there are no packets in flight yet, then we can start the delivery rate interval at the current time, since we know that any ACKs after now indicate that the network was able to deliver those packets completely in the sampling interval between now and the next ACK".
+		if nothing_in_flight || self.delivered_time.is_none()
+		{
+			self.first_sent_time = Some(now);
+			self.delivered_time = Some(now);
+		}
+		
+		DeliveryRateSnapshot
+		{
+			delivered: self.delivered,
+			delivered_time: self.delivered_time.unwrap(),
+			first_sent_time: self.first_sent_time.unwrap(),
+			is_application_limited: self.application_limited != 0,
+		}
+	}
+	
+	/// draft-cheng-iccrg-delivery-rate-estimation Section 3.3 "Upon receiving ACK": "For each newly SACKed or ACKed packet P".
+	///
+	/// `transmission` is `None` for a segment which has been retransmitted.
+	#[inline(always)]
+	pub(crate) fn delivered(&mut self, bytes_delivered: u32, transmission: Option<(MonotonicMillisecondTimestamp, DeliveryRateSnapshot)>, now: MonotonicMillisecondTimestamp)
+	{
+		self.delivered += bytes_delivered as u64;
+		self.delivered_time = Some(now);
+		
+		if let Some((sent_time, snapshot)) = transmission
+		{
+			// "Update info using the newest packet".
+			let is_newest = match self.most_recently_transmitted_segment_delivered
+			{
+				None => true,
+				Some((_, ref most_recently_transmitted_segment_delivered)) => snapshot.delivered >= most_recently_transmitted_segment_delivered.delivered,
+			};
+			
+			if is_newest
+			{
+				self.most_recently_transmitted_segment_delivered = Some((sent_time, snapshot));
+				self.first_sent_time = Some(sent_time);
+			}
+		}
+	}
+	
+	/// draft-cheng-iccrg-delivery-rate-estimation Section 3.3 "Upon receiving ACK": "After processing all newly SACKed or ACKed packets for this ACK ... generate a rate sample".
+	///
+	/// Returns `None` if there is no valid sample.
+	#[inline(always)]
+	pub(crate) fn generate_rate_sample(&mut self) -> Option<DeliveryRateSample>
+	{
+		// "Clear app-limited field if bubble is ACKed and gone".
+		if self.application_limited != 0 && self.delivered > self.application_limited
+		{
+			self.application_limited = 0;
+		}
+		
+		let (sent_time, snapshot) = self.most_recently_transmitted_segment_delivered.take()?;
+		
+		let delivered_time = self.delivered_time.unwrap();
+		
+		// "Use the longer of the send_elapsed and ack_elapsed".
+		let send_elapsed: u64 = (sent_time - snapshot.first_sent_time).into();
+		let ack_elapsed: u64 = (delivered_time - snapshot.delivered_time).into();
+		let interval = max(send_elapsed, ack_elapsed);
+		
+		// An interval shorter than the minimum round trip time usually indicates compressed acknowledgments and so over-estimates the delivery rate.
+		// The minimum round trip time is known to congestion control algorithms, which should discard such samples; a sample with a zero interval is always discarded here.
+		if interval == 0
+		{
+			return None
+		}
+		
+		Some
+		(
+			DeliveryRateSample
+			{
+				delivered: self.delivered - snapshot.delivered,
+				interval,
+				prior_delivered: snapshot.delivered,
+				total_delivered: self.delivered,
+				is_application_limited: snapshot.is_application_limited,
+			}
+		)
+	}
+	
+	/// draft-cheng-iccrg-delivery-rate-estimation Section 3.4 "Detecting application-limited phases": "... mark the flow as application-limited ... C.app_limited = (C.delivered + C.inflight) ? : 1".
+	#[inline(always)]
+	pub(crate) fn application_limited(&mut self, bytes_in_flight: u32)
+	{
+		self.application_limited = max(self.delivered + bytes_in_flight as u64, 1)
+	}
+}
@@ -0,0 +1,67 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// draft-cheng-iccrg-delivery-rate-estimation Section 3.3: A delivery rate sample, generated when an acknowledgment delivers data.
+#[derive(Debug, Copy, Clone)]
+pub struct DeliveryRateSample
+{
+	delivered: u64,
+	
+	interval: u64,
+	
+	prior_delivered: u64,
+	
+	total_delivered: u64,
+	
+	is_application_limited: bool,
+}
+
+impl DeliveryRateSample
+{
+	/// "rs.delivered": The amount of data, in bytes, delivered over the sampled interval.
+	#[inline(always)]
+	pub fn delivered(&self) -> u64
+	{
+		self.delivered
+	}
+	
+	/// "rs.interval": The length of the sampled interval, in milliseconds; never zero.
+	#[inline(always)]
+	pub fn interval_in_milliseconds(&self) -> u64
+	{
+		self.interval
+	}
+	
+	/// "rs.delivery_rate = rs.delivered / rs.interval", in bytes per second.
+	#[inline(always)]
+	pub fn delivery_rate(&self) -> u64
+	{
+		self.delivered.saturating_mul(1000) / self.interval
+	}
+	
+	/// "rs.prior_delivered": The total amount of data, in bytes, delivered when the most recently transmitted of the segments acknowledged was sent.
+	///
+	/// Congestion control algorithms can use this to count round trips: a round trip ends when data sent after the start of the round is delivered.
+	#[inline(always)]
+	pub fn prior_delivered(&self) -> u64
+	{
+		self.prior_delivered
+	}
+	
+	/// "C.delivered": The total amount of data, in bytes, delivered over the lifetime of the connection, including that acknowledged by this acknowledgment.
+	#[inline(always)]
+	pub fn total_delivered(&self) -> u64
+	{
+		self.total_delivered
+	}
+	
+	/// "rs.is_app_limited": Was the sender application limited when the most recently transmitted of the segments acknowledged was sent?
+	///
+	/// If so, the delivery rate is likely to be below the available bandwidth.
+	#[inline(always)]
+	pub fn is_application_limited(&self) -> bool
+	{
+		self.is_application_limited
+	}
+}
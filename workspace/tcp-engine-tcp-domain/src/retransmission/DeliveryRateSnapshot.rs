@@ -0,0 +1,20 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// draft-cheng-iccrg-delivery-rate-estimation Section 3.2: "Upon transmitting ... the sender stores in the per-packet data the snapshot of the current connection delivery information".
+#[derive(Debug, Copy, Clone)]
+pub struct DeliveryRateSnapshot
+{
+	/// "P.delivered".
+	delivered: u64,
+	
+	/// "P.delivered_time".
+	delivered_time: MonotonicMillisecondTimestamp,
+	
+	/// "P.first_sent_time".
+	first_sent_time: MonotonicMillisecondTimestamp,
+	
+	/// "P.is_app_limited".
+	is_application_limited: bool,
+}
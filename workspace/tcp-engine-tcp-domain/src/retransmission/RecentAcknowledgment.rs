@@ -0,0 +1,225 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// RACK, 'Recent ACKnowledgment' (RFC 8985), time-based loss detection.
+///
+/// A segment is considered lost if a segment transmitted after it has been delivered (acknowledged or selectively acknowledged), and more than a round trip time plus a reordering window has passed since it was transmitted.
+///
+/// Unlike counting duplicate acknowledgments, this detects the loss of retransmissions, and the loss of segments at the tail of a flight when combined with a tail loss probe.
+#[derive(Debug)]
+pub(crate) struct RecentAcknowledgment
+{
+	/// RFC 8985 Section 6.1 "RACK.xmit_ts": The latest transmission time of the most recently transmitted segment which has been delivered.
+	transmitted_at: Option<MonotonicMillisecondTimestamp>,
+	
+	/// RFC 8985 Section 6.1 "RACK.end_seq": The sequence number after the end of the segment recorded in `transmitted_at`.
+	ends_at: WrappingSequenceNumber,
+	
+	/// RFC 8985 Section 6.1 "RACK.fack": The highest sequence number (exclusive) delivered so far.
+	highest_delivered: Option<WrappingSequenceNumber>,
+	
+	/// RFC 8985 Section 6.1 "RACK.rtt": The round trip time of the segment recorded in `transmitted_at`.
+	round_trip_time: MillisecondDuration,
+	
+	/// RFC 8985 Section 6.1 "RACK.min_RTT": The minimum round trip time measured so far.
+	minimum_round_trip_time: Option<MillisecondDuration>,
+	
+	/// RFC 8985 Section 6.1 "RACK.reordering_seen": Has a segment been delivered after a segment with a higher sequence number was?
+	reordering_seen: bool,
+	
+	/// RFC 8985 Section 6.1 "RACK.reo_wnd_mult": The multiplier of a quarter of the minimum round trip time used for the reordering window.
+	reordering_window_multiplier: u32,
+	
+	/// RFC 8985 Section 6.1 "RACK.reo_wnd_persist": The number of loss recovery episodes for which an increased `reordering_window_multiplier` is kept.
+	reordering_window_persist: u32,
+	
+	/// RFC 8985 Section 6.1 "RACK.dsack_round": `SND.NXT` when the reordering window was last increased because of a D-SACK block; it is only increased once per round trip.
+	duplicate_selective_acknowledgment_round: Option<WrappingSequenceNumber>,
+}
+
+impl Default for RecentAcknowledgment
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			transmitted_at: None,
+			ends_at: WrappingSequenceNumber::Zero,
+			highest_delivered: None,
+			round_trip_time: MillisecondDuration::from_milliseconds(0),
+			minimum_round_trip_time: None,
+			reordering_seen: false,
+			reordering_window_multiplier: 1,
+			reordering_window_persist: Self::ReorderingWindowPersist,
+			duplicate_selective_acknowledgment_round: None,
+		}
+	}
+}
+
+impl RecentAcknowledgment
+{
+	/// RFC 8985 Section 6.2 Step 4: The number of loss recovery episodes for which a reordering window increased because of a D-SACK block is kept.
+	const ReorderingWindowPersist: u32 = 16;
+	
+	/// RFC 6675 Section 2 'DupThresh'.
+	const DuplicateAcknowledgmentThreshold: usize = 3;
+	
+	/// RFC 8985 Section 6.2 Steps 2 and 3: A segment has been delivered, ie it has been fully acknowledged, cumulatively or selectively, by the acknowledgment being processed.
+	///
+	/// `round_trip_time_measured_using_timestamps` is the round trip time measured from the timestamp echoed by the acknowledgment, if timestamps are in use; it is used to tell if the acknowledgment was for the original transmission of a segment which has been retransmitted.
+	#[inline(always)]
+	pub(crate) fn delivered(&mut self, now: MonotonicMillisecondTimestamp, segment_transmitted_at: MonotonicMillisecondTimestamp, segment_ends_at: WrappingSequenceNumber, segment_has_been_retransmitted: bool, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>)
+	{
+		debug_assert!(now >= segment_transmitted_at, "time has run backwards");
+		let round_trip_time = now - segment_transmitted_at;
+		
+		// Step 2: If the segment has been retransmitted, it is ambiguous which transmission was delivered; if the acknowledgment is for the original transmission, using the time of the retransmission would under-estimate the round trip time.
+		let is_ambiguous = segment_has_been_retransmitted &&
+		{
+			let echoed_timestamp_is_from_before_retransmission = match round_trip_time_measured_using_timestamps
+			{
+				None => false,
+				Some(round_trip_time_measured_using_timestamps) => round_trip_time_measured_using_timestamps > round_trip_time,
+			};
+			
+			let shorter_than_minimum_round_trip_time = match self.minimum_round_trip_time
+			{
+				None => false,
+				Some(minimum_round_trip_time) => round_trip_time < minimum_round_trip_time,
+			};
+			
+			echoed_timestamp_is_from_before_retransmission || shorter_than_minimum_round_trip_time
+		};
+		
+		if !is_ambiguous
+		{
+			self.minimum_round_trip_time = Some
+			(
+				match self.minimum_round_trip_time
+				{
+					None => round_trip_time,
+					Some(minimum_round_trip_time) => min(minimum_round_trip_time, round_trip_time),
+				}
+			);
+			
+			if self.is_sent_after_most_recently_delivered(segment_transmitted_at, segment_ends_at)
+			{
+				self.transmitted_at = Some(segment_transmitted_at);
+				self.ends_at = segment_ends_at;
+				self.round_trip_time = round_trip_time;
+			}
+		}
+		
+		// Step 3: A segment delivered below the highest sequence number already delivered, and which was not retransmitted, was reordered.
+		match self.highest_delivered
+		{
+			Some(highest_delivered) if segment_ends_at <= highest_delivered =>
+			{
+				if segment_ends_at < highest_delivered && !segment_has_been_retransmitted
+				{
+					self.reordering_seen = true;
+				}
+			}
+			
+			_ => self.highest_delivered = Some(segment_ends_at),
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A D-SACK block has been received; the reordering window is increased, at most once per round trip.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub(crate) fn duplicate_selective_acknowledgment_received(&mut self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber)
+	{
+		if let Some(duplicate_selective_acknowledgment_round) = self.duplicate_selective_acknowledgment_round
+		{
+			if SND_UNA >= duplicate_selective_acknowledgment_round
+			{
+				self.duplicate_selective_acknowledgment_round = None;
+			}
+		}
+		
+		if self.duplicate_selective_acknowledgment_round.is_none()
+		{
+			self.duplicate_selective_acknowledgment_round = Some(SND_NXT);
+			self.reordering_window_multiplier = self.reordering_window_multiplier.saturating_add(1);
+			self.reordering_window_persist = Self::ReorderingWindowPersist;
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A loss recovery episode has ended; after `ReorderingWindowPersist` episodes, any increase of the reordering window is forgotten.
+	#[inline(always)]
+	pub(crate) fn loss_recovery_exited(&mut self)
+	{
+		self.reordering_window_persist = self.reordering_window_persist.saturating_sub(1);
+		if self.reordering_window_persist == 0
+		{
+			self.reordering_window_multiplier = 1;
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: "RACK.reo_wnd".
+	///
+	/// Until reordering has been seen, there is no reordering window during loss recovery or once enough segments have been selectively acknowledged to indicate loss by counting them; this makes loss detection no slower than counting duplicate acknowledgments.
+	///
+	/// Otherwise it is a quarter of the minimum round trip time (multiplied after D-SACK blocks), but no more than `SRTT`.
+	#[inline(always)]
+	pub(crate) fn reordering_window(&self, is_in_loss_recovery: bool, number_of_segments_selectively_acknowledged: usize, smoothed_round_trip_time: MillisecondDuration) -> MillisecondDuration
+	{
+		if !self.reordering_seen && (is_in_loss_recovery || number_of_segments_selectively_acknowledged >= Self::DuplicateAcknowledgmentThreshold)
+		{
+			return MillisecondDuration::from_milliseconds(0)
+		}
+		
+		match self.minimum_round_trip_time
+		{
+			None => MillisecondDuration::from_milliseconds(0),
+			
+			Some(minimum_round_trip_time) =>
+			{
+				let minimum_round_trip_time: u64 = minimum_round_trip_time.into();
+				let reordering_window = MillisecondDuration::from_milliseconds((self.reordering_window_multiplier as u64) * minimum_round_trip_time / 4);
+				min(reordering_window, smoothed_round_trip_time)
+			}
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 Step 5: How long until an undelivered segment is considered lost.
+	///
+	/// Returns `None` if no segment transmitted after it has been delivered, so it can not (yet) be considered lost; returns zero if it is lost.
+	#[inline(always)]
+	pub(crate) fn time_until_lost(&self, segment_transmitted_at: MonotonicMillisecondTimestamp, segment_ends_at: WrappingSequenceNumber, reordering_window: MillisecondDuration, now: MonotonicMillisecondTimestamp) -> Option<MillisecondDuration>
+	{
+		let transmitted_at = self.transmitted_at?;
+		
+		if !Self::is_sent_after(transmitted_at, self.ends_at, segment_transmitted_at, segment_ends_at)
+		{
+			return None
+		}
+		
+		let elapsed: u64 = (now - segment_transmitted_at).into();
+		let round_trip_time: u64 = self.round_trip_time.into();
+		let reordering_window: u64 = reordering_window.into();
+		let lost_after = round_trip_time + reordering_window;
+		
+		Some(MillisecondDuration::from_milliseconds(lost_after.saturating_sub(elapsed)))
+	}
+	
+	#[inline(always)]
+	fn is_sent_after_most_recently_delivered(&self, segment_transmitted_at: MonotonicMillisecondTimestamp, segment_ends_at: WrappingSequenceNumber) -> bool
+	{
+		match self.transmitted_at
+		{
+			None => true,
+			Some(transmitted_at) => Self::is_sent_after(segment_transmitted_at, segment_ends_at, transmitted_at, self.ends_at),
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 "RACK_sent_after()": Segments transmitted at the same time are ordered by sequence number.
+	#[inline(always)]
+	fn is_sent_after(transmitted_at: MonotonicMillisecondTimestamp, ends_at: WrappingSequenceNumber, other_transmitted_at: MonotonicMillisecondTimestamp, other_ends_at: WrappingSequenceNumber) -> bool
+	{
+		transmitted_at > other_transmitted_at || (transmitted_at == other_transmitted_at && ends_at > other_ends_at)
+	}
+}
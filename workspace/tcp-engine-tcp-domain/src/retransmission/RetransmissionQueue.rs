@@ -3,12 +3,24 @@
 
 
 /// A retransmission queue.
-#[repr(C, packed)]
+#[repr(C)]
 pub struct RetransmissionQueue
 {
 	queue: ManuallyDrop<[RetransmissionSegment; RetransmissionQueue::MaximumDepth]>,
 	start: usize,
 	depth: usize,
+	
+	selective_acknowledgment_scoreboard: SelectiveAcknowledgmentScoreboard,
+	
+	/// RFC 6675 Section 2: "HighRxt: The highest sequence number which has been retransmitted during the current loss recovery phase".
+	///
+	/// Held as the sequence number after the end of the highest retransmitted segment.
+	HighRxt: Option<WrappingSequenceNumber>,
+	
+	delivery_rate_estimation: DeliveryRateEstimation,
+	
+	/// `None` if losses are detected by counting duplicate acknowledgments (RFC 6675 Section 4 IsLost) rather than by RACK (RFC 8985).
+	recent_acknowledgment: Option<RecentAcknowledgment>,
 }
 
 impl Drop for RetransmissionQueue
@@ -50,17 +62,27 @@ impl Default for RetransmissionQueue
 			queue: ManuallyDrop::new(unsafe { uninitialized() }),
 			start: 0,
 			depth: 0,
+			selective_acknowledgment_scoreboard: SelectiveAcknowledgmentScoreboard::default(),
+			HighRxt: None,
+			delivery_rate_estimation: DeliveryRateEstimation::default(),
+			recent_acknowledgment: None,
 		}
 	}
 }
 
 impl RetransmissionQueue
 {
-	const MaximumDepth: usize = 32;
+	pub(crate) const MaximumDepth: usize = 32;
 	
 	/// Acknowledged.
+	///
+	/// `acknowledgment_had_explicit_congestion_echo` is whether the acknowledgment had the explicit congestion echo (ECE) flag set; the `bool` returned is whether any acknowledgment of the segments fully acknowledged had it set.
+	///
+	/// A delivery rate sample can then be obtained using `delivery_rate_sample()`.
+	///
+	/// `round_trip_time_measured_using_timestamps` is only used by RACK (RFC 8985); see `RecentAcknowledgment.delivered()`.
 	#[inline(always)]
-	pub fn acknowledged(&mut self, sequence_numbers_length: u32) -> Result<(u32, Option<MonotonicMillisecondTimestamp>, bool, bool), TooManySequenceNumbersAcknowledgedError>
+	pub fn acknowledged(&mut self, sequence_numbers_length: u32, acknowledgment_had_explicit_congestion_echo: bool, now: MonotonicMillisecondTimestamp, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>) -> Result<(u32, Option<MonotonicMillisecondTimestamp>, bool, bool), TooManySequenceNumbersAcknowledgedError>
 	{
 		use self::RetransmissionSegmentDecreaseSequenceNumberLengthOutcome::*;
 		use self::TooManySequenceNumbersAcknowledgedError::*;
@@ -80,25 +102,52 @@ impl RetransmissionQueue
 				return Err(TooManySequenceNumbersAcknowledged)
 			}
 			
-			let outcome =
+			let (bytes_acknowledged, outcome, delivery_rate_transmission, recent_acknowledgment_delivery) =
 			{
 				let retransmission_segment = self.oldest();
-				let (bytes_acknowledged, outcome) = retransmission_segment.decrease_sequence_number_length(total_remaining_sequence_number_length, explicit_congestion_echo)?;
+				let ends_at = retransmission_segment.ends_at();
+				let (bytes_acknowledged, outcome) = retransmission_segment.decrease_sequence_number_length(total_remaining_sequence_number_length, acknowledgment_had_explicit_congestion_echo)?;
 				
-				total_bytes_acknowledged += bytes_acknowledged;
-				
-				if outcome == Partial
+				let recent_acknowledgment_delivery = if outcome != Partial
 				{
-					break
+					retransmission_segment.set_unretransmitted_segment_timestamp_if_unset(&mut unretransmitted_segment_timestamp);
+					a_window_of_data_was_processed = true;
+					retransmission_segment.set_explicit_congestion_echo(&mut explicit_congestion_echo);
+					
+					// A segment already selectively acknowledged was delivered when it was.
+					if retransmission_segment.is_selectively_acknowledged()
+					{
+						None
+					}
+					else
+					{
+						Some((retransmission_segment.transmitted_at(), ends_at, retransmission_segment.has_been_retransmitted()))
+					}
 				}
+				else
+				{
+					None
+				};
 				
-				retransmission_segment.set_unretransmitted_segment_timestamp_if_unset(&mut unretransmitted_segment_timestamp);
-				a_window_of_data_was_processed = true;
-				retransmission_segment.set_explicit_congestion_echo(&mut explicit_congestion_echo);
-				
-				outcome
+				(bytes_acknowledged, outcome, retransmission_segment.delivery_rate_transmission(), recent_acknowledgment_delivery)
 			};
 			
+			total_bytes_acknowledged += bytes_acknowledged;
+			self.delivery_rate_estimation.delivered(bytes_acknowledged, delivery_rate_transmission, now);
+			
+			if let Some(ref mut recent_acknowledgment) = self.recent_acknowledgment
+			{
+				if let Some((transmitted_at, ends_at, has_been_retransmitted)) = recent_acknowledgment_delivery
+				{
+					recent_acknowledgment.delivered(now, transmitted_at, ends_at, has_been_retransmitted, round_trip_time_measured_using_timestamps);
+				}
+			}
+			
+			if outcome == Partial
+			{
+				break
+			}
+			
 			self.dequeue();
 			
 			match outcome
@@ -114,13 +163,441 @@ impl RetransmissionQueue
 			}
 		}
 		
+		if self.is_empty()
+		{
+			self.selective_acknowledgment_scoreboard.clear();
+		}
+		else
+		{
+			let SND_UNA = self.oldest().starts_at();
+			self.selective_acknowledgment_scoreboard.cumulatively_acknowledged(SND_UNA);
+		}
+		
 		Ok((total_bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo))
 	}
 	
+	/// A delivery rate sample for the data delivered by the most recent call to `acknowledged()`, if one could be generated.
+	#[inline(always)]
+	pub fn delivery_rate_sample(&mut self) -> Option<DeliveryRateSample>
+	{
+		self.delivery_rate_estimation.generate_rate_sample()
+	}
+	
+	/// The sender is application limited, ie there is no more data to send although the congestion window and receive window would permit it.
+	///
+	/// Delivery rate samples for data sent from now until the data currently in flight is delivered are marked as application limited.
+	#[inline(always)]
+	pub fn application_limited(&mut self, bytes_in_flight: u32)
+	{
+		self.delivery_rate_estimation.application_limited(bytes_in_flight)
+	}
+	
+	/// Updates the selective acknowledgment scoreboard from an incoming `SACK` option.
+	///
+	/// Returns `true` if the option contained new information (ie it identified previously unacknowledged and un-SACKed sequence numbers); RFC 6675 Section 2 only considers an acknowledgment carrying such information to be a 'duplicate acknowledgment'.
+	///
+	/// If RACK (RFC 8985) is in use, segments which are now fully selectively acknowledged are delivered to it; `now` and `round_trip_time_measured_using_timestamps` are only used for this.
+	#[inline(always)]
+	pub fn selectively_acknowledged(&mut self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, selective_acknowledgment_option: &SelectiveAcknowledgmentOption, now: MonotonicMillisecondTimestamp, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>) -> bool
+	{
+		let had_new_information = self.selective_acknowledgment_scoreboard.selectively_acknowledged(SND_UNA, SND_NXT, selective_acknowledgment_option);
+		
+		if had_new_information && self.is_using_recent_acknowledgment_loss_detection()
+		{
+			let mut index = 0;
+			while index < self.depth
+			{
+				let delivery =
+				{
+					let retransmission_segment = self.get(index);
+					let starts_at = retransmission_segment.starts_at();
+					let ends_at = retransmission_segment.ends_at();
+					
+					if retransmission_segment.is_selectively_acknowledged() || self.selective_acknowledgment_scoreboard.number_of_sequence_numbers_selectively_acknowledged(starts_at, ends_at) != ends_at - starts_at
+					{
+						None
+					}
+					else
+					{
+						Some((retransmission_segment.transmitted_at(), ends_at, retransmission_segment.has_been_retransmitted()))
+					}
+				};
+				
+				if let Some((transmitted_at, ends_at, has_been_retransmitted)) = delivery
+				{
+					self.get_mutable_reference(index).set_selectively_acknowledged(true);
+					self.recent_acknowledgment.as_mut().unwrap().delivered(now, transmitted_at, ends_at, has_been_retransmitted, round_trip_time_measured_using_timestamps);
+				}
+				
+				index += 1;
+			}
+		}
+		
+		had_new_information
+	}
+	
+	/// How many of the sequence numbers from `SND_UNA` (inclusive) to `SND_NXT` (exclusive) have been selectively acknowledged?
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn number_of_sequence_numbers_selectively_acknowledged(&self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber) -> u32
+	{
+		self.selective_acknowledgment_scoreboard.number_of_sequence_numbers_selectively_acknowledged(SND_UNA, SND_NXT)
+	}
+	
+	/// Detect losses using RACK (RFC 8985) rather than by counting duplicate acknowledgments.
+	///
+	/// Should be called once selective acknowledgments are known to be permitted, and before any segments are transmitted.
+	#[inline(always)]
+	pub fn use_recent_acknowledgment_loss_detection(&mut self)
+	{
+		if self.recent_acknowledgment.is_none()
+		{
+			self.recent_acknowledgment = Some(RecentAcknowledgment::default())
+		}
+	}
+	
+	/// Are losses detected using RACK (RFC 8985)?
+	#[inline(always)]
+	pub fn is_using_recent_acknowledgment_loss_detection(&self) -> bool
+	{
+		self.recent_acknowledgment.is_some()
+	}
+	
+	/// RFC 8985 Section 6.2 Steps 4 and 5 "RACK_detect_loss()": Marks as lost each segment for which a segment transmitted after it has been delivered and more than `RACK.rtt` plus the reordering window has passed since it was transmitted.
+	///
+	/// Returns a tuple of `(any_segments_newly_marked_as_lost, time_out)`; if `time_out` is `Some`, a segment may be marked as lost after this time has passed (RFC 8985 Section 6.2 Step 5 "RACK_detect_loss_and_arm_timer()").
+	///
+	/// Does nothing if RACK is not in use.
+	#[inline(always)]
+	pub fn detect_losses_using_recent_acknowledgment(&mut self, now: MonotonicMillisecondTimestamp, is_in_loss_recovery: bool, smoothed_round_trip_time: MillisecondDuration) -> (bool, Option<MillisecondDuration>)
+	{
+		let reordering_window = match self.recent_acknowledgment
+		{
+			None => return (false, None),
+			
+			Some(ref recent_acknowledgment) => recent_acknowledgment.reordering_window(is_in_loss_recovery, self.number_of_segments_selectively_acknowledged(), smoothed_round_trip_time),
+		};
+		
+		let mut any_segments_newly_marked_as_lost = false;
+		let mut time_out = None;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			let time_until_lost =
+			{
+				let retransmission_segment = self.get(index);
+				if retransmission_segment.is_selectively_acknowledged() || retransmission_segment.is_lost()
+				{
+					None
+				}
+				else
+				{
+					self.recent_acknowledgment.as_ref().unwrap().time_until_lost(retransmission_segment.transmitted_at(), retransmission_segment.ends_at(), reordering_window, now)
+				}
+			};
+			
+			if let Some(time_until_lost) = time_until_lost
+			{
+				let time_until_lost_in_milliseconds: u64 = time_until_lost.into();
+				if time_until_lost_in_milliseconds == 0
+				{
+					self.get_mutable_reference(index).mark_lost();
+					any_segments_newly_marked_as_lost = true;
+				}
+				else
+				{
+					time_out = Some
+					(
+						match time_out
+						{
+							None => time_until_lost,
+							Some(time_out) => max(time_out, time_until_lost),
+						}
+					);
+				}
+			}
+			
+			index += 1;
+		}
+		
+		(any_segments_newly_marked_as_lost, time_out)
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A D-SACK block has been received.
+	///
+	/// Does nothing if RACK is not in use.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn recent_acknowledgment_duplicate_selective_acknowledgment_received(&mut self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber)
+	{
+		if let Some(ref mut recent_acknowledgment) = self.recent_acknowledgment
+		{
+			recent_acknowledgment.duplicate_selective_acknowledgment_received(SND_UNA, SND_NXT)
+		}
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A loss recovery episode has ended.
+	///
+	/// Does nothing if RACK is not in use.
+	#[inline(always)]
+	pub fn recent_acknowledgment_loss_recovery_exited(&mut self)
+	{
+		if let Some(ref mut recent_acknowledgment) = self.recent_acknowledgment
+		{
+			recent_acknowledgment.loss_recovery_exited()
+		}
+	}
+	
+	/// RFC 6675 Section 4 IsLost (SeqNum).
+	#[inline(always)]
+	pub fn is_lost(&self, SeqNum: WrappingSequenceNumber, sender_maximum_segment_size: u32) -> bool
+	{
+		self.selective_acknowledgment_scoreboard.is_lost(SeqNum, sender_maximum_segment_size)
+	}
+	
+	/// RFC 6675 Section 4: "SetPipe (): This routine traverses the sequence space from HighACK to HighData and MUST set the "pipe" variable to an estimate of the number of octets that are currently in transit between the TCP sender and the TCP receiver".
+	///
+	/// The traversal is done a segment at a time rather than an octet at a time.
+	///
+	/// If RACK (RFC 8985) is in use, a segment is in transit once unless it is marked as lost; a lost segment which has been retransmitted is no longer marked as lost.
+	#[inline(always)]
+	pub fn pipe(&self, sender_maximum_segment_size: u32) -> u32
+	{
+		let mut pipe = 0;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			let retransmission_segment = self.get(index);
+			index += 1;
+			
+			let starts_at = retransmission_segment.starts_at();
+			let ends_at = retransmission_segment.ends_at();
+			
+			let not_selectively_acknowledged = (ends_at - starts_at) - self.selective_acknowledgment_scoreboard.number_of_sequence_numbers_selectively_acknowledged(starts_at, ends_at);
+			if not_selectively_acknowledged == 0
+			{
+				continue
+			}
+			
+			if self.is_using_recent_acknowledgment_loss_detection()
+			{
+				if !retransmission_segment.is_lost()
+				{
+					pipe += not_selectively_acknowledged;
+				}
+				continue
+			}
+			
+			// RFC 6675 Section 4 SetPipe (a): "If IsLost (S1) returns false: Pipe is incremented by 1 octet".
+			if !self.is_lost(starts_at, sender_maximum_segment_size)
+			{
+				pipe += not_selectively_acknowledged;
+			}
+			
+			// RFC 6675 Section 4 SetPipe (b): "If S1 <= HighRxt: Pipe is incremented by 1 octet".
+			if retransmission_segment.has_been_retransmitted()
+			{
+				pipe += not_selectively_acknowledged;
+			}
+		}
+		
+		pipe
+	}
+	
+	/// RFC 6675 Section 4 NextSeg () rules (1) and (3), which choose a segment to retransmit; rule (2), the transmission of new data, is the responsibility of the caller.
+	///
+	/// If `must_be_lost` is `true` then rule (1) is applied, otherwise rule (3) is.
+	///
+	/// If RACK (RFC 8985) is in use, rule (1) instead returns the earliest segment marked as lost which has not been selectively acknowledged.
+	///
+	/// The segment returned is marked as having been retransmitted at `now`.
+	///
+	/// Returns a tuple of `(starts_at, offset_of_data_from_SND_UNA, data_length_excluding_length_of_synchronize_and_finish_controls, flags)`.
+	#[inline(always)]
+	pub fn next_segment_to_retransmit(&mut self, sender_maximum_segment_size: u32, must_be_lost: bool, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, usize, u32, Flags)>
+	{
+		if must_be_lost && self.is_using_recent_acknowledgment_loss_detection()
+		{
+			return self.next_segment_marked_as_lost_to_retransmit(now)
+		}
+		
+		let highest_selectively_acknowledged = match self.selective_acknowledgment_scoreboard.highest_selectively_acknowledged()
+		{
+			None => return None,
+			Some(highest_selectively_acknowledged) => highest_selectively_acknowledged,
+		};
+		
+		let mut offset_of_data_from_SND_UNA = 0;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			let (starts_at, ends_at, data_length) =
+			{
+				let retransmission_segment = self.get(index);
+				(retransmission_segment.starts_at(), retransmission_segment.ends_at(), retransmission_segment.data_length_excluding_length_of_synchronize_and_finish_controls())
+			};
+			
+			// RFC 6675 Section 4 NextSeg () (1.b): "S2 is less than the highest octet covered by any received SACK".
+			if starts_at >= highest_selectively_acknowledged
+			{
+				break
+			}
+			
+			let is_a_candidate =
+			{
+				// RFC 6675 Section 4 NextSeg () (1.a): "S2 is greater than HighRxt".
+				let is_greater_than_HighRxt = match self.HighRxt
+				{
+					None => true,
+					Some(HighRxt) => starts_at >= HighRxt,
+				};
+				
+				let is_not_selectively_acknowledged = self.selective_acknowledgment_scoreboard.number_of_sequence_numbers_selectively_acknowledged(starts_at, ends_at) != ends_at - starts_at;
+				
+				// RFC 6675 Section 4 NextSeg () (1.c): "IsLost (S2) returns true".
+				is_greater_than_HighRxt && is_not_selectively_acknowledged && (!must_be_lost || self.is_lost(starts_at, sender_maximum_segment_size))
+			};
+			
+			if is_a_candidate
+			{
+				let (starts_at, data_length, flags) = self.get_mutable_reference(index).retransmitted(now);
+				self.HighRxt = Some(ends_at);
+				return Some((starts_at, offset_of_data_from_SND_UNA, data_length, flags))
+			}
+			
+			offset_of_data_from_SND_UNA += data_length as usize;
+			index += 1;
+		}
+		
+		None
+	}
+	
+	#[inline(always)]
+	fn next_segment_marked_as_lost_to_retransmit(&mut self, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, usize, u32, Flags)>
+	{
+		let mut offset_of_data_from_SND_UNA = 0;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			let (is_a_candidate, ends_at, data_length) =
+			{
+				let retransmission_segment = self.get(index);
+				(retransmission_segment.is_lost() && !retransmission_segment.is_selectively_acknowledged(), retransmission_segment.ends_at(), retransmission_segment.data_length_excluding_length_of_synchronize_and_finish_controls())
+			};
+			
+			if is_a_candidate
+			{
+				let (starts_at, data_length, flags) = self.get_mutable_reference(index).retransmitted(now);
+				let is_greater_than_HighRxt = match self.HighRxt
+				{
+					None => true,
+					Some(HighRxt) => ends_at > HighRxt,
+				};
+				if is_greater_than_HighRxt
+				{
+					self.HighRxt = Some(ends_at);
+				}
+				
+				return Some((starts_at, offset_of_data_from_SND_UNA, data_length, flags))
+			}
+			
+			offset_of_data_from_SND_UNA += data_length as usize;
+			index += 1;
+		}
+		
+		None
+	}
+	
+	/// RFC 2018 Section 8: "After a retransmit timeout the data sender SHOULD turn off all of the SACKed bits, since the timeout might indicate that the data receiver has reneged".
+	///
+	/// Also ends the current loss recovery phase's record of retransmissions (HighRxt).
+	#[inline(always)]
+	pub fn retransmission_timed_out(&mut self)
+	{
+		self.selective_acknowledgment_scoreboard.clear();
+		self.HighRxt = None;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			self.get_mutable_reference(index).set_selectively_acknowledged(false);
+			index += 1;
+		}
+	}
+	
+	/// The oldest segment, ie the one starting at `SND.UNA`, which is marked as having been retransmitted at `now`.
+	///
+	/// Returns a tuple of `(starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags)`.
+	#[inline(always)]
+	pub fn oldest_for_retransmission(&mut self, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, u32, Flags)>
+	{
+		if self.is_empty()
+		{
+			return None
+		}
+		
+		let (starts_at, data_length, flags) = self.oldest().retransmitted(now);
+		
+		// RFC 6675 Section 5 Step (4.3): "... set both HighRxt and RescueRxt to the highest sequence number in the retransmitted segment".
+		self.HighRxt = Some(self.oldest().ends_at());
+		
+		Some((starts_at, data_length, flags))
+	}
+	
+	/// The newest segment, ie the one most recently transmitted for the first time, which is marked as having been retransmitted at `now`.
+	///
+	/// Used for a tail loss probe (RFC 8985 Section 7.3) when there is no new data to transmit.
+	///
+	/// Returns a tuple of `(starts_at, offset_of_data_from_SND_UNA, data_length_excluding_length_of_synchronize_and_finish_controls, flags)`.
+	#[inline(always)]
+	pub fn newest_for_retransmission(&mut self, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, usize, u32, Flags)>
+	{
+		if self.is_empty()
+		{
+			return None
+		}
+		
+		let newest_index = self.depth - 1;
+		
+		let mut offset_of_data_from_SND_UNA = 0;
+		let mut index = 0;
+		while index < newest_index
+		{
+			offset_of_data_from_SND_UNA += self.get(index).data_length_excluding_length_of_synchronize_and_finish_controls() as usize;
+			index += 1;
+		}
+		
+		let (starts_at, data_length, flags) = self.get_mutable_reference(newest_index).retransmitted(now);
+		Some((starts_at, offset_of_data_from_SND_UNA, data_length, flags))
+	}
+	
+	/// The number of segments which are fully selectively acknowledged; RFC 8985 Section 6.2 "RACK.segs_sacked".
+	#[inline(always)]
+	fn number_of_segments_selectively_acknowledged(&self) -> usize
+	{
+		let mut number_of_segments_selectively_acknowledged = 0;
+		
+		let mut index = 0;
+		while index < self.depth
+		{
+			if self.get(index).is_selectively_acknowledged()
+			{
+				number_of_segments_selectively_acknowledged += 1;
+			}
+			index += 1;
+		}
+		
+		number_of_segments_selectively_acknowledged
+	}
+	
 	#[inline(always)]
 	fn oldest(&mut self) -> &mut RetransmissionSegment
 	{
-		debug_assert!(self.is_empty(), "is empty");
+		debug_assert!(!self.is_empty(), "is empty");
 		
 		let start_index = self.start_index();
 		unsafe { &mut * self.get_mutable(start_index) }
@@ -141,9 +618,11 @@ impl RetransmissionQueue
 	{
 		debug_assert!(self.is_not_full(), "retransmission queue is full");
 		
+		let delivery_rate_snapshot = self.delivery_rate_estimation.transmitted(now, self.is_empty());
+		
 		let end_index = self.end_index();
 		let item = self.get_mutable(end_index);
-		unsafe { item.write_unaligned(RetransmissionSegment::new(now, starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags)) }
+		unsafe { item.write_unaligned(RetransmissionSegment::new(now, starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags, delivery_rate_snapshot)) }
 		self.depth += 1
 	}
 	
@@ -167,6 +646,26 @@ impl RetransmissionQueue
 		self.depth == Self::MaximumDepth
 	}
 	
+	/// `index` is relative to the oldest segment.
+	#[inline(always)]
+	fn get(&self, index: usize) -> &RetransmissionSegment
+	{
+		debug_assert!(index < self.depth, "index is beyond depth");
+		
+		let index = (self.start + index) % Self::MaximumDepth;
+		unsafe { self.queue.get_unchecked(index) }
+	}
+	
+	/// `index` is relative to the oldest segment.
+	#[inline(always)]
+	fn get_mutable_reference(&mut self, index: usize) -> &mut RetransmissionSegment
+	{
+		debug_assert!(index < self.depth, "index is beyond depth");
+		
+		let index = (self.start + index) % Self::MaximumDepth;
+		unsafe { &mut * self.get_mutable(index) }
+	}
+	
 	#[inline(always)]
 	fn get_mutable(&mut self, index: usize) -> *mut RetransmissionSegment
 	{
@@ -16,6 +16,14 @@ pub struct RetransmissionSegment
 	partially_acknowledged: bool,
 
 	explicit_congestion_echo: bool,
+	
+	/// Has this segment been fully selectively acknowledged (and so delivered, as far as RACK (RFC 8985) is concerned)?
+	selectively_acknowledged: bool,
+	
+	/// RFC 8985 Section 6.1 "Segment.lost": Has RACK (RFC 8985) marked this segment as lost since it was last transmitted?
+	lost: bool,
+	
+	delivery_rate_snapshot: DeliveryRateSnapshot,
 }
 
 impl Debug for RetransmissionSegment
@@ -31,12 +39,12 @@ impl RetransmissionSegment
 {
 	/// Create a new instance.
 	#[inline(always)]
-	pub fn new(timestamp: MonotonicMillisecondTimestamp, starts_at: WrappingSequenceNumber, data_length_excluding_length_of_synchronize_and_finish_controls: u32, flags: Flags) -> Self
+	pub fn new(timestamp: MonotonicMillisecondTimestamp, starts_at: WrappingSequenceNumber, data_length_excluding_length_of_synchronize_and_finish_controls: u32, flags: Flags, delivery_rate_snapshot: DeliveryRateSnapshot) -> Self
 	{
 		debug_assert!(flags.does_not_contain(Flags::Reset), "Flags should not contain Reset");
 		debug_assert!(flags.does_not_contain(Flags::Urgent), "Flags should not contain Urgent");
 		debug_assert!(flags.does_not_contain(Flags::Push), "Flags should not contain Push");
-		debug_assert!(flags.does_not_contain(Flags::Synchronize) && flags.does_not_contain(Flags::Finish), "Flags should not contain Synchronize and Finish");
+		debug_assert!(flags.does_not_contain(Flags::Synchronize) || flags.does_not_contain(Flags::Finish), "Flags should not contain Synchronize and Finish");
 		
 		if cfg!(debug_assertions)
 		{
@@ -58,6 +66,12 @@ impl RetransmissionSegment
 			partially_acknowledged: false,
 		
 			explicit_congestion_echo: false,
+			
+			selectively_acknowledged: false,
+			
+			lost: false,
+			
+			delivery_rate_snapshot,
 		}
 	}
 	
@@ -126,6 +140,93 @@ impl RetransmissionSegment
 		Ok(outcome)
 	}
 	
+	/// Starts at.
+	#[inline(always)]
+	pub fn starts_at(&self) -> WrappingSequenceNumber
+	{
+		self.starts_at
+	}
+	
+	/// Ends at (exclusive), ie the sequence number after the last one occupied by this segment, including any `SYN` or `FIN` control.
+	#[inline(always)]
+	pub fn ends_at(&self) -> WrappingSequenceNumber
+	{
+		let mut sequence_number_length = self.data_length_excluding_length_of_synchronize_and_finish_controls;
+		if self.flags.contains(Flags::Synchronize)
+		{
+			sequence_number_length += 1;
+		}
+		if self.flags.contains(Flags::Finish)
+		{
+			sequence_number_length += 1;
+		}
+		self.starts_at + sequence_number_length
+	}
+	
+	/// Data length, excluding the length of any `SYN` or `FIN` control.
+	#[inline(always)]
+	pub fn data_length_excluding_length_of_synchronize_and_finish_controls(&self) -> u32
+	{
+		self.data_length_excluding_length_of_synchronize_and_finish_controls
+	}
+	
+	/// Has this segment been retransmitted?
+	#[inline(always)]
+	pub fn has_been_retransmitted(&self) -> bool
+	{
+		self.has_been_retransmitted
+	}
+	
+	/// Marks this segment as having been retransmitted at `now`.
+	///
+	/// RFC 8985 Section 6.1 "Segment.xmit_ts": The transmission time becomes that of the retransmission, and the segment is no longer marked as lost.
+	///
+	/// Returns a tuple of `(starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags)`.
+	#[inline(always)]
+	pub fn retransmitted(&mut self, now: MonotonicMillisecondTimestamp) -> (WrappingSequenceNumber, u32, Flags)
+	{
+		self.has_been_retransmitted = true;
+		self.timestamp = now;
+		self.lost = false;
+		
+		(self.starts_at, self.data_length_excluding_length_of_synchronize_and_finish_controls, self.flags)
+	}
+	
+	/// When this segment was most recently transmitted (or retransmitted).
+	#[inline(always)]
+	pub fn transmitted_at(&self) -> MonotonicMillisecondTimestamp
+	{
+		self.timestamp
+	}
+	
+	/// Has this segment been fully selectively acknowledged?
+	#[inline(always)]
+	pub fn is_selectively_acknowledged(&self) -> bool
+	{
+		self.selectively_acknowledged
+	}
+	
+	/// Marks this segment as fully selectively acknowledged (`true`) or not (`false`, eg after a retransmission time out, as the receiver may have reneged).
+	#[inline(always)]
+	pub fn set_selectively_acknowledged(&mut self, selectively_acknowledged: bool)
+	{
+		self.selectively_acknowledged = selectively_acknowledged
+	}
+	
+	/// Has RACK (RFC 8985) marked this segment as lost since it was last transmitted?
+	#[inline(always)]
+	pub fn is_lost(&self) -> bool
+	{
+		self.lost
+	}
+	
+	/// RFC 8985 Section 6.2 Step 5: Marks this segment as lost.
+	#[inline(always)]
+	pub fn mark_lost(&mut self)
+	{
+		self.lost = true
+	}
+	
 	#[allow(missing_docs)]
 	#[inline(always)]
 	pub fn set_unretransmitted_segment_timestamp_if_unset(&self, unretransmitted_segment_timestamp: &mut Option<MonotonicMillisecondTimestamp>)
@@ -138,6 +239,20 @@ impl RetransmissionSegment
 		*unretransmitted_segment_timestamp = Some(self.timestamp)
 	}
 	
+	/// The transmission time and delivery rate snapshot of this segment, or `None` if it has been retransmitted.
+	#[inline(always)]
+	pub(crate) fn delivery_rate_transmission(&self) -> Option<(MonotonicMillisecondTimestamp, DeliveryRateSnapshot)>
+	{
+		if self.has_been_retransmitted
+		{
+			None
+		}
+		else
+		{
+			Some((self.timestamp, self.delivery_rate_snapshot))
+		}
+	}
+	
 	#[allow(missing_docs)]
 	#[inline(always)]
 	pub fn set_explicit_congestion_echo(&self, explicit_congestion_echo: &mut bool)
@@ -0,0 +1,262 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// RFC 6675 Section 2: "Scoreboard: ... stores the information about which segments have been SACKed".
+///
+/// Holds disjoint blocks of selectively acknowledged sequence numbers, sorted in ascending order; adjacent and overlapping blocks are merged.
+///
+/// All blocks are above `SND.UNA` (RFC 6675 'HighACK') and at or below `SND.NXT` (RFC 6675 'HighData').
+#[derive(Debug)]
+pub struct SelectiveAcknowledgmentScoreboard
+{
+	blocks: [SelectiveAcknowledgmentBlock; SelectiveAcknowledgmentScoreboard::MaximumNumberOfBlocks],
+	number_of_blocks: usize,
+}
+
+impl Default for SelectiveAcknowledgmentScoreboard
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			blocks: [Self::EmptyBlock; Self::MaximumNumberOfBlocks],
+			number_of_blocks: 0,
+		}
+	}
+}
+
+impl SelectiveAcknowledgmentScoreboard
+{
+	/// There can not be more 'holes' than there are segments in the retransmission queue.
+	const MaximumNumberOfBlocks: usize = RetransmissionQueue::MaximumDepth;
+	
+	const EmptyBlock: SelectiveAcknowledgmentBlock = SelectiveAcknowledgmentBlock
+	{
+		left_edge_of_block: WrappingSequenceNumber::Zero,
+		right_edge_of_block: WrappingSequenceNumber::Zero,
+	};
+	
+	/// RFC 6675 Section 2: "DupThresh: The number of duplicate acknowledgments required to trigger a fast retransmission ... DupThresh of 3".
+	const DupThresh: u32 = 3;
+	
+	/// Updates the scoreboard from the blocks of an incoming `SACK` option.
+	///
+	/// Blocks which are at or below `SND.UNA` or beyond `SND.NXT` are ignored.
+	///
+	/// Returns `true` if the option contained new information, ie it identified previously unacknowledged and un-SACKed sequence numbers.
+	#[inline(always)]
+	pub fn selectively_acknowledged(&mut self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, selective_acknowledgment_option: &SelectiveAcknowledgmentOption) -> bool
+	{
+		let mut contained_new_information = self.selectively_acknowledged_block(SND_UNA, SND_NXT, selective_acknowledgment_option.first_block());
+		
+		let number_of_blocks = selective_acknowledgment_option.number_of_blocks();
+		if number_of_blocks >= 2
+		{
+			contained_new_information |= self.selectively_acknowledged_block(SND_UNA, SND_NXT, selective_acknowledgment_option.second_block());
+		}
+		if number_of_blocks >= 3
+		{
+			contained_new_information |= self.selectively_acknowledged_block(SND_UNA, SND_NXT, selective_acknowledgment_option.third_block());
+		}
+		if number_of_blocks == 4
+		{
+			contained_new_information |= self.selectively_acknowledged_block(SND_UNA, SND_NXT, selective_acknowledgment_option.fourth_block());
+		}
+		
+		contained_new_information
+	}
+	
+	/// Discards information at or below the new value of `SND.UNA`.
+	#[inline(always)]
+	pub fn cumulatively_acknowledged(&mut self, SND_UNA: WrappingSequenceNumber)
+	{
+		let mut number_of_blocks_to_remove = 0;
+		while number_of_blocks_to_remove < self.number_of_blocks && self.blocks[number_of_blocks_to_remove].right_edge_of_block <= SND_UNA
+		{
+			number_of_blocks_to_remove += 1;
+		}
+		
+		self.remove_blocks(0, number_of_blocks_to_remove);
+		
+		if self.number_of_blocks != 0 && self.blocks[0].left_edge_of_block < SND_UNA
+		{
+			self.blocks[0].left_edge_of_block = SND_UNA
+		}
+	}
+	
+	/// RFC 2018 Section 8: "After a retransmit timeout the data sender SHOULD turn off all of the SACKed bits, since the timeout might indicate that the data receiver has reneged".
+	#[inline(always)]
+	pub fn clear(&mut self)
+	{
+		self.number_of_blocks = 0
+	}
+	
+	/// Is there any information in the scoreboard?
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.number_of_blocks == 0
+	}
+	
+	/// The right edge of the highest block; this is one more than the highest sequence number selectively acknowledged.
+	#[inline(always)]
+	pub fn highest_selectively_acknowledged(&self) -> Option<WrappingSequenceNumber>
+	{
+		if self.is_empty()
+		{
+			None
+		}
+		else
+		{
+			Some(self.blocks[self.number_of_blocks - 1].right_edge_of_block)
+		}
+	}
+	
+	/// How many of the sequence numbers from `starts_at` (inclusive) to `ends_at` (exclusive) have been selectively acknowledged?
+	#[inline(always)]
+	pub fn number_of_sequence_numbers_selectively_acknowledged(&self, starts_at: WrappingSequenceNumber, ends_at: WrappingSequenceNumber) -> u32
+	{
+		let mut count = 0;
+		for block in self.blocks[ .. self.number_of_blocks].iter()
+		{
+			if block.left_edge_of_block >= ends_at
+			{
+				break
+			}
+			
+			let overlap_starts_at = max(block.left_edge_of_block, starts_at);
+			let overlap_ends_at = min(block.right_edge_of_block, ends_at);
+			if overlap_starts_at < overlap_ends_at
+			{
+				count += overlap_ends_at - overlap_starts_at
+			}
+		}
+		count
+	}
+	
+	/// RFC 6675 Section 4: "IsLost (SeqNum): This routine returns whether the given sequence number is considered to be lost.
+	/// The routine returns true when either DupThresh discontiguous SACKed sequences have arrived above 'SeqNum' or more than (DupThresh - 1) * SMSS bytes with sequence numbers greater than 'SeqNum' have been SACKed.
+	/// Otherwise, the routine returns false".
+	#[inline(always)]
+	pub fn is_lost(&self, SeqNum: WrappingSequenceNumber, sender_maximum_segment_size: u32) -> bool
+	{
+		let mut discontiguous_selectively_acknowledged_sequences = 0;
+		let mut selectively_acknowledged_bytes = 0;
+		
+		for block in self.blocks[ .. self.number_of_blocks].iter().rev()
+		{
+			if block.right_edge_of_block <= SeqNum
+			{
+				break
+			}
+			
+			discontiguous_selectively_acknowledged_sequences += 1;
+			selectively_acknowledged_bytes += block.right_edge_of_block - max(block.left_edge_of_block, SeqNum + 1u32);
+			
+			if discontiguous_selectively_acknowledged_sequences >= Self::DupThresh || selectively_acknowledged_bytes > (Self::DupThresh - 1) * sender_maximum_segment_size
+			{
+				return true
+			}
+		}
+		
+		false
+	}
+	
+	#[inline(always)]
+	fn selectively_acknowledged_block(&mut self, SND_UNA: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, block: SelectiveAcknowledgmentBlock) -> bool
+	{
+		// RFC 2883 Section 4: a block at or below the cumulative acknowledgment reports a duplicate segment.
+		if block.right_edge_of_block <= SND_UNA
+		{
+			return false
+		}
+		
+		// A block beyond data we have sent is bogus.
+		if block.right_edge_of_block > SND_NXT
+		{
+			return false
+		}
+		
+		let left_edge_of_block = max(block.left_edge_of_block, SND_UNA);
+		let right_edge_of_block = block.right_edge_of_block;
+		
+		let already_selectively_acknowledged = self.number_of_sequence_numbers_selectively_acknowledged(left_edge_of_block, right_edge_of_block);
+		if already_selectively_acknowledged == right_edge_of_block - left_edge_of_block
+		{
+			return false
+		}
+		
+		self.insert(left_edge_of_block, right_edge_of_block);
+		true
+	}
+	
+	/// Inserts a block, merging it with any blocks it overlaps or is adjacent to.
+	#[inline(always)]
+	fn insert(&mut self, mut left_edge_of_block: WrappingSequenceNumber, mut right_edge_of_block: WrappingSequenceNumber)
+	{
+		let mut first_index = 0;
+		while first_index < self.number_of_blocks && self.blocks[first_index].right_edge_of_block < left_edge_of_block
+		{
+			first_index += 1;
+		}
+		
+		let mut end_index = first_index;
+		while end_index < self.number_of_blocks && self.blocks[end_index].left_edge_of_block <= right_edge_of_block
+		{
+			left_edge_of_block = min(left_edge_of_block, self.blocks[end_index].left_edge_of_block);
+			right_edge_of_block = max(right_edge_of_block, self.blocks[end_index].right_edge_of_block);
+			end_index += 1;
+		}
+		
+		let merged_block = SelectiveAcknowledgmentBlock
+		{
+			left_edge_of_block,
+			right_edge_of_block,
+		};
+		
+		if first_index != end_index
+		{
+			self.blocks[first_index] = merged_block;
+			self.remove_blocks(first_index + 1, end_index - first_index - 1);
+			return
+		}
+		
+		// If the scoreboard is full, forget the highest block; this is conservative, as it can only cause data to be retransmitted which need not have been.
+		if self.number_of_blocks == Self::MaximumNumberOfBlocks
+		{
+			if first_index == Self::MaximumNumberOfBlocks
+			{
+				return
+			}
+			self.number_of_blocks -= 1;
+		}
+		
+		let mut index = self.number_of_blocks;
+		while index > first_index
+		{
+			self.blocks[index] = self.blocks[index - 1];
+			index -= 1;
+		}
+		self.blocks[first_index] = merged_block;
+		self.number_of_blocks += 1;
+	}
+	
+	#[inline(always)]
+	fn remove_blocks(&mut self, from_index: usize, count: usize)
+	{
+		if count == 0
+		{
+			return
+		}
+		
+		let mut index = from_index;
+		while index + count < self.number_of_blocks
+		{
+			self.blocks[index] = self.blocks[index + count];
+			index += 1;
+		}
+		self.number_of_blocks -= count;
+	}
+}
@@ -5,7 +5,12 @@
 use super::*;
 
 
+include!("DeliveryRateEstimation.rs");
+include!("DeliveryRateSample.rs");
+include!("DeliveryRateSnapshot.rs");
+include!("RecentAcknowledgment.rs");
 include!("RetransmissionQueue.rs");
 include!("RetransmissionSegment.rs");
 include!("RetransmissionSegmentDecreaseSequenceNumberLengthOutcome.rs");
+include!("SelectiveAcknowledgmentScoreboard.rs");
 include!("TooManySequenceNumbersAcknowledgedError.rs");
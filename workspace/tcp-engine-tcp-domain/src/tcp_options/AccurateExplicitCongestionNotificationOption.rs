@@ -0,0 +1,126 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Accurate ECN (AccECN) Option, RFC 9768.
+///
+/// Carries the least significant 24 bits of the data receiver's counts of payload bytes received with an IP ECN field of ECT(0), CE or ECT(1).
+///
+/// There are two option kinds which differ only in the order of the counter fields; trailing fields may be omitted, hence each counter is optional.
+#[derive(Default, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct AccurateExplicitCongestionNotificationOption
+{
+	/// Count of payload bytes received marked ECT(0) (`EE0B`).
+	pub ect_zero_bytes: Option<u32>,
+	
+	/// Count of payload bytes received marked CE (`ECEB`).
+	pub congestion_experienced_bytes: Option<u32>,
+	
+	/// Count of payload bytes received marked ECT(1) (`EE1B`).
+	pub ect_one_bytes: Option<u32>,
+}
+
+impl AccurateExplicitCongestionNotificationOption
+{
+	/// Fields are in the order `EE0B`, `ECEB`, `EE1B`.
+	#[doc(hidden)]
+	pub const Order0Kind: u8 = 172;
+	
+	/// Fields are in the order `EE1B`, `ECEB`, `EE0B`.
+	#[doc(hidden)]
+	pub const Order1Kind: u8 = 174;
+	
+	#[doc(hidden)]
+	pub const FieldLength: usize = 3;
+	
+	#[doc(hidden)]
+	pub const MaximumLength: usize = TcpOptions::LengthOverhead + 3 * Self::FieldLength;
+	
+	/// Counters are 24-bit.
+	#[doc(hidden)]
+	pub const CounterMask: u32 = 0x00FF_FFFF;
+	
+	/// Is `length` (which includes the option kind and length fields) one of the permitted lengths of 2, 5, 8 or 11?
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn is_valid_length(length: usize) -> bool
+	{
+		length >= TcpOptions::LengthOverhead && length <= Self::MaximumLength && (length - TcpOptions::LengthOverhead) % Self::FieldLength == 0
+	}
+	
+	/// Parses the counter fields of an option of `option_kind` and `length` (which includes the option kind and length fields); `length` must have been validated with `is_valid_length()`.
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn parse(option_kind: u8, pointer_to_data: usize, length: usize) -> Self
+	{
+		let number_of_fields = (length - TcpOptions::LengthOverhead) / Self::FieldLength;
+		
+		let field = |index: usize| -> Option<u32>
+		{
+			if index < number_of_fields
+			{
+				let pointer_to_field = (pointer_to_data + index * Self::FieldLength) as *const u8;
+				let (most_significant, middle, least_significant) = unsafe { (*pointer_to_field, *pointer_to_field.offset(1), *pointer_to_field.offset(2)) };
+				Some(((most_significant as u32) << 16) | ((middle as u32) << 8) | (least_significant as u32))
+			}
+			else
+			{
+				None
+			}
+		};
+		
+		let (first, second, third) = (field(0), field(1), field(2));
+		
+		if option_kind == Self::Order0Kind
+		{
+			Self
+			{
+				ect_zero_bytes: first,
+				congestion_experienced_bytes: second,
+				ect_one_bytes: third,
+			}
+		}
+		else
+		{
+			Self
+			{
+				ect_one_bytes: first,
+				congestion_experienced_bytes: second,
+				ect_zero_bytes: third,
+			}
+		}
+	}
+	
+	/// The increase, modulo 2^24, of a 24-bit byte counter since `previous_counter`.
+	#[inline(always)]
+	pub fn delta(counter: u32, previous_counter: u32) -> u32
+	{
+		counter.wrapping_sub(previous_counter) & Self::CounterMask
+	}
+	
+	/// Length of this option, including option kind and length fields, when written in order 0.
+	///
+	/// Trailing counters which are absent are omitted; a counter which is absent before one which is present is written as zero.
+	#[inline(always)]
+	pub fn option_length(&self) -> usize
+	{
+		let number_of_fields = if self.ect_one_bytes.is_some()
+		{
+			3
+		}
+		else if self.congestion_experienced_bytes.is_some()
+		{
+			2
+		}
+		else if self.ect_zero_bytes.is_some()
+		{
+			1
+		}
+		else
+		{
+			0
+		};
+		
+		TcpOptions::LengthOverhead + number_of_fields * Self::FieldLength
+	}
+}
@@ -41,6 +41,63 @@ impl SelectiveAcknowledgmentOption
 	#[doc(hidden)]
 	pub const FourBlocksLength: usize = Self::ThreeBlocksLength + Self::BlockLength;
 	
+	/// RFC 2018 Section 3: "... a SACK option that specifies n blocks will have a length of 8*n+2 bytes, so the 40 bytes available for TCP options can specify a maximum of 4 blocks".
+	///
+	/// Returns the number of blocks which can be written into the space that remains after `options_size_so_far` bytes of other options; this may be zero.
+	#[inline(always)]
+	pub fn maximum_number_of_blocks_that_fit(options_size_so_far: usize) -> usize
+	{
+		const MaximumOptionsSize: usize = 40;
+		const MaximumNumberOfBlocks: usize = 4;
+		
+		let remaining = MaximumOptionsSize.saturating_sub(options_size_so_far);
+		if remaining < Self::OneBlockLength
+		{
+			0
+		}
+		else
+		{
+			min((remaining - Self::OptionTypeAndLengthOverhead) / Self::BlockLength, MaximumNumberOfBlocks)
+		}
+	}
+	
+	/// Creates an option from between one (1) and four (4) blocks, the first block being the first in `blocks`.
+	///
+	/// Returns `None` if `blocks` is empty.
+	#[inline(always)]
+	pub fn from_blocks(blocks: &[SelectiveAcknowledgmentBlock]) -> Option<Self>
+	{
+		match blocks.len()
+		{
+			0 => None,
+			1 => Some(Self::one_block(blocks[0])),
+			2 => Some(Self::two_blocks(blocks[0], blocks[1])),
+			3 => Some(Self::three_blocks(blocks[0], blocks[1], blocks[2])),
+			_ => Some(Self::four_blocks(blocks[0], blocks[1], blocks[2], blocks[3])),
+		}
+	}
+	
+	/// RFC 2883 Section 4: Does the first block report a duplicate segment (a D-SACK block)?
+	///
+	/// It does if it is at or below the cumulative acknowledgment, `SEG_ACK`, or if it is contained within the second block.
+	#[inline(always)]
+	pub fn duplicate_selective_acknowledgment_block(&self, SEG_ACK: WrappingSequenceNumber) -> Option<SelectiveAcknowledgmentBlock>
+	{
+		let first_block = self.first_block;
+		
+		if first_block.right_edge_of_block <= SEG_ACK
+		{
+			return Some(first_block)
+		}
+		
+		match self.second_block
+		{
+			Some(second_block) if second_block.left_edge_of_block <= first_block.left_edge_of_block && first_block.right_edge_of_block <= second_block.right_edge_of_block => Some(first_block),
+			
+			_ => None,
+		}
+	}
+	
 	/// Option length.
 	#[inline(always)]
 	pub fn option_length(&self) -> usize
@@ -14,6 +14,7 @@ pub struct TcpOptions
 	pub timestamps: Option<TimestampsOption>,
 	pub user_time_out: Option<UserTimeOutOption>,
 	pub authentication: Option<AuthenticationOption>,
+	pub accurate_explicit_congestion_notification: Option<AccurateExplicitCongestionNotificationOption>,
 }
 
 impl TcpOptions
@@ -78,6 +79,13 @@ impl TcpOptions
 		self.authentication.is_some()
 	}
 	
+	#[doc(hidden)]
+	#[inline(always)]
+	pub fn has_accurate_explicit_congestion_notification(&self) -> bool
+	{
+		self.accurate_explicit_congestion_notification.is_some()
+	}
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	pub fn parse_option_kind_without_checks(pointer_to_option_kind: usize) -> u8
@@ -5,6 +5,7 @@
 use super::*;
 
 
+include!("AccurateExplicitCongestionNotificationOption.rs");
 include!("AuthenticationOption.rs");
 include!("MaximumSegmentSizeOption.rs");
 include!("SelectiveAcknowledgmentOption.rs");
@@ -12,3 +13,6 @@ include!("TcpOptions.rs");
 include!("TimestampsOption.rs");
 include!("UserTimeOutOption.rs");
 include!("WindowScaleOption.rs");
+
+
+#[cfg(test)] mod tests;
@@ -0,0 +1,37 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+use super::*;
+
+
+#[test]
+fn four_selective_acknowledgment_blocks_fit_when_there_are_no_other_options()
+{
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(0), 4);
+}
+
+#[test]
+fn three_selective_acknowledgment_blocks_fit_after_timestamps()
+{
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(TimestampsOption::KnownLength), 3);
+}
+
+#[test]
+fn two_selective_acknowledgment_blocks_fit_after_a_md5_signature()
+{
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(AuthenticationOption::Md5SignatureOptionKnownLength), 2);
+}
+
+#[test]
+fn one_selective_acknowledgment_block_fits_after_timestamps_and_a_md5_signature()
+{
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(TimestampsOption::KnownLength + AuthenticationOption::Md5SignatureOptionKnownLength), 1);
+}
+
+#[test]
+fn no_selective_acknowledgment_blocks_fit_when_there_is_no_room_for_one_block()
+{
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(40 - SelectiveAcknowledgmentOption::OneBlockLength + 1), 0);
+	assert_eq!(SelectiveAcknowledgmentOption::maximum_number_of_blocks_that_fit(40), 0);
+}
@@ -0,0 +1,245 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Accurate ECN (AccECN) state, RFC 9768.
+///
+/// Instead of the single explicit congestion echo (ECE) flag of RFC 3168, which can signal at most one congestion event per round trip, the data receiver feeds back a count of CE marked packets in the three bit ACE field of every acknowledgment, and counts of ECT(0), CE and ECT(1) marked payload bytes in the AccECN option.
+///
+/// The data sender therefore learns how much of its data experienced congestion, as is needed by DCTCP (RFC 8257) and other scalable congestion control algorithms.
+///
+/// As for all of RFC 9768's counters, only the least significant bits are fed back, so a receiver must acknowledge before the ACE field can wrap; if more than seven (7) acknowledgments carrying CE counts are lost in a row, the data sender under counts.
+#[derive(Debug)]
+pub struct AccurateExplicitCongestionNotificationState
+{
+	/// `r.cep`: The count of CE marked packets received.
+	received_congestion_experienced_packets: u32,
+	
+	/// `r.e0b`: The count of ECT(0) marked payload bytes received.
+	received_ect_zero_bytes: u32,
+	
+	/// `r.ceb`: The count of CE marked payload bytes received.
+	received_congestion_experienced_bytes: u32,
+	
+	/// `r.e1b`: The count of ECT(1) marked payload bytes received.
+	received_ect_one_bytes: u32,
+	
+	/// The byte counters (`r.e0b`, `r.ceb`, `r.e1b`) last sent in an AccECN option, if any.
+	///
+	/// An option is only sent when one of them has changed.
+	last_sent_byte_counters: Option<(u32, u32, u32)>,
+	
+	/// Was the most recent data packet received CE marked?
+	last_data_packet_was_congestion_experienced: bool,
+	
+	/// The number of CE marked packets received since an acknowledgment was last sent.
+	unacknowledged_congestion_experienced_packets: u32,
+	
+	/// A client reflects the IP ECN field of the SynchronizeAcknowledgment in the ACE field of the first acknowledgment it sends, rather than `r.cep`.
+	handshake_acknowledgment_field: Option<AccurateExplicitCongestionEchoField>,
+	
+	/// `s.cep`: The data sender's count of CE marked packets fed back by the remote.
+	sent_congestion_experienced_packets: u32,
+	
+	/// `s.ceb`: The data sender's count of CE marked payload bytes fed back by the remote.
+	sent_congestion_experienced_bytes: u32,
+	
+	/// A server ignores the ACE field of the first acknowledgment it receives, as that is the client's reflection of the IP ECN field of our SynchronizeAcknowledgment and not a count.
+	ignore_next_acknowledgment_field: bool,
+	
+	/// CE marked packets fed back but not yet passed to the congestion control algorithm.
+	pending_congestion_experienced_packets: u32,
+	
+	/// CE marked payload bytes fed back but not yet passed to the congestion control algorithm.
+	pending_congestion_experienced_bytes: u32,
+}
+
+impl AccurateExplicitCongestionNotificationState
+{
+	/// An immediate acknowledgment is sent once this many CE marked packets have been received without being acknowledged, well before the ACE field, which wraps at eight (8), becomes ambiguous.
+	const MaximumUnacknowledgedCongestionExperiencedPackets: u32 = 3;
+	
+	/// State for a client which is requesting AccECN in its Synchronize.
+	///
+	/// If the SynchronizeAcknowledgment received does not negotiate AccECN, this state must be discarded.
+	#[inline(always)]
+	pub fn for_client() -> Self
+	{
+		Self::new(false)
+	}
+	
+	/// State for a server which has received the acknowledgment completing the three-way handshake with a client which negotiated AccECN.
+	///
+	/// That acknowledgment is processed afterwards, so its ACE field is ignored.
+	#[inline(always)]
+	pub fn for_server() -> Self
+	{
+		Self::new(true)
+	}
+	
+	#[inline(always)]
+	fn new(ignore_next_acknowledgment_field: bool) -> Self
+	{
+		const InitialCongestionExperiencedPackets: u32 = AccurateExplicitCongestionEchoField::InitialCongestionExperiencedPacketsCounter;
+		
+		// RFC 9768 starts the ECT byte counters at one (1) and the CE byte counter at zero (0).
+		Self
+		{
+			received_congestion_experienced_packets: InitialCongestionExperiencedPackets,
+			received_ect_zero_bytes: 1,
+			received_congestion_experienced_bytes: 0,
+			received_ect_one_bytes: 1,
+			last_sent_byte_counters: None,
+			last_data_packet_was_congestion_experienced: false,
+			unacknowledged_congestion_experienced_packets: 0,
+			handshake_acknowledgment_field: None,
+			sent_congestion_experienced_packets: InitialCongestionExperiencedPackets,
+			sent_congestion_experienced_bytes: 0,
+			ignore_next_acknowledgment_field,
+			pending_congestion_experienced_packets: 0,
+			pending_congestion_experienced_bytes: 0,
+		}
+	}
+	
+	/// A client has received a SynchronizeAcknowledgment which negotiated AccECN with an IP ECN field of `explicit_congestion_notification`; this is reflected in the ACE field of the first acknowledgment sent.
+	#[inline(always)]
+	pub fn synchronize_acknowledgment_received(&mut self, explicit_congestion_notification: ExplicitCongestionNotification)
+	{
+		self.handshake_acknowledgment_field = Some(AccurateExplicitCongestionEchoField::handshake_reflection_of(explicit_congestion_notification))
+	}
+	
+	/// A data packet with a payload of `payload_size` bytes has been received with an IP ECN field of `explicit_congestion_notification`.
+	///
+	/// Returns `true` if an immediate acknowledgment should be sent, which is when the CE marking of data packets starts or stops, or when several CE marked packets have not yet been acknowledged.
+	#[inline(always)]
+	pub fn incoming_data_packet(&mut self, explicit_congestion_notification: ExplicitCongestionNotification, payload_size: u32) -> bool
+	{
+		use self::ExplicitCongestionNotification::*;
+		
+		let congestion_experienced = match explicit_congestion_notification
+		{
+			NotCapableTransport => false,
+			
+			CapableTransportEctZero =>
+			{
+				self.received_ect_zero_bytes = self.received_ect_zero_bytes.wrapping_add(payload_size);
+				false
+			}
+			
+			CapableTransportEctOne =>
+			{
+				self.received_ect_one_bytes = self.received_ect_one_bytes.wrapping_add(payload_size);
+				false
+			}
+			
+			CongestionEncountered =>
+			{
+				self.received_congestion_experienced_packets = self.received_congestion_experienced_packets.wrapping_add(1);
+				self.received_congestion_experienced_bytes = self.received_congestion_experienced_bytes.wrapping_add(payload_size);
+				self.unacknowledged_congestion_experienced_packets += 1;
+				true
+			}
+		};
+		
+		let congestion_experienced_changed = congestion_experienced != self.last_data_packet_was_congestion_experienced;
+		self.last_data_packet_was_congestion_experienced = congestion_experienced;
+		
+		congestion_experienced_changed || self.unacknowledged_congestion_experienced_packets >= Self::MaximumUnacknowledgedCongestionExperiencedPackets
+	}
+	
+	/// A packet without data, such as a pure acknowledgment, has been received with an IP ECN field of `explicit_congestion_notification`.
+	///
+	/// A remote using generalized ECN may send such packets ECN-capable; if CE marked, they are counted in `r.cep` but, having no payload, add nothing to the byte counters.
+	///
+	/// An acknowledgment is never sent just to acknowledge one.
+	#[inline(always)]
+	pub fn incoming_packet_without_data(&mut self, explicit_congestion_notification: ExplicitCongestionNotification)
+	{
+		if explicit_congestion_notification.congestion_encountered()
+		{
+			self.received_congestion_experienced_packets = self.received_congestion_experienced_packets.wrapping_add(1);
+			self.unacknowledged_congestion_experienced_packets += 1;
+		}
+	}
+	
+	/// The ACE field to send on an acknowledgment.
+	#[inline(always)]
+	pub fn acknowledgment_field(&mut self) -> AccurateExplicitCongestionEchoField
+	{
+		if let Some(handshake_acknowledgment_field) = self.handshake_acknowledgment_field.take()
+		{
+			return handshake_acknowledgment_field
+		}
+		
+		self.unacknowledged_congestion_experienced_packets = 0;
+		AccurateExplicitCongestionEchoField::from_counter(self.received_congestion_experienced_packets)
+	}
+	
+	/// The AccECN option to send on an acknowledgment, if any of the byte counters have changed since one was last sent.
+	///
+	/// Must only be called if there is space for an option of `AccurateExplicitCongestionNotificationOption::MaximumLength`, as it is assumed the option is sent.
+	#[inline(always)]
+	pub fn option_to_send(&mut self) -> Option<AccurateExplicitCongestionNotificationOption>
+	{
+		let byte_counters = (self.received_ect_zero_bytes, self.received_congestion_experienced_bytes, self.received_ect_one_bytes);
+		if self.last_sent_byte_counters == Some(byte_counters)
+		{
+			return None
+		}
+		self.last_sent_byte_counters = Some(byte_counters);
+		
+		Some
+		(
+			AccurateExplicitCongestionNotificationOption
+			{
+				ect_zero_bytes: Some(byte_counters.0),
+				congestion_experienced_bytes: Some(byte_counters.1),
+				ect_one_bytes: Some(byte_counters.2),
+			}
+		)
+	}
+	
+	/// An acknowledgment has been received with an ACE field of `acknowledgment_field` and, perhaps, an AccECN option.
+	///
+	/// If there is no CE bytes counter in an option, the CE marked bytes are estimated as a full sized segment for every CE marked packet.
+	///
+	/// Returns `true` if CE marks have been fed back which have not yet been passed to the congestion control algorithm.
+	#[inline(always)]
+	pub fn acknowledgment_received(&mut self, acknowledgment_field: AccurateExplicitCongestionEchoField, accurate_explicit_congestion_notification_option: Option<&AccurateExplicitCongestionNotificationOption>, sender_maximum_segment_size: u32) -> bool
+	{
+		if self.ignore_next_acknowledgment_field
+		{
+			self.ignore_next_acknowledgment_field = false;
+			return false
+		}
+		
+		let congestion_experienced_packets = acknowledgment_field.delta(self.sent_congestion_experienced_packets);
+		self.sent_congestion_experienced_packets = self.sent_congestion_experienced_packets.wrapping_add(congestion_experienced_packets);
+		self.pending_congestion_experienced_packets += congestion_experienced_packets;
+		
+		let congestion_experienced_bytes = match accurate_explicit_congestion_notification_option.and_then(|option| option.congestion_experienced_bytes)
+		{
+			Some(congestion_experienced_bytes_counter) =>
+			{
+				let congestion_experienced_bytes = AccurateExplicitCongestionNotificationOption::delta(congestion_experienced_bytes_counter, self.sent_congestion_experienced_bytes);
+				self.sent_congestion_experienced_bytes = congestion_experienced_bytes_counter;
+				congestion_experienced_bytes
+			}
+			
+			None => congestion_experienced_packets * sender_maximum_segment_size,
+		};
+		self.pending_congestion_experienced_bytes += congestion_experienced_bytes;
+		
+		self.pending_congestion_experienced_packets != 0
+	}
+	
+	/// Takes the CE marked payload bytes fed back since this was last called.
+	#[inline(always)]
+	pub fn take_congestion_experienced_bytes(&mut self) -> u32
+	{
+		let congestion_experienced_bytes = self.pending_congestion_experienced_bytes;
+		self.pending_congestion_experienced_packets = 0;
+		self.pending_congestion_experienced_bytes = 0;
+		congestion_experienced_bytes
+	}
+}
@@ -0,0 +1,65 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Appropriate Byte Counting (ABC), as specified by RFC 3465, for use by congestion control algorithms.
+#[derive(Default, Debug)]
+pub(crate) struct AppropriateByteCounting
+{
+	/// Named as per RFC 3465 Section 2.1.
+	bytes_acked: u32,
+}
+
+impl AppropriateByteCounting
+{
+	/// Byte counting starts afresh, eg after a congestion response.
+	#[inline(always)]
+	pub(crate) fn reset(&mut self)
+	{
+		self.bytes_acked = 0;
+	}
+	
+	/// RFC 5681 Section 7 Paragraph 5: "During slow start, the usage of Appropriate Byte Counting (RFC 3465) with L=1*SMSS is explicitly recommended".
+	///
+	/// `bytes_acked` is only counted during congestion avoidance, so that it starts afresh when congestion avoidance is entered; were bytes acknowledged in slow start carried over, `cwnd` would be increased before a congestion window of data had been acknowledged in congestion avoidance.
+	#[inline(always)]
+	#[allow(non_snake_case)]
+	pub(crate) fn slow_start(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, N: u32)
+	{
+		self.bytes_acked = 0;
+		
+		// RFC 3465 Section 2.3: "The limit, L, chosen for the cwnd increase during slow start, controls the aggressiveness of the algorithm".
+		let L = congestion_control_state_variables.sender_maximum_segment_size();
+		
+		// RFC 5681 Section 3.1: "... we RECOMMEND that TCP implementations increase cwnd, per: cwnd += min (N, SMSS) where N is the number of previously unacknowledged bytes acknowledged in the incoming ACK".
+		congestion_control_state_variables.increment_congestion_window(min(N, L))
+	}
+	
+	/// RFC 5681 Section 3.1: "During congestion avoidance, cwnd is incremented by roughly 1 full-sized segment per round-trip time (RTT)".
+	///
+	/// This uses Appropriate Byte Counting (RFC 3465) rather than the per-ACK approximation `cwnd += SMSS*SMSS/cwnd` of RFC 5681 equation (3), which RFC 5681 Section 3.1 notes "can produce unacceptable behavior when ACKs are delayed or lost".
+	#[inline(always)]
+	#[allow(non_snake_case)]
+	pub(crate) fn congestion_avoidance(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, N: u32)
+	{
+		// RFC 3465 Section 2.1: "The bytes_acked variable is incremented by the number of bytes acknowledged by each arriving ACK".
+		self.bytes_acked = self.bytes_acked.saturating_add(N);
+		
+		// RFC 5681 Section 3.1: "When the number of bytes acknowledged reaches cwnd, then cwnd can be incremented by up to SMSS bytes.
+		// Note that during congestion avoidance, cwnd MUST NOT be increased by more than SMSS bytes per RTT".
+		//
+		// RFC 3465 Section 2.1: "When bytes_acked becomes greater than or equal to the value of the congestion window, bytes_acked is reduced by the value of cwnd.
+		// Next, cwnd is incremented by a full-sized segment (SMSS)".
+		let congestion_window = congestion_control_state_variables.congestion_window();
+		if self.bytes_acked >= congestion_window
+		{
+			self.bytes_acked -= congestion_window;
+			
+			// Any excess bytes_acked is kept so that a stretch acknowledgment does not lose credit, but is never allowed to cause more than one increment per acknowledgment.
+			self.bytes_acked = min(self.bytes_acked, congestion_window);
+			
+			let sender_maximum_segment_size = congestion_control_state_variables.sender_maximum_segment_size();
+			congestion_control_state_variables.increment_congestion_window(sender_maximum_segment_size)
+		}
+	}
+}
@@ -0,0 +1,660 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// BBR ('Bottleneck Bandwidth and Round-trip propagation time') version 1, as specified by draft-cardwell-iccrg-bbr-congestion-control-00.
+///
+/// BBR builds a model of the path from a windowed maximum of delivery rate samples (the bottleneck bandwidth, `BtlBw`) and a windowed minimum of round trip times (the round trip propagation time, `RTprop`), and sets a pacing rate and `cwnd` from it rather than reacting to loss.
+///
+/// Loss is responded to only by packet conservation during recovery; explicit congestion notification is not responded to.
+///
+/// The pacing rate is set in `CongestionControlStateVariables`.
+#[derive(Debug)]
+pub struct BbrCongestionControlAlgorithm
+{
+	state: BbrState,
+	
+	/// "BBR.BtlBwFilter": A windowed maximum of delivery rates, in bytes per second, over round trips.
+	bottleneck_bandwidth_filter: WindowedMaximumFilter,
+	
+	/// "BBR.RTprop": The round trip propagation time, in milliseconds.
+	round_trip_propagation_time: Option<u64>,
+	
+	/// "BBR.rtprop_stamp": When `round_trip_propagation_time` was last set.
+	round_trip_propagation_time_stamp: Option<MonotonicMillisecondTimestamp>,
+	
+	/// "BBR.rtprop_expired", if it was found when a round trip time was sampled.
+	round_trip_propagation_time_expired: bool,
+	
+	/// "BBR.pacing_gain".
+	pacing_gain: f64,
+	
+	/// "BBR.cwnd_gain".
+	congestion_window_gain: f64,
+	
+	/// "BBR.next_round_delivered".
+	next_round_delivered: u64,
+	
+	/// "BBR.round_count".
+	round_count: u64,
+	
+	/// "BBR.round_start".
+	round_start: bool,
+	
+	/// "C.delivered", as of the most recent delivery rate sample.
+	delivered: u64,
+	
+	/// "BBR.filled_pipe".
+	filled_pipe: bool,
+	
+	/// "BBR.full_bw".
+	full_bandwidth: u64,
+	
+	/// "BBR.full_bw_count".
+	full_bandwidth_count: u8,
+	
+	/// "BBR.cycle_index".
+	cycle_index: usize,
+	
+	/// "BBR.cycle_stamp".
+	cycle_stamp: Option<MonotonicMillisecondTimestamp>,
+	
+	/// When the data in flight was first at or below the minimum pipe congestion window after entering `ProbeRoundTripTime`; "BBR.probe_rtt_done_stamp" is this plus `ProbeRoundTripTimeDuration`.
+	probe_round_trip_time_minimum_reached_at: Option<MonotonicMillisecondTimestamp>,
+	
+	/// "BBR.probe_rtt_round_done".
+	probe_round_trip_time_round_done: bool,
+	
+	/// "BBR.prior_cwnd".
+	prior_congestion_window: u32,
+	
+	/// "BBR.packet_conservation".
+	packet_conservation: bool,
+	
+	/// Fast recovery has been entered; the acknowledgment hook is next called once it has been exited, when `cwnd` is restored.
+	in_fast_recovery: bool,
+	
+	/// "BBR.idle_restart".
+	idle_restart: bool,
+	
+	/// "InitialCwnd".
+	initial_congestion_window: Option<u32>,
+}
+
+impl Default for BbrCongestionControlAlgorithm
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			state: BbrState::Startup,
+			bottleneck_bandwidth_filter: WindowedMaximumFilter::default(),
+			round_trip_propagation_time: None,
+			round_trip_propagation_time_stamp: None,
+			round_trip_propagation_time_expired: false,
+			pacing_gain: Self::HighGain,
+			congestion_window_gain: Self::HighGain,
+			next_round_delivered: 0,
+			round_count: 0,
+			round_start: false,
+			delivered: 0,
+			filled_pipe: false,
+			full_bandwidth: 0,
+			full_bandwidth_count: 0,
+			cycle_index: 0,
+			cycle_stamp: None,
+			probe_round_trip_time_minimum_reached_at: None,
+			probe_round_trip_time_round_done: false,
+			prior_congestion_window: 0,
+			packet_conservation: false,
+			in_fast_recovery: false,
+			idle_restart: false,
+			initial_congestion_window: None,
+		}
+	}
+}
+
+impl CongestionControlAlgorithm for BbrCongestionControlAlgorithm
+{
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.6 "BBRSetCwnd()".
+	#[inline(always)]
+	fn acknowledgment(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, _now: MonotonicMillisecondTimestamp)
+	{
+		let initial_congestion_window = self.initial_congestion_window(congestion_control_state_variables);
+		
+		// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4: "Upon exiting loss recovery (RTO recovery or fast recovery), either by repairing all losses or undoing recovery, BBR restores the best-known cwnd value we had upon entering loss recovery".
+		if self.in_fast_recovery
+		{
+			self.in_fast_recovery = false;
+			self.packet_conservation = false;
+			self.restore_congestion_window(congestion_control_state_variables);
+		}
+		
+		let target_congestion_window = self.inflight(congestion_control_state_variables, self.congestion_window_gain);
+		let minimum_pipe_congestion_window = Self::minimum_pipe_congestion_window(congestion_control_state_variables);
+		
+		let mut congestion_window = congestion_control_state_variables.congestion_window();
+		
+		// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4: "BBRModulateCwndForRecovery()".
+		if self.packet_conservation
+		{
+			congestion_window = max(congestion_window, congestion_control_state_variables.FlightSize().saturating_add(bytes_acknowledged));
+		}
+		else
+		{
+			if self.filled_pipe
+			{
+				congestion_window = min(congestion_window.saturating_add(bytes_acknowledged), target_congestion_window);
+			}
+			else if congestion_window < target_congestion_window || self.delivered < initial_congestion_window as u64
+			{
+				congestion_window = congestion_window.saturating_add(bytes_acknowledged);
+			}
+			congestion_window = max(congestion_window, minimum_pipe_congestion_window);
+		}
+		
+		// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.5: "BBRModulateCwndForProbeRTT()".
+		if self.state == BbrState::ProbeRoundTripTime
+		{
+			congestion_window = min(congestion_window, minimum_pipe_congestion_window);
+		}
+		
+		congestion_control_state_variables.set_congestion_window(congestion_window)
+	}
+	
+	/// BBR does not use `ssthresh`, but `CongestionControl` reduces `cwnd` towards it during fast recovery; it is set to `FlightSize` so that `cwnd` conserves packets, as draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4 describes: "Upon entering Fast Recovery, set cwnd to the number of packets still in flight".
+	#[inline(always)]
+	fn loss(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		self.save_congestion_window(congestion_control_state_variables);
+		
+		let ssthresh = max(congestion_control_state_variables.FlightSize(), Self::minimum_pipe_congestion_window(congestion_control_state_variables));
+		congestion_control_state_variables.set_ssthresh(ssthresh);
+		
+		self.in_fast_recovery = true;
+	}
+	
+	/// BBR version 1 does not respond to explicit congestion notification.
+	#[inline(always)]
+	fn explicit_congestion_echo(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4: "Upon a retransmission timeout, ... BBR saves the current cwnd ... and sets cwnd to 1 packet"; `CongestionControl` sets `cwnd` to the loss window.
+	///
+	/// Packets are then conserved for a round trip, after which `cwnd` is restored.
+	#[inline(always)]
+	fn retransmission_time_out(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, is_first_retransmission: bool, _now: MonotonicMillisecondTimestamp)
+	{
+		if is_first_retransmission
+		{
+			self.save_congestion_window(congestion_control_state_variables);
+		}
+		
+		self.in_fast_recovery = false;
+		self.packet_conservation = true;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.1: "BBRHandleRestartFromIdle()".
+	#[inline(always)]
+	fn restart_after_idle(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		self.idle_restart = true;
+		
+		// "If we're in ProbeBW ... BBR paces at BBR.BtlBw".
+		if self.state == BbrState::ProbeBandwidth
+		{
+			let bottleneck_bandwidth = self.bottleneck_bandwidth();
+			if bottleneck_bandwidth != 0
+			{
+				congestion_control_state_variables.set_pacing_rate(Some(bottleneck_bandwidth))
+			}
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.1.2.3 "BBRUpdateRTprop()".
+	#[inline(always)]
+	fn round_trip_time_sample(&mut self, _congestion_control_state_variables: &CongestionControlStateVariables, measurement_of_round_trip_time: MillisecondDuration, _smoothed_round_trip_time: MillisecondDuration, now: MonotonicMillisecondTimestamp)
+	{
+		let measurement_of_round_trip_time: u64 = measurement_of_round_trip_time.into();
+		
+		let round_trip_propagation_time_expired = self.round_trip_propagation_time_has_expired(now);
+		
+		let is_new_minimum = match self.round_trip_propagation_time
+		{
+			None => true,
+			Some(round_trip_propagation_time) => measurement_of_round_trip_time <= round_trip_propagation_time,
+		};
+		
+		if is_new_minimum || round_trip_propagation_time_expired
+		{
+			self.round_trip_propagation_time = Some(measurement_of_round_trip_time);
+			self.round_trip_propagation_time_stamp = Some(now);
+		}
+		
+		self.round_trip_propagation_time_expired |= round_trip_propagation_time_expired;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2 "BBRUpdateModelAndState()" and "BBRSetPacingRate()".
+	#[inline(always)]
+	fn delivery_rate_sample(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, delivery_rate_sample: &DeliveryRateSample, now: MonotonicMillisecondTimestamp)
+	{
+		self.initial_congestion_window(congestion_control_state_variables);
+		
+		self.delivered = delivery_rate_sample.total_delivered();
+		
+		// `FlightSize` has not yet been decreased by the data acknowledged.
+		let prior_inflight = congestion_control_state_variables.FlightSize();
+		
+		self.update_bottleneck_bandwidth(delivery_rate_sample);
+		self.check_cycle_phase(congestion_control_state_variables, prior_inflight, now);
+		self.check_full_pipe(delivery_rate_sample);
+		self.check_drain(congestion_control_state_variables, prior_inflight, now);
+		self.check_probe_round_trip_time(congestion_control_state_variables, prior_inflight, now);
+		
+		// Packet conservation after a retransmission time out lasts for one round trip.
+		if self.packet_conservation && !self.in_fast_recovery && self.round_start
+		{
+			self.packet_conservation = false;
+			self.restore_congestion_window(congestion_control_state_variables);
+		}
+		
+		self.set_pacing_rate(congestion_control_state_variables);
+	}
+	
+	#[inline(always)]
+	fn congestion_response_undone(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		self.in_fast_recovery = false;
+		self.packet_conservation = false;
+	}
+}
+
+impl BbrCongestionControlAlgorithm
+{
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.2: "BBRHighGain = 2/ln(2)".
+	const HighGain: f64 = 2.885;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.3: "BBRGainCycleLen = 8".
+	const GainCycleLength: usize = 8;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.3: The pacing gains cycled through in `ProbeBandwidth`.
+	const PacingGainCycle: [f64; Self::GainCycleLength] = [5.0 / 4.0, 3.0 / 4.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.1.1.5: "BtlBwFilterLen: ... 10 packet-timed round trips".
+	const BottleneckBandwidthFilterLength: u64 = 10;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.3.1: "BBRGrowthRate ... 1.25".
+	const FullPipeGrowthRate: f64 = 1.25;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.3.1: "three rounds".
+	const FullPipeRounds: u8 = 3;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.5: "ProbeRTTDuration: 200 ms".
+	const ProbeRoundTripTimeDuration: u64 = 200;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.3: "In ProbeBW ... a cwnd_gain of 2".
+	const ProbeBandwidthCongestionWindowGain: f64 = 2.0;
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.3 "BBRMinPipeCwnd": "4 packets".
+	#[inline(always)]
+	fn minimum_pipe_congestion_window(congestion_control_state_variables: &CongestionControlStateVariables) -> u32
+	{
+		4 * congestion_control_state_variables.sender_maximum_segment_size()
+	}
+	
+	#[inline(always)]
+	fn initial_congestion_window(&mut self, congestion_control_state_variables: &CongestionControlStateVariables) -> u32
+	{
+		*self.initial_congestion_window.get_or_insert(congestion_control_state_variables.congestion_window())
+	}
+	
+	/// "BBR.BtlBw", in bytes per second.
+	#[inline(always)]
+	fn bottleneck_bandwidth(&self) -> u64
+	{
+		self.bottleneck_bandwidth_filter.maximum()
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.2 "BBRInflight(gain)", in bytes.
+	#[inline(always)]
+	fn inflight(&self, congestion_control_state_variables: &CongestionControlStateVariables, gain: f64) -> u32
+	{
+		match self.round_trip_propagation_time
+		{
+			// "No valid RTT samples yet".
+			None => self.initial_congestion_window.unwrap_or(congestion_control_state_variables.congestion_window()),
+			
+			Some(round_trip_propagation_time) =>
+			{
+				let estimated_bandwidth_delay_product = (self.bottleneck_bandwidth().saturating_mul(round_trip_propagation_time) / 1000) as f64;
+				
+				// "quanta = 3*BBR.send_quantum"; segments are not offloaded, so the send quantum is one segment.
+				let quanta = 3 * congestion_control_state_variables.sender_maximum_segment_size();
+				
+				let inflight = gain * estimated_bandwidth_delay_product;
+				if inflight >= (::std::u32::MAX as f64)
+				{
+					::std::u32::MAX
+				}
+				else
+				{
+					(inflight as u32).saturating_add(quanta)
+				}
+			}
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.1.1.5 "BBRUpdateBtlBw()".
+	#[inline(always)]
+	fn update_bottleneck_bandwidth(&mut self, delivery_rate_sample: &DeliveryRateSample)
+	{
+		self.update_round(delivery_rate_sample);
+		
+		// Samples over an interval shorter than the round trip propagation time are likely to be the result of compressed acknowledgments, and so over-estimate the delivery rate (draft-cheng-iccrg-delivery-rate-estimation Section 3.3).
+		if let Some(round_trip_propagation_time) = self.round_trip_propagation_time
+		{
+			if delivery_rate_sample.interval_in_milliseconds() < round_trip_propagation_time
+			{
+				return
+			}
+		}
+		
+		let delivery_rate = delivery_rate_sample.delivery_rate();
+		if delivery_rate >= self.bottleneck_bandwidth() || !delivery_rate_sample.is_application_limited()
+		{
+			self.bottleneck_bandwidth_filter.update(Self::BottleneckBandwidthFilterLength, self.round_count, delivery_rate);
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.1.1.3 "BBRUpdateRound()".
+	#[inline(always)]
+	fn update_round(&mut self, delivery_rate_sample: &DeliveryRateSample)
+	{
+		if delivery_rate_sample.prior_delivered() >= self.next_round_delivered
+		{
+			self.next_round_delivered = delivery_rate_sample.total_delivered();
+			self.round_count += 1;
+			self.round_start = true;
+		}
+		else
+		{
+			self.round_start = false;
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.4 "BBRCheckCyclePhase()".
+	#[inline(always)]
+	fn check_cycle_phase(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, prior_inflight: u32, now: MonotonicMillisecondTimestamp)
+	{
+		if self.state == BbrState::ProbeBandwidth && self.is_next_cycle_phase(congestion_control_state_variables, prior_inflight, now)
+		{
+			self.advance_cycle_phase(now)
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.4 "BBRIsNextCyclePhase()".
+	///
+	/// Losses are not known to delivery rate samples, so a phase with a pacing gain above one lasts until enough data is in flight or until, as for other phases, a round trip propagation time has passed.
+	#[inline(always)]
+	fn is_next_cycle_phase(&self, congestion_control_state_variables: &CongestionControlStateVariables, prior_inflight: u32, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		let is_full_length = match (self.cycle_stamp, self.round_trip_propagation_time)
+		{
+			(Some(cycle_stamp), Some(round_trip_propagation_time)) =>
+			{
+				let elapsed: u64 = (now - cycle_stamp).into();
+				elapsed > round_trip_propagation_time
+			}
+			
+			_ => false,
+		};
+		
+		if self.pacing_gain == 1.0
+		{
+			is_full_length
+		}
+		else if self.pacing_gain > 1.0
+		{
+			is_full_length && prior_inflight >= self.inflight(congestion_control_state_variables, self.pacing_gain)
+		}
+		else
+		{
+			is_full_length || prior_inflight <= self.inflight(congestion_control_state_variables, 1.0)
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.4 "BBRAdvanceCyclePhase()".
+	#[inline(always)]
+	fn advance_cycle_phase(&mut self, now: MonotonicMillisecondTimestamp)
+	{
+		self.cycle_stamp = Some(now);
+		self.cycle_index = (self.cycle_index + 1) % Self::GainCycleLength;
+		self.pacing_gain = Self::PacingGainCycle[self.cycle_index];
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.3.1 "BBRCheckFullPipe()".
+	#[inline(always)]
+	fn check_full_pipe(&mut self, delivery_rate_sample: &DeliveryRateSample)
+	{
+		if self.filled_pipe || !self.round_start || delivery_rate_sample.is_application_limited()
+		{
+			return
+		}
+		
+		// "BBR.BtlBw still growing?".
+		let bottleneck_bandwidth = self.bottleneck_bandwidth();
+		if (bottleneck_bandwidth as f64) >= (self.full_bandwidth as f64) * Self::FullPipeGrowthRate
+		{
+			// "Record new baseline level".
+			self.full_bandwidth = bottleneck_bandwidth;
+			self.full_bandwidth_count = 0;
+			return
+		}
+		
+		// "Another round w/o much growth".
+		self.full_bandwidth_count += 1;
+		if self.full_bandwidth_count >= Self::FullPipeRounds
+		{
+			self.filled_pipe = true;
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.2 "BBRCheckDrain()".
+	#[inline(always)]
+	fn check_drain(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, inflight: u32, now: MonotonicMillisecondTimestamp)
+	{
+		if self.state == BbrState::Startup && self.filled_pipe
+		{
+			self.enter_drain();
+		}
+		
+		if self.state == BbrState::Drain && inflight <= self.inflight(congestion_control_state_variables, 1.0)
+		{
+			// "We estimate queue is drained".
+			self.enter_probe_bandwidth(now);
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.5 "BBRCheckProbeRTT()".
+	#[inline(always)]
+	fn check_probe_round_trip_time(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, inflight: u32, now: MonotonicMillisecondTimestamp)
+	{
+		let round_trip_propagation_time_expired = self.round_trip_propagation_time_expired || self.round_trip_propagation_time_has_expired(now);
+		self.round_trip_propagation_time_expired = false;
+		
+		if self.state != BbrState::ProbeRoundTripTime && round_trip_propagation_time_expired && !self.idle_restart
+		{
+			self.enter_probe_round_trip_time();
+			self.save_congestion_window(congestion_control_state_variables);
+			self.probe_round_trip_time_minimum_reached_at = None;
+		}
+		
+		if self.state == BbrState::ProbeRoundTripTime
+		{
+			self.handle_probe_round_trip_time(congestion_control_state_variables, inflight, now);
+		}
+		
+		self.idle_restart = false;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.5 "BBRHandleProbeRTT()".
+	#[inline(always)]
+	fn handle_probe_round_trip_time(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, inflight: u32, now: MonotonicMillisecondTimestamp)
+	{
+		match self.probe_round_trip_time_minimum_reached_at
+		{
+			None =>
+			{
+				if inflight <= Self::minimum_pipe_congestion_window(congestion_control_state_variables)
+				{
+					self.probe_round_trip_time_minimum_reached_at = Some(now);
+					self.probe_round_trip_time_round_done = false;
+					self.next_round_delivered = self.delivered;
+				}
+			}
+			
+			Some(probe_round_trip_time_minimum_reached_at) =>
+			{
+				if self.round_start
+				{
+					self.probe_round_trip_time_round_done = true;
+				}
+				
+				let elapsed: u64 = (now - probe_round_trip_time_minimum_reached_at).into();
+				if self.probe_round_trip_time_round_done && elapsed > Self::ProbeRoundTripTimeDuration
+				{
+					self.round_trip_propagation_time_stamp = Some(now);
+					self.restore_congestion_window(congestion_control_state_variables);
+					self.exit_probe_round_trip_time(now);
+				}
+			}
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.1.2.3: "BBR.rtprop_expired = Now() > BBR.rtprop_stamp + RTpropFilterLen", where "RTpropFilterLen" is 10 seconds.
+	#[inline(always)]
+	fn round_trip_propagation_time_has_expired(&self, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		match self.round_trip_propagation_time_stamp
+		{
+			None => false,
+			Some(round_trip_propagation_time_stamp) => (now - round_trip_propagation_time_stamp) > MillisecondDuration::TenSeconds,
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.1 "BBRSetPacingRate()".
+	#[inline(always)]
+	fn set_pacing_rate(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		let bandwidth = match self.bottleneck_bandwidth()
+		{
+			// "BBRInitPacingRate()": "nominal_bandwidth = InitialCwnd / (SRTT ? SRTT : 1ms)".
+			0 =>
+			{
+				let initial_congestion_window = self.initial_congestion_window(congestion_control_state_variables) as u64;
+				let round_trip_time = max(self.round_trip_propagation_time.unwrap_or(1), 1);
+				initial_congestion_window * 1000 / round_trip_time
+			}
+			
+			bottleneck_bandwidth => bottleneck_bandwidth,
+		};
+		
+		let rate = (self.pacing_gain * (bandwidth as f64)) as u64;
+		
+		let increases_pacing_rate = match congestion_control_state_variables.pacing_rate()
+		{
+			None => true,
+			Some(pacing_rate) => rate > pacing_rate,
+		};
+		
+		if self.filled_pipe || increases_pacing_rate
+		{
+			congestion_control_state_variables.set_pacing_rate(Some(rate))
+		}
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4 "BBRSaveCwnd()".
+	#[inline(always)]
+	fn save_congestion_window(&mut self, congestion_control_state_variables: &CongestionControlStateVariables)
+	{
+		let congestion_window = congestion_control_state_variables.congestion_window();
+		
+		self.prior_congestion_window = if !self.in_fast_recovery && !self.packet_conservation && self.state != BbrState::ProbeRoundTripTime
+		{
+			congestion_window
+		}
+		else
+		{
+			max(self.prior_congestion_window, congestion_window)
+		};
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.2.3.4 "BBRRestoreCwnd()".
+	#[inline(always)]
+	fn restore_congestion_window(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		let congestion_window = max(congestion_control_state_variables.congestion_window(), self.prior_congestion_window);
+		congestion_control_state_variables.set_congestion_window(congestion_window)
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.1.1 "BBREnterStartup()".
+	#[inline(always)]
+	fn enter_startup(&mut self)
+	{
+		self.state = BbrState::Startup;
+		self.pacing_gain = Self::HighGain;
+		self.congestion_window_gain = Self::HighGain;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.2 "BBREnterDrain()".
+	#[inline(always)]
+	fn enter_drain(&mut self)
+	{
+		self.state = BbrState::Drain;
+		
+		// "Pace slowly".
+		self.pacing_gain = 1.0 / Self::HighGain;
+		
+		// "Maintain cwnd".
+		self.congestion_window_gain = Self::HighGain;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.4.3 "BBREnterProbeBW()".
+	#[inline(always)]
+	fn enter_probe_bandwidth(&mut self, now: MonotonicMillisecondTimestamp)
+	{
+		self.state = BbrState::ProbeBandwidth;
+		self.pacing_gain = 1.0;
+		self.congestion_window_gain = Self::ProbeBandwidthCongestionWindowGain;
+		
+		// "BBR.cycle_index = BBRGainCycleLen - 1 - random_int_between(0, 6)"; this never starts with the phase which drains (a pacing gain of 3/4).
+		let random = (generate_hyper_thread_safe_random_u64() % 7) as usize;
+		self.cycle_index = Self::GainCycleLength - 1 - random;
+		self.advance_cycle_phase(now);
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.5 "BBREnterProbeRTT()".
+	#[inline(always)]
+	fn enter_probe_round_trip_time(&mut self)
+	{
+		self.state = BbrState::ProbeRoundTripTime;
+		self.pacing_gain = 1.0;
+		self.congestion_window_gain = 1.0;
+	}
+	
+	/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3.5 "BBRExitProbeRTT()".
+	#[inline(always)]
+	fn exit_probe_round_trip_time(&mut self, now: MonotonicMillisecondTimestamp)
+	{
+		if self.filled_pipe
+		{
+			self.enter_probe_bandwidth(now)
+		}
+		else
+		{
+			self.enter_startup()
+		}
+	}
+}
@@ -0,0 +1,20 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// draft-cardwell-iccrg-bbr-congestion-control Section 4.3: The states of the BBR state machine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum BbrState
+{
+	/// Rapidly increasing the sending rate to find the bottleneck bandwidth.
+	Startup,
+	
+	/// Draining the queue created during `Startup`.
+	Drain,
+	
+	/// Cycling the pacing gain to probe for more bandwidth and then drain any queue created, whilst keeping the pipe full.
+	ProbeBandwidth,
+	
+	/// Briefly reducing the data in flight to re-measure the round trip propagation time.
+	ProbeRoundTripTime,
+}
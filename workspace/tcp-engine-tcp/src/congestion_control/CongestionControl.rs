@@ -3,8 +3,9 @@
 
 
 /// Congestion control.
+///
+/// The parts of congestion control which vary between algorithms, such as how `cwnd` grows and how it responds to loss, are delegated to a `CongestionControlAlgorithm`.
 #[derive(Debug)]
-#[allow(non_snake_case)]
 pub struct CongestionControl
 {
 	explicit_congestion_notification_state: Option<ExplicitCongestionNotificationState>,
@@ -13,54 +14,106 @@ pub struct CongestionControl
 	
 	number_of_duplicate_acknowledgments_received_since_SND_UNA_advanced: u64,
 	
-	last_sent_data_at: MonotonicMillisecondTimestamp,
-	
-	/// Named as per RFC 3465 Section 2.1.
+	/// RFC 6582 Section 3.2 Step 1: "... the TCP sender records the highest sequence number transmitted in the variable recover".
 	///
-	/// Known as 'Appropriate Byte Counting (ABC)'.
-	bytes_acked: u32,
+	/// `None` until fast retransmit or a retransmission time out first occurs; RFC 6582 initializes it to the initial send sequence number, which has the same effect.
+	recover: Option<WrappingSequenceNumber>,
 	
-	/// RFC 5681 Section 2: "CONGESTION WINDOW (cwnd):  A TCP state variable that limits the amount of data a TCP can send.
-	/// At any given time, a TCP MUST NOT send data with a sequence number higher than the sum of the highest acknowledged sequence number and the minimum of cwnd and rwnd".
-	cwnd: u32,
+	/// RFC 5681 Section 3.2: Are we in fast recovery?
+	in_fast_recovery: bool,
 	
-	/// RFC 5681 Section 2: "FLIGHT SIZE: The amount of data that has been sent but not yet cumulatively acknowledged".
-	FlightSize: u32,
+	/// Is fast recovery the conservative, selective acknowledgment (SACK) based loss recovery of RFC 6675 rather than that of RFC 6582 (NewReno)?
+	in_selective_acknowledgment_loss_recovery: bool,
 	
-	/// RFC 5681: Section 3.1: "... the slow start threshold (ssthresh), is used to determine whether the slow start or congestion avoidance algorithm is used to control data transmission ..."
-	ssthresh: u32,
+	/// RFC 6937: How `cwnd` is reduced during fast recovery.
+	proportional_rate_reduction: ProportionalRateReduction,
 	
-	/// RFC 5681 Section 2: "Sender Maximum Segment Size (SMSS)".
+	/// RFC 3708 Section 3: `SND.UNA` when the current (or most recent) loss recovery episode started; data retransmitted during the episode lies between this and `recover`.
 	///
-	/// This changes after the initial SYN has been sent, as the value reflects the use of options.
-	sender_maximum_segment_size: u32,
+	/// `None` if there is no congestion response which could be undone.
+	undo_marker: Option<WrappingSequenceNumber>,
+	
+	/// `cwnd` before the congestion response of the current loss recovery episode.
+	prior_cwnd: u32,
+	
+	/// `ssthresh` before the congestion response of the current loss recovery episode.
+	prior_ssthresh: u32,
+	
+	/// RFC 4015 Section 4 'pipe_prev': The larger of `FlightSize` and `ssthresh` before the congestion response of the current loss recovery episode.
+	prior_pipe: u32,
+	
+	/// RFC 3708 Section 3: The number of segments retransmitted during the current loss recovery episode which have not (yet) been reported as duplicates by a D-SACK block.
+	retransmissions_not_reported_as_duplicates: u32,
+	
+	/// `Some` whilst the most recent retransmission time out may yet be found to be spurious (RFC 3522 and RFC 5682).
+	spurious_retransmission_time_out_detection: Option<SpuriousRetransmissionTimeOutDetection>,
+	
+	/// `Some` if Congestion Window Validation for rate-limited senders (RFC 7661) is in use.
+	congestion_window_validation: Option<CongestionWindowValidation>,
+	
+	last_sent_data_at: MonotonicMillisecondTimestamp,
+	
+	/// `cwnd`, `ssthresh`, `FlightSize` and `SMSS`.
+	congestion_control_state_variables: CongestionControlStateVariables,
+	
+	congestion_control_algorithm: Box<CongestionControlAlgorithm>,
 }
 
 impl CongestionControl
 {
+	/// RFC 5681 Section 3.2 Paragraph 2: "The fast retransmit algorithm uses the arrival of 3 duplicate ACKs ...".
+	///
+	/// RFC 6675 Section 2 calls this 'DupThresh'.
+	const DuplicateAcknowledgmentThreshold: u64 = 3;
+	
+	/// Linux's default `net.ipv4.tcp_pacing_ss_ratio`.
+	const SlowStartPacingPercentage: u64 = 200;
+	
+	/// Linux's default `net.ipv4.tcp_pacing_ca_ratio`.
+	const CongestionAvoidancePacingPercentage: u64 = 120;
+	
 	/// Creates a new instance.
 	#[inline(always)]
-	pub fn new(explicit_congestion_notification_supported: bool, initial_congestion_window_algorithm: InitialCongestionWindowAlgorithm, last_sent_data_at: MonotonicMillisecondTimestamp, sender_maximum_segment_size: u16, recent_connection_data: &RecentConnectionData) -> Self
+	pub fn new(explicit_congestion_notification_supported: bool, initial_congestion_window_algorithm: InitialCongestionWindowAlgorithm, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor, last_sent_data_at: MonotonicMillisecondTimestamp, sender_maximum_segment_size: u16, recent_connection_data: &RecentConnectionData) -> Self
 	{
 		let sender_maximum_segment_size = sender_maximum_segment_size as u32;
 		
 		// RFC 5681 Section 2: "INITIAL WINDOW (IW): The initial window is the size of the sender's congestion window after the three-way handshake is completed".
 		#[allow(non_snake_case)] let IW = initial_congestion_window_algorithm.compute_initial_window(sender_maximum_segment_size);
 		
+		let congestion_control_algorithm = congestion_control_algorithm_constructor();
+		
 		Self
 		{
-			explicit_congestion_notification_state: ExplicitCongestionNotificationState::new(explicit_congestion_notification_supported),
+			explicit_congestion_notification_state: ExplicitCongestionNotificationState::new(explicit_congestion_notification_supported, congestion_control_algorithm.explicit_congestion_echo_per_segment(), recent_connection_data.explicit_congestion_notification_fall_back()),
 			initial_congestion_window_algorithm,
 			number_of_duplicate_acknowledgments_received_since_SND_UNA_advanced: 0,
+			recover: None,
+			in_fast_recovery: false,
+			in_selective_acknowledgment_loss_recovery: false,
+			proportional_rate_reduction: ProportionalRateReduction::default(),
+			undo_marker: None,
+			prior_cwnd: 0,
+			prior_ssthresh: 0,
+			prior_pipe: 0,
+			retransmissions_not_reported_as_duplicates: 0,
+			spurious_retransmission_time_out_detection: None,
+			congestion_window_validation: None,
 			last_sent_data_at,
-			bytes_acked: 0,
-			cwnd: IW,
-			FlightSize: 0,
-			ssthresh: recent_connection_data.ssthresh(sender_maximum_segment_size),
-			sender_maximum_segment_size,
+			congestion_control_state_variables: CongestionControlStateVariables::new(IW, recent_connection_data.ssthresh(sender_maximum_segment_size), sender_maximum_segment_size),
+			congestion_control_algorithm,
 		}
 	}
 	
+	/// Uses Congestion Window Validation for rate-limited senders (RFC 7661), so that `cwnd` is neither increased nor, after an idle period, reduced to the restart window whilst the sender is not using it.
+	///
+	/// RFC 7661 is experimental, so this is not the default.
+	#[inline(always)]
+	pub fn use_congestion_window_validation(&mut self)
+	{
+		self.congestion_window_validation = Some(CongestionWindowValidation::default())
+	}
+	
 	/// Disables explicit congestion notification.
 	///
 	/// Can only be called in the state SynchronizeSent.
@@ -70,11 +123,51 @@ impl CongestionControl
 		self.explicit_congestion_notification_state = None
 	}
 	
+	/// Requests Accurate ECN (RFC 9768) in our Synchronize, or uses it as a server once the client has negotiated it.
+	///
+	/// Does nothing if explicit congestion notification is not supported.
+	#[inline(always)]
+	pub fn use_accurate_explicit_congestion_notification(&mut self, accurate_explicit_congestion_notification_state: AccurateExplicitCongestionNotificationState)
+	{
+		if let Some(ref mut explicit_congestion_notification_state) = self.explicit_congestion_notification_state
+		{
+			explicit_congestion_notification_state.use_accurate_explicit_congestion_notification(accurate_explicit_congestion_notification_state)
+		}
+	}
+	
+	/// Falls back from Accurate ECN (RFC 9768) to classic ECN (RFC 3168).
+	///
+	/// Can only be called in the state SynchronizeSent.
+	#[inline(always)]
+	pub fn disable_accurate_explicit_congestion_notification(&mut self)
+	{
+		if let Some(ref mut explicit_congestion_notification_state) = self.explicit_congestion_notification_state
+		{
+			explicit_congestion_notification_state.disable_accurate_explicit_congestion_notification()
+		}
+	}
+	
+	/// RFC 9768: An acknowledgment (including a duplicate acknowledgment) has been received with an ACE field of `accurate_explicit_congestion_echo_field` and, perhaps, an AccECN option.
+	///
+	/// Returns `None` if Accurate ECN is not in use, otherwise whether CE marks have been fed back which are yet to be passed to the congestion control algorithm by `explicit_congestion_notification_feedback()`.
+	#[inline(always)]
+	pub fn accurate_explicit_congestion_notification_acknowledgment_received(&mut self, accurate_explicit_congestion_echo_field: AccurateExplicitCongestionEchoField, accurate_explicit_congestion_notification_option: Option<&AccurateExplicitCongestionNotificationOption>) -> Option<bool>
+	{
+		let sender_maximum_segment_size = self.sender_maximum_segment_size();
+		
+		match self.explicit_congestion_notification_state
+		{
+			None => None,
+			
+			Some(ref mut explicit_congestion_notification_state) => explicit_congestion_notification_state.accurate_explicit_congestion_notification_mutable_reference().map(|accurate_explicit_congestion_notification_state| accurate_explicit_congestion_notification_state.acknowledgment_received(accurate_explicit_congestion_echo_field, accurate_explicit_congestion_notification_option, sender_maximum_segment_size)),
+		}
+	}
+	
 	/// `ssthresh`; used for recent connection data.
 	#[inline(always)]
 	pub fn ssthresh(&self) -> u32
 	{
-		self.ssthresh
+		self.congestion_control_state_variables.ssthresh()
 	}
 	
 	/// When entering the Established state for connections opened outbound (as a client), the maximum segment size may have changed.
@@ -82,7 +175,7 @@ impl CongestionControl
 	#[inline(always)]
 	pub fn entering_established_state(&mut self, sender_maximum_segment_size: u16)
 	{
-		self.sender_maximum_segment_size = sender_maximum_segment_size as u32;
+		self.congestion_control_state_variables.set_sender_maximum_segment_size(sender_maximum_segment_size as u32);
 		self.reset_congestion_window_to_initial_window()
 	}
 	
@@ -92,105 +185,615 @@ impl CongestionControl
 	#[inline(always)]
 	pub fn congestion_window_is_one(&self) -> bool
 	{
-		self.congestion_window() <= self.sender_maximum_segment_size
+		self.congestion_window() <= self.sender_maximum_segment_size()
 	}
 	
-	// TODO: See also RFC 6582 New Reno: https://tools.ietf.org/html/rfc6582; this is a modification to fast retransmit / Fast Recovery.
 	#[allow(missing_docs)]
 	#[inline(always)]
 	pub fn bytes_sent_in_payload_in_a_segment_which_is_not_a_zero_window_probe_or_retransmission(&mut self, increase_flight_size_by_amount_of_bytes: u32)
 	{
-		self.FlightSize += increase_flight_size_by_amount_of_bytes
+		self.congestion_control_state_variables.increase_flight_size(increase_flight_size_by_amount_of_bytes);
+		
+		if self.in_fast_recovery
+		{
+			self.proportional_rate_reduction.sent(increase_flight_size_by_amount_of_bytes)
+		}
+	}
+	
+	/// Increase bytes acknowledged for an acknowledgment which moved `SND.UNA` to `SEG.ACK`.
+	///
+	/// Returns `true` if this was a partial acknowledgment during fast recovery; the caller must then either retransmit the first unacknowledged segment (RFC 6582 Section 3.2 Step 3) or, if in selective acknowledgment loss recovery, transmit segments as permitted by RFC 6675 Section 5 Step (C).
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn increase_bytes_acknowledged(&mut self, decrease_flight_size_by_amount_of_bytes: u32, SEG_ACK: WrappingSequenceNumber, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		self.congestion_control_state_variables.decrease_flight_size(decrease_flight_size_by_amount_of_bytes);
+		
+		let congestion_window = self.congestion_window();
+		if let Some(ref mut congestion_window_validation) = self.congestion_window_validation
+		{
+			congestion_window_validation.acknowledged(decrease_flight_size_by_amount_of_bytes, congestion_window, now);
+		}
+		
+		if self.in_fast_recovery
+		{
+			return self.acknowledgment_during_fast_recovery(decrease_flight_size_by_amount_of_bytes, SEG_ACK)
+		}
+		
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.acknowledgment(congestion_control_state_variables, decrease_flight_size_by_amount_of_bytes, now));
+		
+		// RFC 7661: In the non-validated phase, `cwnd` is not increased.
+		let is_non_validated = match self.congestion_window_validation
+		{
+			None => false,
+			Some(ref congestion_window_validation) => !congestion_window_validation.is_validated(congestion_window),
+		};
+		if is_non_validated && self.congestion_window() > congestion_window
+		{
+			self.set_congestion_window(congestion_window);
+		}
+		
+		false
 	}
 	
-	/// Increase bytes acknowledged
+	/// RFC 6582 Section 3.2 Step 3: "When an ACK arrives that acknowledges new data, this ACK could be the acknowledgment elicited by the initial retransmission from fast retransmit or elicited by a later retransmission".
+	#[allow(non_snake_case)]
 	#[inline(always)]
-	pub fn increase_bytes_acknowledged(&mut self, decrease_flight_size_by_amount_of_bytes: u32)
+	fn acknowledgment_during_fast_recovery(&mut self, bytes_acknowledged: u32, SEG_ACK: WrappingSequenceNumber) -> bool
 	{
-		self.FlightSize -= decrease_flight_size_by_amount_of_bytes;
+		let recover = self.recover.expect("recover is always set when in fast recovery");
 		
-		// RFC 5681 Section 3.1: "The slow start algorithm is used when cwnd < ssthresh, while the congestion avoidance algorithm is used when cwnd > ssthresh.
-		// When cwnd and ssthresh are equal, the sender may use either slow start or congestion avoidance".
-		if self.congestion_window() <= self.ssthresh
+		if self.in_selective_acknowledgment_loss_recovery
 		{
-			self.increase_bytes_acknowledged_slow_start(decrease_flight_size_by_amount_of_bytes)
+			// RFC 6675 Section 5 Paragraph 2: "... (RecoveryPoint) ... When the TCP sender receives a cumulative ACK for this data octet, the loss recovery phase is terminated".
+			//
+			// RFC 6937 Section 3.1: "On exiting recovery: cwnd = ssthresh".
+			if SEG_ACK > recover
+			{
+				self.set_congestion_window_on_exiting_loss_recovery();
+				self.in_fast_recovery = false;
+				self.in_selective_acknowledgment_loss_recovery = false;
+				return false
+			}
+			
+			// The caller reduces `cwnd` using `proportional_rate_reduction_during_selective_acknowledgment_loss_recovery()`.
+			return true
 		}
+		
+		// RFC 6582 Section 3.2 Step 3: "Full acknowledgments: If this ACK acknowledges all of the data up to and including recover, then the ACK acknowledges all the intermediate segments sent between the original transmission of the lost segment and the receipt of the third duplicate ACK.
+		// Set cwnd to either (1) min (ssthresh, max(FlightSize, SMSS) + SMSS) or (2) ssthresh, where ssthresh is the value set when fast retransmit was entered, and where FlightSize in (1) is the amount of data presently outstanding.
+		// This is termed "deflating" the window.
+		// If the second option is selected, the implementation is encouraged to take measures to avoid a possible burst of data, in case the amount of data outstanding in the network is much less than the new congestion window allows.
+		// Exit the fast recovery procedure".
+		//
+		// RFC 6937 Section 3.1: "On exiting recovery: cwnd = ssthresh"; proportional rate reduction has already brought the data outstanding close to ssthresh, so there is no burst.
+		if SEG_ACK > recover
+		{
+			self.set_congestion_window_on_exiting_loss_recovery();
+			self.in_fast_recovery = false;
+			false
+		}
+		// RFC 6582 Section 3.2 Step 3: "Partial acknowledgments: If this ACK does *not* acknowledge all of the data up to and including recover, then this is a partial ACK.
+		// In this case, retransmit the first unacknowledged segment.
+		// ...
+		// Do not exit the fast recovery procedure (i.e., if any duplicate ACKs subsequently arrive, execute step 4 of Section 3.2 of [RFC5681])".
+		//
+		// Rather than the "partial window deflation" of RFC 6582, `cwnd` is set by proportional rate reduction (RFC 6937).
 		else
 		{
-			self.increase_bytes_acknowledged_congestion_avoidance(decrease_flight_size_by_amount_of_bytes)
+			let delivered_data = self.proportional_rate_reduction.delivered_data_for_cumulative_acknowledgment(bytes_acknowledged);
+			self.proportional_rate_reduction_without_selective_acknowledgments(delivered_data);
+			true
 		}
 	}
 	
-	/// RFC 5681: "... N is the number of previously unacknowledged bytes acknowledged in the incoming ACK".
+	/// Should fast retransmit be entered now that a duplicate acknowledgment has been counted?
 	///
-	/// `N` is `bytes_acked` in RFC 3465.
+	/// RFC 5681 Section 3.2 Paragraph 2: "The fast retransmit algorithm uses the arrival of 3 duplicate ACKs ... as an indication that a segment has been lost".
+	///
+	/// RFC 6582 Section 3.2 Step 2: "When the third duplicate ACK is received, the TCP sender first checks the value of recover to see if the Cumulative Acknowledgment field covers more than recover.
+	/// If so, the value of recover is incremented to the value of the highest sequence number transmitted by the TCP so far.
+	/// The TCP then enters fast retransmit (step 2 of Section 3.2 of [RFC5681]).
+	/// If not, the TCP does not enter fast retransmit and does not reset ssthresh".
+	#[allow(non_snake_case)]
 	#[inline(always)]
+	pub fn enter_fast_retransmit_and_fast_recovery_if_third_duplicate_acknowledgment(&mut self, SND_UNA_less_one: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		if self.in_fast_recovery || self.number_of_duplicate_acknowledgments_received_since_SND_UNA_advanced != Self::DuplicateAcknowledgmentThreshold
+		{
+			return false
+		}
+		
+		if !self.cumulative_acknowledgment_covers_more_than_recover(SND_UNA_less_one)
+		{
+			return false
+		}
+		self.save_state_for_undo_if_starting_a_new_loss_recovery_episode(SND_UNA_less_one);
+		self.recover = Some(SND_NXT);
+		self.congestion_window_validation_loss_recovery_started();
+		
+		// RFC 5681 Section 3.2 Step 2: "When the third duplicate ACK is received, a TCP MUST set ssthresh to no more than the value given in equation (4)".
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.loss(congestion_control_state_variables, now));
+		
+		// RFC 5681 Section 3.2 Step 3: "The lost segment starting at SND.UNA MUST be retransmitted and cwnd set to ssthresh plus 3*SMSS".
+		//
+		// Instead, `cwnd` is set by proportional rate reduction (RFC 6937); the duplicate acknowledgments before this one are estimated to have delivered a segment each.
+		let sender_maximum_segment_size = self.sender_maximum_segment_size();
+		let RecoverFS = self.congestion_control_state_variables.FlightSize();
+		self.proportional_rate_reduction.start(RecoverFS, (Self::DuplicateAcknowledgmentThreshold as u32 - 1) * sender_maximum_segment_size);
+		
+		self.in_fast_recovery = true;
+		
+		let delivered_data = self.proportional_rate_reduction.delivered_data_for_duplicate_acknowledgment(sender_maximum_segment_size);
+		self.proportional_rate_reduction_without_selective_acknowledgments(delivered_data);
+		
+		true
+	}
+	
+	/// Should selective acknowledgment (SACK) based loss recovery be entered now that a duplicate acknowledgment has been counted?
+	///
+	/// RFC 6675 Section 5 Step (2): "If DupAcks < DupThresh but IsLost (HighACK + 1) returns true -- indicating at least three segments have arrived above the current cumulative acknowledgment point, which is taken to indicate loss -- go to step (4)".
+	///
+	/// RFC 6675 Section 5 Step (4): "Invoke fast retransmit and enter loss recovery as follows:
+	/// (4.1) RecoveryPoint = HighData ...
+	/// (4.2) ssthresh = cwnd = (FlightSize / 2)".
+	///
+	/// As for RFC 6582, loss recovery is not entered again until the cumulative acknowledgment covers more than the previous RecoveryPoint (`recover`).
+	///
+	/// If losses are detected by RACK (RFC 8985) rather than by counting duplicate acknowledgments, `first_unacknowledged_segment_is_lost` is instead whether RACK has marked any segment as lost.
 	#[allow(non_snake_case)]
-	fn increase_bytes_acknowledged_slow_start(&mut self, N: u32)
+	#[inline(always)]
+	pub fn enter_selective_acknowledgment_loss_recovery_if_appropriate(&mut self, SND_UNA_less_one: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, first_unacknowledged_segment_is_lost: bool, now: MonotonicMillisecondTimestamp) -> bool
 	{
-		let sender_maximum_segment_size = self.sender_maximum_segment_size;
-
-		// RFC 5681 Section 7 Paragraph 5: "During slow start, the usage of Appropriate Byte Counting (RFC 3465) with L=1*SMSS is explicitly recommended".
+		if self.in_fast_recovery
+		{
+			return false
+		}
+		
+		if self.number_of_duplicate_acknowledgments_received_since_SND_UNA_advanced < Self::DuplicateAcknowledgmentThreshold && !first_unacknowledged_segment_is_lost
+		{
+			return false
+		}
+		
+		if !self.cumulative_acknowledgment_covers_more_than_recover(SND_UNA_less_one)
+		{
+			return false
+		}
+		self.save_state_for_undo_if_starting_a_new_loss_recovery_episode(SND_UNA_less_one);
+		self.recover = Some(SND_NXT);
+		self.congestion_window_validation_loss_recovery_started();
+		
+		// RFC 6675 Section 5 Step (4.2) defers to equation (4) of RFC 5681 Section 3.1 for the value of ssthresh.
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.loss(congestion_control_state_variables, now));
+		
+		// Rather than setting cwnd to ssthresh, `cwnd` is set by proportional rate reduction (RFC 6937) using `proportional_rate_reduction_during_selective_acknowledgment_loss_recovery()`, which the caller must call for this acknowledgment and every subsequent one during loss recovery.
+		let RecoverFS = self.congestion_control_state_variables.FlightSize();
+		self.proportional_rate_reduction.start(RecoverFS, 0);
+		
+		self.in_fast_recovery = true;
+		self.in_selective_acknowledgment_loss_recovery = true;
+		
+		true
+	}
+	
+	/// RFC 6937 Section 3.1: "On every ACK during recovery", when using selective acknowledgment (SACK) based loss recovery (RFC 6675).
+	///
+	/// `bytes_cumulatively_acknowledged` is zero for a duplicate acknowledgment; `selectively_acknowledged` is the number of bytes now selectively acknowledged ('SACKd'), and `pipe` is as computed by RFC 6675 Section 4 SetPipe ().
+	#[inline(always)]
+	pub fn proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(&mut self, bytes_cumulatively_acknowledged: u32, selectively_acknowledged: u32, pipe: u32)
+	{
+		debug_assert!(self.in_selective_acknowledgment_loss_recovery, "not in selective acknowledgment loss recovery");
+		
+		let delivered_data = self.proportional_rate_reduction.delivered_data_with_selective_acknowledgments(bytes_cumulatively_acknowledged, selectively_acknowledged);
+		
+		let ssthresh = self.ssthresh();
+		let sender_maximum_segment_size = self.sender_maximum_segment_size();
+		let sndcnt = self.proportional_rate_reduction.send_count(delivered_data, pipe, ssthresh, sender_maximum_segment_size);
+		
+		// "cwnd = pipe + sndcnt".
+		self.set_congestion_window(pipe.saturating_add(sndcnt))
+	}
+	
+	/// RFC 6937 Section 3.1: "On every ACK during recovery", when using NewReno fast recovery (RFC 6582).
+	///
+	/// Transmission is limited by all the data outstanding (`FlightSize`) rather than by 'pipe', so `cwnd` is set to `FlightSize` plus 'sndcnt'.
+	#[inline(always)]
+	fn proportional_rate_reduction_without_selective_acknowledgments(&mut self, delivered_data: u32)
+	{
+		let flight_size = self.congestion_control_state_variables.FlightSize();
+		let pipe = self.proportional_rate_reduction.pipe_without_selective_acknowledgments(flight_size);
+		
+		let ssthresh = self.ssthresh();
+		let sender_maximum_segment_size = self.sender_maximum_segment_size();
+		let sndcnt = self.proportional_rate_reduction.send_count(delivered_data, pipe, ssthresh, sender_maximum_segment_size);
+		
+		self.set_congestion_window(flight_size.saturating_add(sndcnt))
+	}
+	
+	/// Saves `cwnd` and `ssthresh` so that the congestion response about to be made can be undone if the retransmissions it accompanies turn out to be spurious (RFC 3708).
+	///
+	/// Must be called before `ssthresh` or `cwnd` are reduced, on entering fast recovery or on a retransmission time out.
+	///
+	/// If a loss recovery episode is already in progress (eg a retransmission time out occurs during fast recovery, or a segment is retransmitted again by the retransmission timer), the values from before the episode started are kept.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn save_state_for_undo_if_starting_a_new_loss_recovery_episode(&mut self, SND_UNA_less_one: WrappingSequenceNumber)
+	{
+		let loss_recovery_episode_in_progress = self.undo_marker.is_some() && (self.in_fast_recovery || !self.cumulative_acknowledgment_covers_more_than_recover(SND_UNA_less_one));
+		if loss_recovery_episode_in_progress
 		{
-			// RFC 3465 Section 2.3: "The limit, L, chosen for the cwnd increase during slow start, controls the aggressiveness of the algorithm".
-			let L = sender_maximum_segment_size;
+			return
+		}
+		
+		self.undo_marker = Some(SND_UNA_less_one + 1u32);
+		self.prior_cwnd = self.congestion_window();
+		self.prior_ssthresh = self.ssthresh();
+		self.prior_pipe = max(self.congestion_control_state_variables.FlightSize(), self.prior_ssthresh);
+		self.retransmissions_not_reported_as_duplicates = 0;
+	}
+	
+	/// A segment with a payload of `bytes` has been retransmitted, by fast retransmit, during fast recovery or because the retransmission timer expired.
+	#[inline(always)]
+	pub fn segment_retransmitted(&mut self, bytes: u32)
+	{
+		if self.undo_marker.is_some()
+		{
+			self.retransmissions_not_reported_as_duplicates += 1;
+		}
+		
+		if self.in_fast_recovery
+		{
+			if let Some(ref mut congestion_window_validation) = self.congestion_window_validation
+			{
+				congestion_window_validation.retransmitted_during_loss_recovery(bytes);
+			}
 			
-			let clamped_maxium_number_of_bytes = max(N, L);
+			self.proportional_rate_reduction.sent(bytes);
 			
-			self.bytes_acked += clamped_maxium_number_of_bytes;
+			// RFC 6937 Section 3.1: A retransmission uses part of 'sndcnt'.
+			// With selective acknowledgments, 'pipe' has increased to account for it; otherwise transmission is limited by `FlightSize`, which a retransmission does not increase, so `cwnd` is reduced instead.
+			if !self.in_selective_acknowledgment_loss_recovery
+			{
+				let congestion_window = self.congestion_window().saturating_sub(bytes);
+				self.set_congestion_window(congestion_window);
+			}
+		}
+	}
+	
+	/// RFC 3708 Section 3: If every segment retransmitted during a loss recovery episode is reported as a duplicate by a D-SACK block (RFC 2883), then all of the retransmissions were unnecessary (spurious), most likely because of reordering or a delayed acknowledgment.
+	///
+	/// When this is so, the congestion response is undone: `cwnd` and `ssthresh` are restored to (at least) their values before the episode started and loss recovery is exited.
+	///
+	/// Returns `true` if the congestion response was undone; the caller should then also stop inflating the retransmission time out (back off).
+	#[inline(always)]
+	pub fn duplicate_selective_acknowledgment_received(&mut self, duplicate_selective_acknowledgment_block: SelectiveAcknowledgmentBlock) -> bool
+	{
+		let undo_marker = match self.undo_marker
+		{
+			None => return false,
 			
-			// RFC 3465 Section 2.1: "When bytes_acked becomes greater than or equal to the value of the congestion window, bytes_acked is reduced by the value of cwnd".
-			if self.bytes_acked >= self.congestion_window()
+			Some(undo_marker) => undo_marker,
+		};
+		
+		let recover = self.recover.expect("recover is always set when undo_marker is");
+		
+		// Only a duplicate of data sent before the loss recovery episode started can be a duplicate caused by a retransmission made during it.
+		if duplicate_selective_acknowledgment_block.left_edge_of_block < undo_marker || duplicate_selective_acknowledgment_block.right_edge_of_block > recover
+		{
+			return false
+		}
+		
+		if self.retransmissions_not_reported_as_duplicates == 0
+		{
+			return false
+		}
+		
+		self.retransmissions_not_reported_as_duplicates -= 1;
+		if self.retransmissions_not_reported_as_duplicates != 0
+		{
+			return false
+		}
+		
+		self.undo_congestion_response_because_retransmissions_were_spurious();
+		true
+	}
+	
+	/// If the most recent retransmission time out may yet be found to be spurious, when the segment was retransmitted.
+	///
+	/// The caller should then pass whether the timestamp echoed by the next acknowledgment is from before this time to `acknowledgment_after_retransmission_time_out()`.
+	#[inline(always)]
+	pub fn retransmitted_at_if_detecting_spurious_retransmission_time_out(&self) -> Option<MonotonicMillisecondTimestamp>
+	{
+		self.spurious_retransmission_time_out_detection.as_ref().map(|spurious_retransmission_time_out_detection| spurious_retransmission_time_out_detection.retransmitted_at())
+	}
+	
+	/// An acknowledgment which moved `SND.UNA` to `SEG.ACK` by `bytes_acknowledged` has been received whilst the most recent retransmission time out may yet be found to be spurious; it must be called after `increase_bytes_acknowledged()`.
+	///
+	/// `timestamp_echo_reply_is_earlier_than_retransmission` is `None` if timestamps are not in use; otherwise it is whether the acknowledgment echoed the timestamp of a segment transmitted before the retransmission.
+	/// `new_data_is_available` is whether there is data which has not yet been transmitted.
+	///
+	/// Returns `true` if the retransmission time out was spurious and the congestion response to it has been undone; the caller should then also stop inflating (backing off) the retransmission time out and adapt it (RFC 4015 Section 4).
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn acknowledgment_after_retransmission_time_out(&mut self, SEG_ACK: WrappingSequenceNumber, bytes_acknowledged: u32, timestamp_echo_reply_is_earlier_than_retransmission: Option<bool>, explicit_congestion_echo: bool, new_data_is_available: bool) -> bool
+	{
+		let (is_awaiting_first_acknowledgment, recover) = match self.spurious_retransmission_time_out_detection
+		{
+			None => return false,
+			
+			Some(ref spurious_retransmission_time_out_detection) => (spurious_retransmission_time_out_detection.is_awaiting_first_acknowledgment(), spurious_retransmission_time_out_detection.recover()),
+		};
+		
+		let is_spurious = if is_awaiting_first_acknowledgment
+		{
+			match timestamp_echo_reply_is_earlier_than_retransmission
+			{
+				// RFC 3522 Section 2: If the acknowledgment of the retransmitted segment echoes a timestamp earlier than that of the retransmission, it was the original transmission which arrived, and so the retransmission was spurious.
+				Some(timestamp_echo_reply_is_earlier_than_retransmission) => timestamp_echo_reply_is_earlier_than_retransmission,
+				
+				// RFC 5682 Section 2.1 Step 2a: If the acknowledgment acknowledges everything up to "recover" then there is nothing left to distinguish a spurious retransmission time out; conventional recovery continues.
+				// The retransmission was of exactly one whole segment, so an acknowledgment which moves `SND.UNA` acknowledges all of it.
+				//
+				// RFC 5682 Section 2.1 Step 2b: Otherwise, if there is new data, up to two new segments are transmitted; the original transmissions of segments after the one retransmitted should then be acknowledged if they were not lost.
+				// If there is no new data, conventional recovery continues.
+				None =>
+				{
+					if SEG_ACK >= recover || !new_data_is_available
+					{
+						self.spurious_retransmission_time_out_detection = None;
+						return false
+					}
+					
+					let congestion_window = self.congestion_window();
+					self.spurious_retransmission_time_out_detection.as_mut().unwrap().transmitting_new_segments(congestion_window);
+					
+					let permits_two_new_segments = self.congestion_control_state_variables.FlightSize() + 2 * self.sender_maximum_segment_size();
+					self.set_congestion_window(max(congestion_window, permits_two_new_segments));
+					return false
+				}
+			}
+		}
+		else
+		{
+			// RFC 5682 Section 2.1 Step 3b: The second acknowledgment after the retransmission acknowledges data which was not retransmitted, so the retransmission time out was spurious.
+			true
+		};
+		
+		self.spurious_retransmission_time_out_detection = None;
+		
+		if is_spurious
+		{
+			self.undo_congestion_response_because_retransmission_time_out_was_spurious(bytes_acknowledged, explicit_congestion_echo);
+		}
+		
+		is_spurious
+	}
+	
+	/// RFC 5682 Section 2.1 Steps 2a and 3a: A duplicate acknowledgment after a retransmission time out indicates that a segment was lost, so the retransmission time out was not spurious and conventional recovery continues.
+	#[inline(always)]
+	pub fn duplicate_acknowledgment_after_retransmission_time_out(&mut self)
+	{
+		if let Some(spurious_retransmission_time_out_detection) = self.spurious_retransmission_time_out_detection.take()
+		{
+			if let Some(congestion_window) = spurious_retransmission_time_out_detection.congestion_window_for_conventional_recovery()
 			{
-				self.bytes_acked -= self.congestion_window()
+				self.set_congestion_window(congestion_window)
 			}
 		}
-		// RFC 3465 Section 2.1: "Next, cwnd is incremented by a full-sized segment (SMSS)".
-		// RFC 5681 Section 3.1: "... we RECOMMEND that TCP implementations increase cwnd, per: cwnd += min (N, SMSS) where N is the number of previously unacknowledged bytes acknowledged in the incoming ACK".
-		self.increment_congestion_window(min(N, sender_maximum_segment_size))
 	}
 	
+	/// RFC 6675 Section 5 Step (4.4): "... If (cwnd - pipe) >= 1 SMSS, there exists previously unsent data, and the receiver's advertised window allows, transmit up to 1 SMSS of data ...".
+	#[inline(always)]
+	pub fn congestion_window_permits_transmitting_a_segment_during_loss_recovery(&self, pipe: u32) -> bool
+	{
+		self.congestion_window().saturating_sub(pipe) >= self.sender_maximum_segment_size()
+	}
+	
+	/// Are we in fast recovery?
+	#[inline(always)]
+	pub fn is_in_fast_recovery(&self) -> bool
+	{
+		self.in_fast_recovery
+	}
+	
+	/// Are we in selective acknowledgment (SACK) based loss recovery (RFC 6675)?
 	#[inline(always)]
+	pub fn is_in_selective_acknowledgment_loss_recovery(&self) -> bool
+	{
+		self.in_selective_acknowledgment_loss_recovery
+	}
+	
+	/// Are we in fast recovery, or recovering from a retransmission time out (ie the cumulative acknowledgment does not yet cover more than `recover`)?
 	#[allow(non_snake_case)]
-	fn increase_bytes_acknowledged_congestion_avoidance(&mut self, N: u32)
+	#[inline(always)]
+	pub fn is_in_loss_recovery(&self, SND_UNA_less_one: WrappingSequenceNumber) -> bool
 	{
-		// TODO: double-check this methodology; it seems wrong.
-		panic!("Unimplemented");
-
-//		let clamped_maxium_number_of_bytes = min(N, self.sender_maximum_segment_size);
-//		self.bytes_acked += clamped_maxium_number_of_bytes;
-//
-//		// RFC 5681: "When the number of bytes acknowledged reaches cwnd, then cwnd can be incremented by up to SMSS bytes.
-//		// Note that during congestion avoidance, cwnd MUST NOT be increased by more than SMSS bytes per RTT".
-//		//
-//		// RFC 3465 Section 2.1: "When bytes_acked becomes greater than or equal to the value of the congestion window, bytes_acked is reduced by the value of cwnd
-//		// ...
-//		// Next, cwnd is incremented by a full-sized segment (SMSS)".
-//		if self.bytes_acked >= self.cwnd
-//		{
-//			self.bytes_acked -= self.cwnd;
-//			self.cwnd += self.sender_maximum_segment_size
-//		}
-
-
-//		let increment = ((self.sender_maximum_segment_size as u32) * (self.sender_maximum_segment_size as u32)) / self.cwnd;
-//		self.cwnd += if increment == 0
-//		{
-//			1
-//		}
-//		else
-//		{
-//			increment
-//		};
+		self.in_fast_recovery || !self.cumulative_acknowledgment_covers_more_than_recover(SND_UNA_less_one)
+	}
+	
+	/// RFC 8985 Section 7.4.2: The retransmission made by a tail loss probe repaired the loss of a segment, without loss recovery being entered.
+	///
+	/// The congestion response is that of fast recovery; as the loss has already been repaired, `cwnd` is reduced to the new value of `ssthresh` at once rather than by proportional rate reduction.
+	#[inline(always)]
+	pub fn loss_repaired_by_tail_loss_probe(&mut self, now: MonotonicMillisecondTimestamp)
+	{
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.loss(congestion_control_state_variables, now));
+		
+		let ssthresh = self.ssthresh();
+		if self.congestion_window() > ssthresh
+		{
+			self.set_congestion_window(ssthresh);
+		}
+	}
+	
+	/// RFC 5681 Section 2: "Sender Maximum Segment Size (SMSS)".
+	#[inline(always)]
+	pub fn sender_maximum_segment_size(&self) -> u32
+	{
+		self.congestion_control_state_variables.sender_maximum_segment_size()
+	}
+	
+	#[inline(always)]
+	fn undo_congestion_response_because_retransmissions_were_spurious(&mut self)
+	{
+		let prior_congestion_window = max(self.congestion_window(), self.prior_cwnd);
+		self.set_congestion_window(prior_congestion_window);
+		let prior_ssthresh = max(self.ssthresh(), self.prior_ssthresh);
+		self.congestion_control_state_variables.set_ssthresh(prior_ssthresh);
+		
+		self.in_fast_recovery = false;
+		self.in_selective_acknowledgment_loss_recovery = false;
+		
+		self.undo_marker = None;
+		
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.congestion_response_undone(congestion_control_state_variables));
+	}
+	
+	/// RFC 4015 Section 4: The Eifel response to a spurious retransmission time out.
+	///
+	/// `cwnd` is set to `FlightSize` plus the smaller of the data acknowledged and the initial window, so that transmission resumes without a burst, and `ssthresh` is restored to 'pipe_prev'.
+	/// If the acknowledgment had the explicit congestion echo (ECE) flag set, congestion is occurring anyway, and so neither is restored.
+	///
+	/// RFC 4015 also resumes transmission from 'SND.MAX' rather than retransmitting further segments; `SND.NXT` is never moved backwards, so there is nothing to do.
+	#[inline(always)]
+	fn undo_congestion_response_because_retransmission_time_out_was_spurious(&mut self, bytes_acknowledged: u32, explicit_congestion_echo: bool)
+	{
+		self.undo_marker = None;
+		
+		if explicit_congestion_echo
+		{
+			return
+		}
+		
+		let flight_size = self.congestion_control_state_variables.FlightSize();
+		let initial_window = self.initial_window();
+		self.set_congestion_window(flight_size + min(bytes_acknowledged, initial_window));
+		let prior_pipe = self.prior_pipe;
+		self.congestion_control_state_variables.set_ssthresh(prior_pipe);
+		
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.congestion_response_undone(congestion_control_state_variables));
+	}
+	
+	/// RFC 6582 Section 3.2 Step 2: "... checks the value of recover to see if the Cumulative Acknowledgment field covers more than recover".
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	fn cumulative_acknowledgment_covers_more_than_recover(&self, SND_UNA_less_one: WrappingSequenceNumber) -> bool
+	{
+		match self.recover
+		{
+			None => true,
+			
+			Some(recover) => SND_UNA_less_one > recover,
+		}
+	}
+	
+	/// RFC 5681 Section 3.2 Step 4: "For each additional duplicate ACK received (after the third), cwnd MUST be incremented by SMSS.
+	/// This artificially inflates the congestion window in order to reflect the additional segment that has left the network".
+	///
+	/// Instead, `cwnd` is set by proportional rate reduction (RFC 6937), estimating that the duplicate acknowledgment delivered one segment.
+	///
+	/// Only for NewReno fast recovery (RFC 6582).
+	#[inline(always)]
+	pub fn additional_duplicate_acknowledgment_during_fast_recovery(&mut self)
+	{
+		debug_assert!(self.in_fast_recovery, "not in fast recovery");
+		debug_assert!(!self.in_selective_acknowledgment_loss_recovery, "in selective acknowledgment loss recovery");
+		
+		let sender_maximum_segment_size = self.sender_maximum_segment_size();
+		let delivered_data = self.proportional_rate_reduction.delivered_data_for_duplicate_acknowledgment(sender_maximum_segment_size);
+		self.proportional_rate_reduction_without_selective_acknowledgments(delivered_data)
+	}
+	
+	/// RFC 3168 Section 6.1.2 Paragraph 1: "If the sender receives an ECN-Echo (ECE) ACK packet ... The indication of congestion should be treated just as a congestion loss in non-ECN-Capable TCP".
+	///
+	/// RFC 3168 Section 6.1.2 Paragraph 2: "TCP should not react to congestion indications more than once every window of data"; the caller is responsible for this.
+	///
+	/// Whilst in fast recovery the congestion window has already been reduced in response to loss, so the indication is ignored.
+	#[inline(always)]
+	pub fn explicit_congestion_echo_received(&mut self, now: MonotonicMillisecondTimestamp)
+	{
+		if self.in_fast_recovery
+		{
+			return
+		}
+		
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.explicit_congestion_echo(congestion_control_state_variables, now));
+	}
+	
+	/// An acknowledgment which moved `SND.UNA` has been received; `explicit_congestion_echo` is whether it had the explicit congestion echo (ECE) flag set or, for Accurate ECN (RFC 9768), whether CE marks have been fed back.
+	///
+	/// For Accurate ECN, the CE marked bytes fed back by this and any preceding duplicate acknowledgments are passed to the congestion control algorithm.
+	///
+	/// Must be called for every such acknowledgment, before `increase_bytes_acknowledged()`; does nothing if explicit congestion notification is not supported.
+	#[inline(always)]
+	pub fn explicit_congestion_notification_feedback(&mut self, bytes_acknowledged: u32, explicit_congestion_echo: bool, now: MonotonicMillisecondTimestamp)
+	{
+		let congestion_experienced_bytes = match self.explicit_congestion_notification_state
+		{
+			None => return,
+			
+			Some(ref mut explicit_congestion_notification_state) => explicit_congestion_notification_state.accurate_explicit_congestion_notification_mutable_reference().map(|accurate_explicit_congestion_notification_state| accurate_explicit_congestion_notification_state.take_congestion_experienced_bytes()),
+		};
+		
+		match congestion_experienced_bytes
+		{
+			None => self.congestion_control_algorithm.explicit_congestion_notification_feedback(&self.congestion_control_state_variables, bytes_acknowledged, explicit_congestion_echo, now),
+			
+			Some(congestion_experienced_bytes) => self.congestion_control_algorithm.accurate_explicit_congestion_notification_feedback(&self.congestion_control_state_variables, bytes_acknowledged, congestion_experienced_bytes, now),
+		}
+	}
+	
+	/// RFC 6298 Section 2: A round trip time measurement, and the smoothed round trip time (`SRTT`) after it has been processed.
+	#[inline(always)]
+	pub fn round_trip_time_measured(&mut self, measurement_of_round_trip_time: MillisecondDuration, smoothed_round_trip_time: MillisecondDuration, now: MonotonicMillisecondTimestamp)
+	{
+		if let Some(ref mut congestion_window_validation) = self.congestion_window_validation
+		{
+			congestion_window_validation.round_trip_time_measured(smoothed_round_trip_time);
+		}
+		
+		self.congestion_control_algorithm.round_trip_time_sample(&self.congestion_control_state_variables, measurement_of_round_trip_time, smoothed_round_trip_time, now)
 	}
 	
-	/// RFC 5681 Section 3.1 Page 7: "When a TCP sender detects segment loss using the retransmission timer and the given segment has not yet been resent by way of the retransmission timer, the value of ssthresh MUST be set to no more than the value given in equation (4): ssthresh = max (FlightSize / 2, 2*SMSS) where ... FlightSize is the amount of outstanding data in the network".
+	/// A delivery rate sample (draft-cheng-iccrg-delivery-rate-estimation) for an acknowledgment which moved `SND.UNA`.
 	///
-	/// RFC 5681 Section 7 Paragraph 6: "...  ssthresh must be set to half the FlightSize on the first retransmission of a given segment and then is held constant on subsequent retransmissions of the same segment".
+	/// Must be called before `increase_bytes_acknowledged()`.
 	#[inline(always)]
-	pub fn rfc_5681_section_7_paragaph_6_set_ssthresh_to_half_of_flight_size_on_first_retransmission(&mut self)
+	pub fn delivery_rate_sampled(&mut self, delivery_rate_sample: &DeliveryRateSample, now: MonotonicMillisecondTimestamp)
 	{
-		self.ssthresh = max(self.FlightSize / 2, 2 * self.sender_maximum_segment_size)
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.delivery_rate_sample(congestion_control_state_variables, delivery_rate_sample, now));
+	}
+	
+	/// The rate, in bytes per second, at which segments should be paced, if any.
+	///
+	/// If the congestion control algorithm has not set a pacing rate, it is derived from `cwnd / SRTT`; as Linux does, this is doubled in slow start, so that pacing does not slow the growth of `cwnd`, and increased by a fifth in congestion avoidance.
+	///
+	/// There is no pacing rate until a round trip time has been measured.
+	#[inline(always)]
+	pub fn pacing_rate(&self, smoothed_round_trip_time: MillisecondDuration) -> Option<u64>
+	{
+		if let Some(pacing_rate) = self.congestion_control_state_variables.pacing_rate()
+		{
+			return Some(pacing_rate)
+		}
+		
+		let smoothed_round_trip_time: u64 = smoothed_round_trip_time.into();
+		if smoothed_round_trip_time == 0
+		{
+			return None
+		}
+		
+		let percentage = if self.congestion_control_state_variables.is_in_slow_start()
+		{
+			Self::SlowStartPacingPercentage
+		}
+		else
+		{
+			Self::CongestionAvoidancePacingPercentage
+		};
+		
+		let congestion_window = self.congestion_window() as u64;
+		Some(congestion_window * 1000 * percentage / (smoothed_round_trip_time * 100))
 	}
 	
 	/// RFC 5681 Section 2: "At any given time, a TCP MUST NOT send data with a sequence number higher than the sum of the highest acknowledged sequence number and the minimum of cwnd and rwnd".
@@ -229,22 +832,73 @@ impl CongestionControl
 	/// RFC 5681 Section 4.1: "When TCP has not received a segment for more than one retransmission timeout, cwnd is reduced to the value of the restart window (RW) before transmission begins
 	/// ...
 	/// TCP SHOULD set cwnd to no more than RW (the restart window) before beginning transmission if the TCP has not sent data in an interval exceeding the retransmission timeout".
+	///
+	/// If Congestion Window Validation (RFC 7661) is in use, `cwnd` is instead preserved after an idle period, unless the sender has been in the non-validated phase for longer than the non-validated period (NVP), when `ssthresh` is set to at least three quarters of `cwnd` and `cwnd` is halved (but to no less than the initial window).
 	#[inline(always)]
 	pub fn reset_congestion_window_to_restart_window_if_no_data_sent_for_an_interval_exceeding_the_retransmission_time_out(&mut self, now: MonotonicMillisecondTimestamp, retransmission_time_out: MillisecondDuration)
 	{
-		debug_assert!(self.last_sent_data_at >= now, "self.last_sent_data_at '{}' is less than now '{}'", self.last_sent_data_at, now);
+		debug_assert!(now >= self.last_sent_data_at, "now '{}' is less than self.last_sent_data_at '{}'", now, self.last_sent_data_at);
+		
+		let non_validated_period_expired = match self.congestion_window_validation
+		{
+			None => None,
+			Some(ref mut congestion_window_validation) => Some(congestion_window_validation.non_validated_period_expired(now)),
+		};
 		
-		if (self.last_sent_data_at - now) > retransmission_time_out
+		if let Some(non_validated_period_expired) = non_validated_period_expired
+		{
+			if non_validated_period_expired
+			{
+				let congestion_window = self.congestion_window();
+				let ssthresh = max(self.ssthresh(), 3 * congestion_window / 4);
+				self.congestion_control_state_variables.set_ssthresh(ssthresh);
+				
+				let initial_window = self.initial_window();
+				self.set_congestion_window(max(congestion_window / 2, initial_window));
+			}
+			return
+		}
+		
+		if (now - self.last_sent_data_at) > retransmission_time_out
 		{
 			self.reset_congestion_window_to_restart_window();
+			self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.restart_after_idle(congestion_control_state_variables, now));
 		}
 	}
 	
+	/// RFC 5681 Section 3.1 Page 7: "When a TCP sender detects segment loss using the retransmission timer and the given segment has not yet been resent by way of the retransmission timer, the value of ssthresh MUST be set to no more than the value given in equation (4)"; this is delegated to the congestion control algorithm.
+	///
 	/// RFC 5681 Section 3.1 Page 8 Paragraph 2: "Furthermore, upon a timeout cwnd MUST be set to no more than the loss window, LW, which equals 1 full-sized segment (regardless of the value of IW).
 	/// Therefore, after retransmitting the dropped segment the TCP sender uses the slow start algorithm to increase the window from 1 full-sized segment to the new value of ssthresh, at which point congestion avoidance again takes over".
+	///
+	/// RFC 6582 Section 3.2 Step 4: "After a retransmit timeout, record the highest sequence number transmitted in the variable recover, and exit the fast recovery procedure if applicable".
+	///
+	/// RFC 5682 Section 2.1 Step 1: Detection of a spurious retransmission time out starts, unless loss recovery is already in progress (ie the segment has been retransmitted before, or the cumulative acknowledgment does not yet cover more than `recover`), in which case conventional recovery continues.
+	#[allow(non_snake_case)]
 	#[inline(always)]
-	pub fn reset_congestion_window_to_loss_window_because_retransmission_timed_out(&mut self)
+	pub fn reset_congestion_window_to_loss_window_because_retransmission_timed_out(&mut self, SND_UNA_less_one: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, is_first_retransmission: bool, now: MonotonicMillisecondTimestamp)
 	{
+		let loss_recovery_in_progress = !is_first_retransmission || !self.cumulative_acknowledgment_covers_more_than_recover(SND_UNA_less_one);
+		self.spurious_retransmission_time_out_detection = if loss_recovery_in_progress
+		{
+			None
+		}
+		else
+		{
+			Some(SpuriousRetransmissionTimeOutDetection::new(now, SND_NXT))
+		};
+		
+		self.with_congestion_control_algorithm(|congestion_control_algorithm, congestion_control_state_variables| congestion_control_algorithm.retransmission_time_out(congestion_control_state_variables, is_first_retransmission, now));
+		
+		if let Some(ref mut congestion_window_validation) = self.congestion_window_validation
+		{
+			congestion_window_validation.retransmission_time_out();
+		}
+		
+		self.recover = Some(SND_NXT);
+		self.in_fast_recovery = false;
+		self.in_selective_acknowledgment_loss_recovery = false;
+		
 		self.reset_congestion_window_to_loss_window();
 	}
 }
@@ -259,6 +913,50 @@ impl CongestionControl
 		self.set_congestion_window(initial_window)
 	}
 	
+	/// RFC 6937 Section 3.1: "On exiting recovery: cwnd = ssthresh".
+	///
+	/// RFC 7661: If loss recovery started in the non-validated phase, `cwnd` is instead `(max(pipeACK, LossFlightSize) - R) / 2` and `ssthresh` is at least `cwnd`.
+	#[inline(always)]
+	fn set_congestion_window_on_exiting_loss_recovery(&mut self)
+	{
+		let loss_recovery_ended = match self.congestion_window_validation
+		{
+			None => None,
+			Some(ref mut congestion_window_validation) => congestion_window_validation.loss_recovery_ended(),
+		};
+		
+		match loss_recovery_ended
+		{
+			None =>
+			{
+				let ssthresh = self.ssthresh();
+				self.set_congestion_window(ssthresh);
+			}
+			
+			Some(congestion_window) =>
+			{
+				let sender_maximum_segment_size = self.sender_maximum_segment_size();
+				let congestion_window = max(congestion_window, sender_maximum_segment_size);
+				self.set_congestion_window(congestion_window);
+				
+				let ssthresh = max(congestion_window, 2 * sender_maximum_segment_size);
+				self.congestion_control_state_variables.set_ssthresh(ssthresh);
+			}
+		}
+	}
+	
+	/// RFC 7661: Records `FlightSize` when congestion is detected, before the congestion response.
+	#[inline(always)]
+	fn congestion_window_validation_loss_recovery_started(&mut self)
+	{
+		let flight_size = self.congestion_control_state_variables.FlightSize();
+		let congestion_window = self.congestion_window();
+		if let Some(ref mut congestion_window_validation) = self.congestion_window_validation
+		{
+			congestion_window_validation.loss_recovery_started(flight_size, congestion_window);
+		}
+	}
+	
 	#[inline(always)]
 	fn reset_congestion_window_to_restart_window(&mut self)
 	{
@@ -277,7 +975,7 @@ impl CongestionControl
 	#[inline(always)]
 	fn initial_window(&self) -> u32
 	{
-		self.initial_congestion_window_algorithm.compute_initial_window(self.sender_maximum_segment_size)
+		self.initial_congestion_window_algorithm.compute_initial_window(self.sender_maximum_segment_size())
 	}
 	
 	/// RFC 5681 Section 2: "RESTART WINDOW (RW): The restart window is the size of the congestion window (cwnd) after a TCP restarts transmission after an idle period (if the slow start algorithm is used ...)".
@@ -296,32 +994,42 @@ impl CongestionControl
 	#[inline(always)]
 	fn loss_window(&self) -> u32
 	{
-		self.sender_maximum_segment_size as u32
+		self.sender_maximum_segment_size()
 	}
 	
 	#[inline(always)]
 	fn congestion_window(&self) -> u32
 	{
-		self.cwnd
+		self.congestion_control_state_variables.congestion_window()
+	}
+	
+	#[inline(always)]
+	fn set_congestion_window(&mut self, value: u32)
+	{
+		let previous_congestion_window = self.congestion_window();
+		self.congestion_control_state_variables.set_congestion_window(value);
+		self.congestion_window_may_have_been_reduced(previous_congestion_window)
 	}
 	
+	/// Calls a hook of the congestion control algorithm.
 	#[inline(always)]
-	fn increment_congestion_window(&mut self, increment: u32)
+	fn with_congestion_control_algorithm<Hook: FnOnce(&mut CongestionControlAlgorithm, &mut CongestionControlStateVariables)>(&mut self, hook: Hook)
 	{
-		let value = self.congestion_window().saturating_add(increment);
-		self.set_congestion_window(value)
+		let previous_congestion_window = self.congestion_window();
+		hook(&mut *self.congestion_control_algorithm, &mut self.congestion_control_state_variables);
+		self.congestion_window_may_have_been_reduced(previous_congestion_window)
 	}
 	
+	/// RFC 3168 Section 6.1.2 Paragraph 4: "When an ECN-Capable TCP sender reduces its congestion window for any reason (because of a retransmit timeout, a Fast Retransmit, or in response to an ECN Notification), the TCP sender sets the CWR flag in the TCP header of the first new data packet sent after the window reduction".
 	#[inline(always)]
-	fn set_congestion_window(&mut self, value: u32)
+	fn congestion_window_may_have_been_reduced(&mut self, previous_congestion_window: u32)
 	{
-		if value > self.cwnd
+		if self.congestion_window() < previous_congestion_window
 		{
 			if let Some(ref mut explicit_congestion_notification_state) = self.explicit_congestion_notification_state
 			{
 				explicit_congestion_notification_state.reduced_congestion_window();
 			}
 		}
-		self.cwnd = value
 	}
 }
@@ -0,0 +1,95 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// A congestion control algorithm, such as Reno (RFC 5681).
+///
+/// `CongestionControl` implements the parts of congestion control common to all algorithms: the initial, restart and loss windows, counting of duplicate acknowledgments, fast recovery (RFC 6582 and RFC 6675) and undoing the congestion response to spurious retransmissions (RFC 3708).
+/// An algorithm decides how `cwnd` grows when data is acknowledged, and what `ssthresh` (and, for explicit congestion notification, `cwnd`) become in response to congestion.
+///
+/// Hooks are only called outside of fast recovery, unless documented otherwise.
+pub trait CongestionControlAlgorithm: Debug
+{
+	/// Creates a new boxed instance; `Self::boxed` is suitable for use as a `CongestionControlAlgorithmConstructor`.
+	#[inline(always)]
+	fn boxed() -> Box<CongestionControlAlgorithm> where Self: 'static + Sized + Default
+	{
+		Box::new(Self::default())
+	}
+	
+	/// Should the receiver set the explicit congestion echo (ECE) flag on acknowledgments according to the CE codepoint of each data packet received, as for DCTCP (RFC 8257 Section 3.2), rather than latching it until the `CongestionWindowReduced` flag is received (RFC 3168 Section 6.1.3)?
+	///
+	/// Only used if explicit congestion notification is supported for a connection.
+	#[inline(always)]
+	fn explicit_congestion_echo_per_segment(&self) -> bool
+	{
+		false
+	}
+	
+	/// An acknowledgment which moved `SND.UNA` by `bytes_acknowledged` has been received; typically `cwnd` is increased.
+	///
+	/// `FlightSize` has already been decreased by `bytes_acknowledged`.
+	fn acknowledgment(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, now: MonotonicMillisecondTimestamp);
+	
+	/// Loss has been detected by duplicate acknowledgments or selective acknowledgments and fast recovery is being entered; `ssthresh` must be set.
+	///
+	/// `CongestionControl` then reduces `cwnd` towards the new value of `ssthresh` during fast recovery using proportional rate reduction (RFC 6937), and sets `cwnd` to it when fast recovery is exited.
+	fn loss(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, now: MonotonicMillisecondTimestamp);
+	
+	/// An acknowledgment with the explicit congestion echo (ECE) flag set has been received, no more than once per window of data; `ssthresh` and `cwnd` should be reduced.
+	fn explicit_congestion_echo(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, now: MonotonicMillisecondTimestamp);
+	
+	/// The retransmission timer has expired; `ssthresh` should be set if this is the first retransmission of the segment.
+	///
+	/// May be called during fast recovery, which is then exited.
+	///
+	/// `CongestionControl` sets `cwnd` to the loss window after this hook returns.
+	fn retransmission_time_out(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, is_first_retransmission: bool, now: MonotonicMillisecondTimestamp);
+	
+	/// Transmission is restarting after an idle period exceeding the retransmission time out; `CongestionControl` has already reduced `cwnd` to the restart window.
+	#[inline(always)]
+	fn restart_after_idle(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+	}
+	
+	/// A round trip time has been measured (RFC 6298); this may be during fast recovery.
+	///
+	/// `smoothed_round_trip_time` is `SRTT` as maintained for the retransmission timer, and already includes `measurement_of_round_trip_time`.
+	#[inline(always)]
+	fn round_trip_time_sample(&mut self, _congestion_control_state_variables: &CongestionControlStateVariables, _measurement_of_round_trip_time: MillisecondDuration, _smoothed_round_trip_time: MillisecondDuration, _now: MonotonicMillisecondTimestamp)
+	{
+	}
+	
+	/// A delivery rate sample (draft-cheng-iccrg-delivery-rate-estimation) has been generated by an acknowledgment which moved `SND.UNA`; this may be during fast recovery.
+	///
+	/// Called before `acknowledgment()`, and before `FlightSize` has been decreased by the amount of data acknowledged.
+	#[inline(always)]
+	fn delivery_rate_sample(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables, _delivery_rate_sample: &DeliveryRateSample, _now: MonotonicMillisecondTimestamp)
+	{
+	}
+	
+	/// An acknowledgment which moved `SND.UNA` by `bytes_acknowledged` has been received on a connection for which explicit congestion notification is supported; `explicit_congestion_echo` is whether the acknowledgment had the explicit congestion echo (ECE) flag set.
+	///
+	/// Unlike `explicit_congestion_echo()`, this is called for every such acknowledgment, and may be during fast recovery.
+	///
+	/// Called before `acknowledgment()`, and before `FlightSize` has been decreased by the amount of data acknowledged.
+	#[inline(always)]
+	fn explicit_congestion_notification_feedback(&mut self, _congestion_control_state_variables: &CongestionControlStateVariables, _bytes_acknowledged: u32, _explicit_congestion_echo: bool, _now: MonotonicMillisecondTimestamp)
+	{
+	}
+	
+	/// As `explicit_congestion_notification_feedback()`, but for a connection using Accurate ECN (RFC 9768); `congestion_experienced_bytes` is the number of bytes the remote has fed back as having been CE marked since the last call, which may exceed `bytes_acknowledged`.
+	///
+	/// The default treats any CE marked bytes as if the explicit congestion echo (ECE) flag had been set.
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_feedback(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, bytes_acknowledged: u32, congestion_experienced_bytes: u32, now: MonotonicMillisecondTimestamp)
+	{
+		self.explicit_congestion_notification_feedback(congestion_control_state_variables, bytes_acknowledged, congestion_experienced_bytes != 0, now)
+	}
+	
+	/// The congestion response has been undone because the retransmissions which caused it were spurious (RFC 3708); `CongestionControl` has already restored `cwnd` and `ssthresh`.
+	#[inline(always)]
+	fn congestion_response_undone(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+	}
+}
@@ -0,0 +1,8 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Creates the congestion control algorithm for a new connection, eg `RenoCongestionControlAlgorithm::boxed`.
+///
+/// This is chosen per listening server port and per outbound connection.
+pub type CongestionControlAlgorithmConstructor = fn() -> Box<CongestionControlAlgorithm>;
@@ -0,0 +1,143 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// The congestion control state variables of RFC 5681 which are shared between `CongestionControl` and a `CongestionControlAlgorithm`.
+#[derive(Debug)]
+#[allow(non_snake_case)]
+pub struct CongestionControlStateVariables
+{
+	/// RFC 5681 Section 2: "CONGESTION WINDOW (cwnd):  A TCP state variable that limits the amount of data a TCP can send.
+	/// At any given time, a TCP MUST NOT send data with a sequence number higher than the sum of the highest acknowledged sequence number and the minimum of cwnd and rwnd".
+	cwnd: u32,
+	
+	/// RFC 5681 Section 2: "FLIGHT SIZE: The amount of data that has been sent but not yet cumulatively acknowledged".
+	FlightSize: u32,
+	
+	/// RFC 5681: Section 3.1: "... the slow start threshold (ssthresh), is used to determine whether the slow start or congestion avoidance algorithm is used to control data transmission ..."
+	ssthresh: u32,
+	
+	/// RFC 5681 Section 2: "Sender Maximum Segment Size (SMSS)".
+	///
+	/// This changes after the initial SYN has been sent, as the value reflects the use of options.
+	sender_maximum_segment_size: u32,
+	
+	/// The rate, in bytes per second, at which segments should be paced, if any.
+	pacing_rate: Option<u64>,
+}
+
+impl CongestionControlStateVariables
+{
+	#[inline(always)]
+	pub(crate) fn new(cwnd: u32, ssthresh: u32, sender_maximum_segment_size: u32) -> Self
+	{
+		Self
+		{
+			cwnd,
+			FlightSize: 0,
+			ssthresh,
+			sender_maximum_segment_size,
+			pacing_rate: None,
+		}
+	}
+	
+	/// `cwnd`.
+	#[inline(always)]
+	pub fn congestion_window(&self) -> u32
+	{
+		self.cwnd
+	}
+	
+	/// Sets `cwnd`.
+	///
+	/// `cwnd` is never set to less than one byte.
+	#[inline(always)]
+	pub fn set_congestion_window(&mut self, value: u32)
+	{
+		self.cwnd = max(value, 1)
+	}
+	
+	/// Increments `cwnd`, saturating.
+	#[inline(always)]
+	pub fn increment_congestion_window(&mut self, increment: u32)
+	{
+		let value = self.cwnd.saturating_add(increment);
+		self.set_congestion_window(value)
+	}
+	
+	/// `ssthresh`.
+	#[inline(always)]
+	pub fn ssthresh(&self) -> u32
+	{
+		self.ssthresh
+	}
+	
+	/// Sets `ssthresh`.
+	#[inline(always)]
+	pub fn set_ssthresh(&mut self, value: u32)
+	{
+		self.ssthresh = value
+	}
+	
+	/// RFC 5681 Section 3.1: "The slow start algorithm is used when cwnd < ssthresh, while the congestion avoidance algorithm is used when cwnd > ssthresh.
+	/// When cwnd and ssthresh are equal, the sender may use either slow start or congestion avoidance".
+	#[inline(always)]
+	pub fn is_in_slow_start(&self) -> bool
+	{
+		self.cwnd <= self.ssthresh
+	}
+	
+	/// `FlightSize`.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub fn FlightSize(&self) -> u32
+	{
+		self.FlightSize
+	}
+	
+	/// `SMSS`.
+	#[inline(always)]
+	pub fn sender_maximum_segment_size(&self) -> u32
+	{
+		self.sender_maximum_segment_size
+	}
+	
+	/// The rate, in bytes per second, at which segments should be paced, if any.
+	#[inline(always)]
+	pub fn pacing_rate(&self) -> Option<u64>
+	{
+		self.pacing_rate
+	}
+	
+	/// Sets the rate, in bytes per second, at which segments should be paced; `None` disables pacing.
+	#[inline(always)]
+	pub fn set_pacing_rate(&mut self, pacing_rate: Option<u64>)
+	{
+		self.pacing_rate = pacing_rate
+	}
+	
+	/// RFC 5681 Section 3.1 equation (4): "ssthresh = max (FlightSize / 2, 2*SMSS)".
+	#[inline(always)]
+	pub fn half_of_flight_size_but_at_least_two_segments(&self) -> u32
+	{
+		max(self.FlightSize / 2, 2 * self.sender_maximum_segment_size)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn set_sender_maximum_segment_size(&mut self, sender_maximum_segment_size: u32)
+	{
+		self.sender_maximum_segment_size = sender_maximum_segment_size
+	}
+	
+	#[inline(always)]
+	pub(crate) fn increase_flight_size(&mut self, increase_flight_size_by_amount_of_bytes: u32)
+	{
+		self.FlightSize += increase_flight_size_by_amount_of_bytes
+	}
+	
+	#[inline(always)]
+	pub(crate) fn decrease_flight_size(&mut self, decrease_flight_size_by_amount_of_bytes: u32)
+	{
+		self.FlightSize -= decrease_flight_size_by_amount_of_bytes
+	}
+}
@@ -13,11 +13,12 @@ pub trait CongestionControlTransmissionControlBlock
 		self.congestion_control_mutable_reference().increment_duplicate_acknowledgments_received_without_any_intervening_acknwoledgments_which_moved_SND_UNA()
 	}
 	
+	#[allow(non_snake_case)]
 	#[doc(hidden)]
 	#[inline(always)]
-	fn reset_congestion_window_to_loss_window_because_retransmission_timed_out(&mut self)
+	fn reset_congestion_window_to_loss_window_because_retransmission_timed_out(&mut self, SND_UNA_less_one: WrappingSequenceNumber, SND_NXT: WrappingSequenceNumber, is_first_retransmission: bool, now: MonotonicMillisecondTimestamp)
 	{
-		self.congestion_control_mutable_reference().reset_congestion_window_to_loss_window_because_retransmission_timed_out()
+		self.congestion_control_mutable_reference().reset_congestion_window_to_loss_window_because_retransmission_timed_out(SND_UNA_less_one, SND_NXT, is_first_retransmission, now)
 	}
 	
 	#[doc(hidden)]
@@ -27,13 +28,6 @@ pub trait CongestionControlTransmissionControlBlock
 		self.congestion_control_mutable_reference().bytes_sent_in_payload_in_a_segment_which_is_not_a_zero_window_probe_or_retransmission(increase_flight_size_by_amount_of_bytes)
 	}
 	
-	#[doc(hidden)]
-	#[inline(always)]
-	fn rfc_5681_section_7_paragaph_6_set_ssthresh_to_half_of_flight_size_on_first_retransmission(&mut self)
-	{
-		self.congestion_control_mutable_reference().rfc_5681_section_7_paragaph_6_set_ssthresh_to_half_of_flight_size_on_first_retransmission();
-	}
-	
 	#[doc(hidden)]
 	#[inline(always)]
 	fn congestion_control_reference(&self) -> &CongestionControl;
@@ -0,0 +1,189 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Congestion Window Validation for rate-limited senders (new-CWV), as specified by RFC 7661.
+///
+/// The volume of data acknowledged in a round trip time ('pipeACK') is measured; if it is less than half of `cwnd`, then `cwnd` has not been validated by the sender's recent use of it (the 'non-validated phase').
+///
+/// In the non-validated phase, `cwnd` is not increased, but is preserved, rather than being reset to the restart window after idle, for up to the non-validated period (NVP); if congestion is detected, the loss response is based on the data actually in flight rather than on the unvalidated `cwnd`.
+#[derive(Default, Debug)]
+pub(crate) struct CongestionWindowValidation
+{
+	/// RFC 7661 "pipeACK": The maximum of the pipeACK samples taken during the pipeACK Sampling Period (PSP).
+	///
+	/// Times are in milliseconds since `epoch`.
+	pipe_acknowledged: WindowedMaximumFilter,
+	
+	/// RFC 7661: "pipeACK" is undefined until the first sample has been taken, and after congestion has been detected in the non-validated phase.
+	pipe_acknowledged_is_defined: bool,
+	
+	epoch: Option<MonotonicMillisecondTimestamp>,
+	
+	/// When the current pipeACK sample started being measured.
+	sample_started_at: Option<MonotonicMillisecondTimestamp>,
+	
+	bytes_acknowledged_in_sample: u32,
+	
+	/// `SRTT` in milliseconds; zero until a round trip time has been measured.
+	smoothed_round_trip_time: u64,
+	
+	/// `Some` whilst in the non-validated phase; when the non-validated period (NVP) started.
+	non_validated_since: Option<MonotonicMillisecondTimestamp>,
+	
+	/// RFC 7661 "LossFlightSize": `FlightSize` when congestion was detected in the non-validated phase.
+	loss_flight_size: Option<u32>,
+	
+	/// RFC 7661 "R": The volume of data retransmitted during loss recovery which started in the non-validated phase.
+	retransmitted_during_loss_recovery: u32,
+}
+
+impl CongestionWindowValidation
+{
+	/// RFC 7661: The non-validated period (NVP) is six minutes.
+	const NonValidatedPeriod: u64 = 360_000;
+	
+	/// RFC 7661: The pipeACK Sampling Period (PSP) is the larger of three times `SRTT` and one second.
+	const MinimumPipeAcknowledgedSamplingPeriod: u64 = 1_000;
+	
+	/// RFC 7661: A round trip time has been measured; pipeACK samples are taken once per round trip.
+	#[inline(always)]
+	pub(crate) fn round_trip_time_measured(&mut self, smoothed_round_trip_time: MillisecondDuration)
+	{
+		self.smoothed_round_trip_time = smoothed_round_trip_time.into();
+	}
+	
+	/// RFC 7661: Data has been acknowledged; at most once per round trip, the data acknowledged during it becomes a new pipeACK sample.
+	///
+	/// `congestion_window` is `cwnd` before any increase for this acknowledgment.
+	#[inline(always)]
+	pub(crate) fn acknowledged(&mut self, bytes_acknowledged: u32, congestion_window: u32, now: MonotonicMillisecondTimestamp)
+	{
+		let sample_started_at = match self.sample_started_at
+		{
+			None =>
+			{
+				self.sample_started_at = Some(now);
+				now
+			}
+			
+			Some(sample_started_at) => sample_started_at,
+		};
+		
+		self.bytes_acknowledged_in_sample = self.bytes_acknowledged_in_sample.saturating_add(bytes_acknowledged);
+		
+		let sample_duration: u64 = (now - sample_started_at).into();
+		if self.smoothed_round_trip_time != 0 && sample_duration >= self.smoothed_round_trip_time
+		{
+			let epoch = *self.epoch.get_or_insert(now);
+			let time: u64 = (now - epoch).into();
+			let sample = self.bytes_acknowledged_in_sample as u64;
+			
+			if self.pipe_acknowledged_is_defined
+			{
+				let pipe_acknowledged_sampling_period = max(3 * self.smoothed_round_trip_time, Self::MinimumPipeAcknowledgedSamplingPeriod);
+				self.pipe_acknowledged.update(pipe_acknowledged_sampling_period, time, sample);
+			}
+			else
+			{
+				self.pipe_acknowledged.reset(time, sample);
+				self.pipe_acknowledged_is_defined = true;
+			}
+			
+			self.sample_started_at = Some(now);
+			self.bytes_acknowledged_in_sample = 0;
+		}
+		
+		if self.is_validated(congestion_window)
+		{
+			self.non_validated_since = None
+		}
+		else if self.non_validated_since.is_none()
+		{
+			self.non_validated_since = Some(now)
+		}
+	}
+	
+	/// RFC 7661: In the validated phase, pipeACK is undefined or at least half of `cwnd`.
+	#[inline(always)]
+	pub(crate) fn is_validated(&self, congestion_window: u32) -> bool
+	{
+		!self.pipe_acknowledged_is_defined || self.pipe_acknowledged.maximum() >= (congestion_window as u64) / 2
+	}
+	
+	/// RFC 7661: Returns `true` if the sender has been in the non-validated phase for longer than the non-validated period; the caller should then reduce `cwnd`.
+	///
+	/// The non-validated period then starts again.
+	#[inline(always)]
+	pub(crate) fn non_validated_period_expired(&mut self, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		match self.non_validated_since
+		{
+			Some(non_validated_since) if Into::<u64>::into(now - non_validated_since) > Self::NonValidatedPeriod =>
+			{
+				self.non_validated_since = Some(now);
+				true
+			}
+			
+			_ => false,
+		}
+	}
+	
+	/// RFC 7661: Congestion has been detected and loss recovery is starting; `flight_size` and `congestion_window` are `FlightSize` and `cwnd` before the congestion response.
+	#[inline(always)]
+	pub(crate) fn loss_recovery_started(&mut self, flight_size: u32, congestion_window: u32)
+	{
+		self.loss_flight_size = if self.is_validated(congestion_window)
+		{
+			None
+		}
+		else
+		{
+			Some(flight_size)
+		};
+		self.retransmitted_during_loss_recovery = 0;
+	}
+	
+	#[inline(always)]
+	pub(crate) fn retransmitted_during_loss_recovery(&mut self, bytes: u32)
+	{
+		if self.loss_flight_size.is_some()
+		{
+			self.retransmitted_during_loss_recovery = self.retransmitted_during_loss_recovery.saturating_add(bytes)
+		}
+	}
+	
+	/// RFC 7661: Loss recovery has ended; if it started in the non-validated phase, returns the value `cwnd` should now have, `(max(pipeACK, LossFlightSize) - R) / 2`.
+	///
+	/// The non-validated phase is then exited, and pipeACK becomes undefined.
+	#[inline(always)]
+	pub(crate) fn loss_recovery_ended(&mut self) -> Option<u32>
+	{
+		let loss_flight_size = self.loss_flight_size.take()? as u64;
+		
+		let pipe_acknowledged = if self.pipe_acknowledged_is_defined
+		{
+			self.pipe_acknowledged.maximum()
+		}
+		else
+		{
+			0
+		};
+		
+		let congestion_window = max(pipe_acknowledged, loss_flight_size).saturating_sub(self.retransmitted_during_loss_recovery as u64) / 2;
+		
+		self.pipe_acknowledged_is_defined = false;
+		self.non_validated_since = None;
+		self.retransmitted_during_loss_recovery = 0;
+		
+		Some(min(congestion_window, ::std::u32::MAX as u64) as u32)
+	}
+	
+	/// A retransmission time out uses the standard response, even in the non-validated phase.
+	#[inline(always)]
+	pub(crate) fn retransmission_time_out(&mut self)
+	{
+		self.loss_flight_size = None;
+		self.retransmitted_during_loss_recovery = 0;
+	}
+}
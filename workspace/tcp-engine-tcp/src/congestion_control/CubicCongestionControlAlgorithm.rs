@@ -0,0 +1,189 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// CUBIC, as specified by RFC 9438, with fast convergence (RFC 9438 Section 4.7) and the Reno-friendly region (RFC 9438 Section 4.3).
+///
+/// Slow start uses Appropriate Byte Counting (RFC 3465) as for Reno, and is exited early using HyStart++ (RFC 9406).
+#[derive(Default, Debug)]
+pub struct CubicCongestionControlAlgorithm
+{
+	appropriate_byte_counting: AppropriateByteCounting,
+	
+	hybrid_slow_start: HybridSlowStart,
+	
+	cubic_epoch: CubicEpoch,
+	
+	/// RFC 9438 Section 4.9.2: The state of window growth saved before the most recent congestion event which could be undone.
+	prior_cubic_epoch: CubicEpoch,
+	
+	/// `SRTT` in seconds; zero until a round trip time has been measured.
+	smoothed_round_trip_time: f64,
+}
+
+impl CongestionControlAlgorithm for CubicCongestionControlAlgorithm
+{
+	#[inline(always)]
+	fn acknowledgment(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, now: MonotonicMillisecondTimestamp)
+	{
+		// RFC 5681 Section 3.1 permits either slow start or congestion avoidance when cwnd and ssthresh are equal; congestion avoidance is used so that setting ssthresh to cwnd exits slow start.
+		if congestion_control_state_variables.congestion_window() < congestion_control_state_variables.ssthresh()
+		{
+			self.slow_start(congestion_control_state_variables, bytes_acknowledged)
+		}
+		else
+		{
+			self.congestion_avoidance(congestion_control_state_variables, bytes_acknowledged, now)
+		}
+	}
+	
+	/// RFC 9438 Section 4.6: "ssthresh = flight_size × β_cubic ... ssthresh = max(ssthresh, 2)".
+	#[inline(always)]
+	fn loss(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		self.prior_cubic_epoch = self.cubic_epoch;
+		
+		let ssthresh = self.cubic_epoch.congestion_event(Self::congestion_window(congestion_control_state_variables), Self::flight_size(congestion_control_state_variables));
+		Self::set_ssthresh(congestion_control_state_variables, ssthresh);
+		
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// RFC 9438 Section 4.6: "ssthresh = flight_size × β_cubic ... cwnd = max(cwnd × β_cubic, 1) ... ssthresh = max(ssthresh, 2)" for a reduction caused by an ECN-Echo.
+	#[inline(always)]
+	fn explicit_congestion_echo(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		let congestion_window = Self::congestion_window(congestion_control_state_variables);
+		
+		let ssthresh = self.cubic_epoch.congestion_event(congestion_window, Self::flight_size(congestion_control_state_variables));
+		Self::set_ssthresh(congestion_control_state_variables, ssthresh);
+		
+		let sender_maximum_segment_size = congestion_control_state_variables.sender_maximum_segment_size();
+		let congestion_window = max(Self::bytes(congestion_window * CubicEpoch::beta_cubic, sender_maximum_segment_size), sender_maximum_segment_size);
+		congestion_control_state_variables.set_congestion_window(congestion_window);
+		
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// RFC 9438 Section 4.8: "In the case of a timeout, CUBIC follows Reno to reduce cwnd, but sets ssthresh using β_cubic (same as in Section 4.6)".
+	#[inline(always)]
+	fn retransmission_time_out(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, is_first_retransmission: bool, _now: MonotonicMillisecondTimestamp)
+	{
+		if is_first_retransmission
+		{
+			self.prior_cubic_epoch = self.cubic_epoch;
+			
+			let ssthresh = self.cubic_epoch.retransmission_time_out(Self::congestion_window(congestion_control_state_variables), Self::flight_size(congestion_control_state_variables));
+			Self::set_ssthresh(congestion_control_state_variables, ssthresh);
+		}
+		
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// `cwnd` has been reduced to the restart window, so slow start is used again and a new congestion avoidance stage will start after it.
+	#[inline(always)]
+	fn restart_after_idle(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		self.cubic_epoch.end();
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.reset();
+	}
+	
+	/// RFC 9438 Section 4.2: "RTT is the smoothed round-trip time".
+	///
+	/// Measurements are also used to detect a delay increase during slow start.
+	#[inline(always)]
+	fn round_trip_time_sample(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, measurement_of_round_trip_time: MillisecondDuration, smoothed_round_trip_time: MillisecondDuration, _now: MonotonicMillisecondTimestamp)
+	{
+		let smoothed_round_trip_time: u64 = smoothed_round_trip_time.into();
+		self.smoothed_round_trip_time = (smoothed_round_trip_time as f64) / 1000.0;
+		
+		if congestion_control_state_variables.congestion_window() < congestion_control_state_variables.ssthresh()
+		{
+			self.hybrid_slow_start.round_trip_time_sample(measurement_of_round_trip_time)
+		}
+	}
+	
+	/// RFC 9438 Section 4.9.2: A spurious congestion event restores the state of window growth saved before it.
+	#[inline(always)]
+	fn congestion_response_undone(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		self.cubic_epoch = self.prior_cubic_epoch;
+		self.appropriate_byte_counting.reset();
+	}
+}
+
+impl CubicCongestionControlAlgorithm
+{
+	/// RFC 9438 Section 4.10: CUBIC uses slow start when cwnd is below ssthresh.
+	#[inline(always)]
+	fn slow_start(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32)
+	{
+		// RFC 9406 Section 4.2: "If CSS_ROUNDS rounds are complete, enter congestion avoidance by setting the ssthresh to the current cwnd".
+		if self.hybrid_slow_start.slow_start(&mut self.appropriate_byte_counting, congestion_control_state_variables, bytes_acknowledged)
+		{
+			let congestion_window = congestion_control_state_variables.congestion_window();
+			congestion_control_state_variables.set_ssthresh(congestion_window);
+			
+			self.cubic_epoch.slow_start_exited(Self::congestion_window(congestion_control_state_variables));
+			self.appropriate_byte_counting.reset();
+		}
+	}
+	
+	/// RFC 9438 Sections 4.2 to 4.5: Window growth in the congestion avoidance stage.
+	#[inline(always)]
+	fn congestion_avoidance(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, now: MonotonicMillisecondTimestamp)
+	{
+		let sender_maximum_segment_size = congestion_control_state_variables.sender_maximum_segment_size();
+		
+		let segments_acknowledged = Self::segments(bytes_acknowledged, sender_maximum_segment_size);
+		let congestion_window = self.cubic_epoch.congestion_avoidance(Self::congestion_window(congestion_control_state_variables), segments_acknowledged, self.smoothed_round_trip_time, now);
+		
+		let congestion_window = max(Self::bytes(congestion_window, sender_maximum_segment_size), congestion_control_state_variables.congestion_window());
+		congestion_control_state_variables.set_congestion_window(congestion_window)
+	}
+	
+	/// RFC 9438 Section 4.6: "ssthresh = max(ssthresh, 2)".
+	#[inline(always)]
+	fn set_ssthresh(congestion_control_state_variables: &mut CongestionControlStateVariables, ssthresh: f64)
+	{
+		let sender_maximum_segment_size = congestion_control_state_variables.sender_maximum_segment_size();
+		let ssthresh = max(Self::bytes(ssthresh, sender_maximum_segment_size), 2 * sender_maximum_segment_size);
+		congestion_control_state_variables.set_ssthresh(ssthresh)
+	}
+	
+	#[inline(always)]
+	fn congestion_window(congestion_control_state_variables: &CongestionControlStateVariables) -> f64
+	{
+		Self::segments(congestion_control_state_variables.congestion_window(), congestion_control_state_variables.sender_maximum_segment_size())
+	}
+	
+	#[inline(always)]
+	fn flight_size(congestion_control_state_variables: &CongestionControlStateVariables) -> f64
+	{
+		Self::segments(congestion_control_state_variables.FlightSize(), congestion_control_state_variables.sender_maximum_segment_size())
+	}
+	
+	#[inline(always)]
+	fn segments(bytes: u32, sender_maximum_segment_size: u32) -> f64
+	{
+		(bytes as f64) / (sender_maximum_segment_size as f64)
+	}
+	
+	#[inline(always)]
+	fn bytes(segments: f64, sender_maximum_segment_size: u32) -> u32
+	{
+		let bytes = segments * (sender_maximum_segment_size as f64);
+		if bytes >= (::std::u32::MAX as f64)
+		{
+			::std::u32::MAX
+		}
+		else
+		{
+			bytes as u32
+		}
+	}
+}
@@ -0,0 +1,189 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// The state of CUBIC window growth (RFC 9438 Section 4.1.2).
+///
+/// Windows are in segments, and times in seconds, as in RFC 9438.
+///
+/// RFC 9438 Section 4.9.2: This is saved at each congestion event so that it can be restored if the congestion event is found to be spurious.
+#[derive(Default, Debug, Copy, Clone)]
+#[allow(non_snake_case)]
+pub(crate) struct CubicEpoch
+{
+	/// RFC 9438 Section 4.1.2: "The size of cwnd in segments just before cwnd was reduced in the last congestion event when fast convergence is disabled (same as cwnd_prior on a congestion event)".
+	W_max: f64,
+	
+	/// RFC 9438 Section 4.1.2: "The size of cwnd in segments at the beginning of the current congestion avoidance stage".
+	cwnd_epoch: f64,
+	
+	/// RFC 9438 Section 4.1.2: "Size of cwnd in segments at the time of setting ssthresh most recently, either upon exiting the first slow start or just before cwnd was reduced in the last congestion event".
+	cwnd_prior: f64,
+	
+	/// RFC 9438 Section 4.1.2: "An estimate for the congestion window in segments in the Reno-friendly region -- that is, an estimate for the congestion window of Reno".
+	W_est: f64,
+	
+	/// RFC 9438 Section 4.2: "K is the time period that the above function takes to increase the congestion window size at the beginning of the current congestion avoidance stage to W_max".
+	K: f64,
+	
+	/// RFC 9438 Section 4.1.2: "The time in seconds at which the current congestion avoidance stage started".
+	///
+	/// `None` if a congestion avoidance stage has not started since the last congestion event.
+	epoch_start: Option<MonotonicMillisecondTimestamp>,
+	
+	/// RFC 9438 Section 4.8: "During the first congestion avoidance stage after a timeout, CUBIC increases its congestion window size using Figure 1, where t is the elapsed time since the beginning of the current congestion avoidance stage, K is set to 0, and W_max is set to the congestion window size at the beginning of the current congestion avoidance stage".
+	retransmission_timed_out: bool,
+}
+
+impl CubicEpoch
+{
+	/// RFC 9438 Section 5: "C SHOULD be set to 0.4".
+	const C: f64 = 0.4;
+	
+	/// RFC 9438 Section 4.6: "β_cubic SHOULD be set to 0.7".
+	pub(crate) const beta_cubic: f64 = 0.7;
+	
+	/// RFC 9438 Section 4.3: "α_cubic = 3 × (1 - β_cubic) / (1 + β_cubic)".
+	const alpha_cubic: f64 = 3.0 * (1.0 - Self::beta_cubic) / (1.0 + Self::beta_cubic);
+	
+	/// An acknowledgment during congestion avoidance; returns the new `cwnd` in segments.
+	#[inline(always)]
+	#[allow(non_snake_case)]
+	pub(crate) fn congestion_avoidance(&mut self, cwnd: f64, segments_acknowledged: f64, smoothed_round_trip_time: f64, now: MonotonicMillisecondTimestamp) -> f64
+	{
+		let epoch_start = match self.epoch_start
+		{
+			Some(epoch_start) => epoch_start,
+			None =>
+			{
+				self.start(cwnd, now);
+				now
+			}
+		};
+		
+		let t = Self::seconds_since(epoch_start, now);
+		
+		// RFC 9438 Section 4.3: "W_est += α_cubic × (segments_acked / cwnd)".
+		//
+		// RFC 9438 Section 4.3: "Once W_est has grown to reach the cwnd at the time of most recently setting ssthresh -- that is, W_est >= cwnd_prior -- the sender SHOULD set α_cubic to 1 to ensure that it can achieve the same congestion window increment rate as Reno".
+		let alpha = if self.W_est >= self.cwnd_prior
+		{
+			1.0
+		}
+		else
+		{
+			Self::alpha_cubic
+		};
+		self.W_est += alpha * (segments_acknowledged / cwnd);
+		
+		// RFC 9438 Section 4.3: "When receiving a new ACK in congestion avoidance (where cwnd could be greater than or less than W_max), CUBIC checks whether W_cubic(t) is less than W_est.
+		// If so, CUBIC is in the Reno-friendly region and cwnd SHOULD be set to W_est at each reception of a new ACK".
+		let W_cubic_t = self.W_cubic(t);
+		let new_cwnd = if W_cubic_t < self.W_est
+		{
+			self.W_est
+		}
+		else
+		{
+			// RFC 9438 Section 4.2: The target window is `W_cubic(t + RTT)`, where `RTT` is the smoothed round-trip time, clamped so that `cwnd <= target <= 1.5 × cwnd`.
+			let target = self.W_cubic(t + smoothed_round_trip_time).max(cwnd).min(1.5 * cwnd);
+			
+			// RFC 9438 Sections 4.4 and 4.5: "cwnd MUST be incremented by (target - cwnd) / cwnd for each received new ACK".
+			cwnd + ((target - cwnd) / cwnd) * segments_acknowledged
+		};
+		
+		new_cwnd.max(cwnd)
+	}
+	
+	/// RFC 9438 Section 4.6: "When a congestion event is detected by the mechanisms described in Section 3.1 ... CUBIC updates W_max and reduces cwnd and ssthresh immediately".
+	///
+	/// Returns the new `ssthresh`, in segments, before it is clamped to at least two segments.
+	#[inline(always)]
+	#[allow(non_snake_case)]
+	pub(crate) fn congestion_event(&mut self, cwnd: f64, flight_size: f64) -> f64
+	{
+		// RFC 9438 Section 4.7: "With fast convergence, when a congestion event occurs, W_max is updated as follows, before the window reduction described in Section 4.6.
+		// if cwnd < W_max and fast convergence enabled, further reduce W_max: W_max = cwnd × (1 + β_cubic) / 2, otherwise, remember cwnd before reduction: W_max = cwnd".
+		self.W_max = if cwnd < self.W_max
+		{
+			cwnd * (1.0 + Self::beta_cubic) / 2.0
+		}
+		else
+		{
+			cwnd
+		};
+		
+		self.cwnd_prior = cwnd;
+		self.epoch_start = None;
+		
+		// RFC 9438 Section 4.6: "ssthresh = flight_size × β_cubic".
+		flight_size * Self::beta_cubic
+	}
+	
+	/// RFC 9438 Section 4.8: "In the case of a timeout, CUBIC follows Reno to reduce cwnd, but sets ssthresh using β_cubic (same as in Section 4.6)".
+	///
+	/// Returns the new `ssthresh`, in segments, before it is clamped to at least two segments.
+	#[inline(always)]
+	pub(crate) fn retransmission_time_out(&mut self, cwnd: f64, flight_size: f64) -> f64
+	{
+		self.cwnd_prior = cwnd;
+		self.epoch_start = None;
+		self.retransmission_timed_out = true;
+		
+		flight_size * Self::beta_cubic
+	}
+	
+	/// RFC 9438 Section 4.1.2: "cwnd_prior ... upon exiting the first slow start".
+	#[inline(always)]
+	pub(crate) fn slow_start_exited(&mut self, cwnd: f64)
+	{
+		self.cwnd_prior = cwnd;
+	}
+	
+	/// A new congestion avoidance stage will start when congestion avoidance is next used, eg after an idle period.
+	#[inline(always)]
+	pub(crate) fn end(&mut self)
+	{
+		self.epoch_start = None;
+	}
+	
+	/// RFC 9438 Section 4.2: "K = cubic_root((W_max - cwnd_epoch) / C) ... where cwnd_epoch is the cwnd at the beginning of the current congestion avoidance stage".
+	#[inline(always)]
+	fn start(&mut self, cwnd: f64, now: MonotonicMillisecondTimestamp)
+	{
+		self.epoch_start = Some(now);
+		self.cwnd_epoch = cwnd;
+		
+		// RFC 9438 Section 4.3: "... W_est is set equal to cwnd_epoch at the start of the congestion avoidance stage".
+		self.W_est = cwnd;
+		
+		// If cwnd has already grown beyond W_max, for example because slow start was exited without loss, growth is from the convex region with cwnd as the plateau.
+		if self.retransmission_timed_out || self.W_max <= self.cwnd_epoch
+		{
+			self.retransmission_timed_out = false;
+			self.W_max = self.cwnd_epoch;
+			self.K = 0.0;
+		}
+		else
+		{
+			self.K = ((self.W_max - self.cwnd_epoch) / Self::C).cbrt();
+		}
+	}
+	
+	/// RFC 9438 Section 4.2 Figure 1: "W_cubic(t) = C × (t - K)^3 + W_max".
+	#[inline(always)]
+	#[allow(non_snake_case)]
+	fn W_cubic(&self, t: f64) -> f64
+	{
+		Self::C * (t - self.K).powi(3) + self.W_max
+	}
+	
+	#[inline(always)]
+	fn seconds_since(earlier: MonotonicMillisecondTimestamp, now: MonotonicMillisecondTimestamp) -> f64
+	{
+		debug_assert!(now >= earlier, "time has run backwards");
+		
+		let milliseconds: u64 = (now - earlier).into();
+		(milliseconds as f64) / 1000.0
+	}
+}
@@ -0,0 +1,203 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Data Center TCP (DCTCP), as specified by RFC 8257.
+///
+/// The sender estimates the fraction of data which has encountered congestion from explicit congestion echo (ECE) flags set on acknowledgments (`alpha`), and reduces `cwnd` in proportion to it rather than halving it.
+/// Growth of `cwnd`, including slow start using HyStart++ (RFC 9406), and the response to loss are those of Reno.
+///
+/// DCTCP is designed for data centre networks in which all switches mark rather than drop (RFC 8257 Section 1); it should only be used for listening server ports (`Interface::set_listening_server_congestion_control_algorithm()`) and outbound connections whose traffic stays within a data centre.
+///
+/// If explicit congestion notification is not negotiated for a connection, this behaves exactly as Reno.
+#[derive(Debug)]
+pub struct DctcpCongestionControlAlgorithm
+{
+	reno: RenoCongestionControlAlgorithm,
+	
+	/// "DCTCP.Alpha": The estimate of the fraction of data which has encountered congestion.
+	alpha: f64,
+	
+	/// "DCTCP.BytesAcked": Bytes acknowledged during the current observation window.
+	bytes_acknowledged: u64,
+	
+	/// "DCTCP.BytesMarked": Bytes acknowledged by acknowledgments with the ECE flag set during the current observation window.
+	bytes_marked: u64,
+	
+	/// The total of all bytes acknowledged.
+	total_bytes_acknowledged: u64,
+	
+	/// "DCTCP.WindowEnd", but as a value of `total_bytes_acknowledged` rather than of `SND.NXT`.
+	///
+	/// `None` until the first acknowledgment is received.
+	window_end: Option<u64>,
+}
+
+impl Default for DctcpCongestionControlAlgorithm
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			reno: RenoCongestionControlAlgorithm::default(),
+			alpha: Self::InitialAlpha,
+			bytes_acknowledged: 0,
+			bytes_marked: 0,
+			total_bytes_acknowledged: 0,
+			window_end: None,
+		}
+	}
+}
+
+impl CongestionControlAlgorithm for DctcpCongestionControlAlgorithm
+{
+	/// RFC 8257 Section 3.2: A DCTCP receiver echoes the CE codepoint of each data packet received.
+	#[inline(always)]
+	fn explicit_congestion_echo_per_segment(&self) -> bool
+	{
+		true
+	}
+	
+	#[inline(always)]
+	fn acknowledgment(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, now: MonotonicMillisecondTimestamp)
+	{
+		self.reno.acknowledgment(congestion_control_state_variables, bytes_acknowledged, now)
+	}
+	
+	/// RFC 8257 Section 3.5: A DCTCP sender reacts to loss in the same way as conventional TCP.
+	#[inline(always)]
+	fn loss(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, now: MonotonicMillisecondTimestamp)
+	{
+		self.reno.loss(congestion_control_state_variables, now)
+	}
+	
+	/// RFC 8257 Section 3.3 Step 9: "Rather than always halving the congestion window as described in [RFC3168], the sender SHOULD update cwnd as follows: cwnd = cwnd * (1 - DCTCP.Alpha / 2)".
+	///
+	/// As for Reno, `cwnd` is never reduced below two segments, and `ssthresh` is set to the reduced `cwnd` so that congestion avoidance follows.
+	#[inline(always)]
+	fn explicit_congestion_echo(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		let congestion_window = congestion_control_state_variables.congestion_window();
+		let reduced_congestion_window = ((congestion_window as f64) * (1.0 - self.alpha / 2.0)) as u32;
+		
+		let ssthresh = max(reduced_congestion_window, 2 * congestion_control_state_variables.sender_maximum_segment_size());
+		congestion_control_state_variables.set_ssthresh(ssthresh);
+		congestion_control_state_variables.set_congestion_window(ssthresh);
+		
+		self.reno.appropriate_byte_counting.reset();
+		self.reno.hybrid_slow_start.slow_start_ended();
+	}
+	
+	#[inline(always)]
+	fn retransmission_time_out(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, is_first_retransmission: bool, now: MonotonicMillisecondTimestamp)
+	{
+		self.reno.retransmission_time_out(congestion_control_state_variables, is_first_retransmission, now)
+	}
+	
+	#[inline(always)]
+	fn restart_after_idle(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, now: MonotonicMillisecondTimestamp)
+	{
+		self.reno.restart_after_idle(congestion_control_state_variables, now)
+	}
+	
+	/// Slow start uses HyStart++ (RFC 9406), as for Reno.
+	#[inline(always)]
+	fn round_trip_time_sample(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, measurement_of_round_trip_time: MillisecondDuration, smoothed_round_trip_time: MillisecondDuration, now: MonotonicMillisecondTimestamp)
+	{
+		self.reno.round_trip_time_sample(congestion_control_state_variables, measurement_of_round_trip_time, smoothed_round_trip_time, now)
+	}
+	
+	/// RFC 8257 Section 3.3 Steps 1 to 8.
+	#[inline(always)]
+	fn explicit_congestion_notification_feedback(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, bytes_acknowledged: u32, explicit_congestion_echo: bool, _now: MonotonicMillisecondTimestamp)
+	{
+		// Step 3: "If the ECE flag is set, update the bytes marked: DCTCP.BytesMarked += BytesAcked".
+		let bytes_marked = if explicit_congestion_echo
+		{
+			bytes_acknowledged
+		}
+		else
+		{
+			0
+		};
+		
+		self.observe(congestion_control_state_variables, bytes_acknowledged, bytes_marked)
+	}
+	
+	/// RFC 8257 Section 3.3 Steps 1 to 8, but with Step 3 using the exact number of CE marked bytes fed back by Accurate ECN (RFC 9768) rather than all the bytes acknowledged by an acknowledgment with the ECE flag set.
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_feedback(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, bytes_acknowledged: u32, congestion_experienced_bytes: u32, _now: MonotonicMillisecondTimestamp)
+	{
+		self.observe(congestion_control_state_variables, bytes_acknowledged, congestion_experienced_bytes)
+	}
+	
+	#[inline(always)]
+	fn congestion_response_undone(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		self.reno.congestion_response_undone(congestion_control_state_variables)
+	}
+}
+
+impl DctcpCongestionControlAlgorithm
+{
+	/// RFC 8257: The initial value of 'DCTCP.Alpha'.
+	///
+	/// A value of 1 is conservative; the first reduction in response to congestion halves `cwnd`, as for RFC 3168.
+	const InitialAlpha: f64 = 1.0;
+	
+	/// RFC 8257: The estimation gain, `g`, for which 1/16 is recommended.
+	#[allow(non_upper_case_globals)]
+	const g: f64 = 1.0 / 16.0;
+	
+	/// RFC 8257 Section 3.3 Steps 1 to 8; `bytes_marked` is the number of bytes to add to 'DCTCP.BytesMarked' in Step 3.
+	#[inline(always)]
+	fn observe(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, bytes_acknowledged: u32, bytes_marked: u32)
+	{
+		// `FlightSize` has not yet been decreased by `bytes_acknowledged`.
+		let flight_size = congestion_control_state_variables.FlightSize() as u64;
+		
+		let bytes_acknowledged = bytes_acknowledged as u64;
+		self.total_bytes_acknowledged += bytes_acknowledged;
+		
+		// Step 2: "Update the bytes sent: DCTCP.BytesAcked += BytesAcked".
+		self.bytes_acknowledged += bytes_acknowledged;
+		
+		// Step 3: Update the bytes marked.
+		//
+		// With Accurate ECN, more bytes may have been fed back as marked than have yet been acknowledged; they are capped so that M never exceeds one.
+		self.bytes_marked = min(self.bytes_marked + (bytes_marked as u64), self.bytes_acknowledged);
+		
+		// Step 7: "Determine the end of the next observation window: DCTCP.WindowEnd = SND.NXT".
+		let next_window_end = self.total_bytes_acknowledged + flight_size.saturating_sub(bytes_acknowledged);
+		
+		let window_end = match self.window_end
+		{
+			None =>
+			{
+				self.window_end = Some(next_window_end);
+				return
+			}
+			
+			Some(window_end) => window_end,
+		};
+		
+		// Step 4: "If the acknowledgment number is less than or equal to DCTCP.WindowEnd, stop processing".
+		if self.total_bytes_acknowledged <= window_end
+		{
+			return
+		}
+		
+		// Step 5: "Compute the congestion level for the current observation window: M = DCTCP.BytesMarked / DCTCP.BytesAcked".
+		#[allow(non_snake_case)] let M = (self.bytes_marked as f64) / (self.bytes_acknowledged as f64);
+		
+		// Step 6: "Update the congestion estimate: DCTCP.Alpha = DCTCP.Alpha * (1 - g) + g * M".
+		self.alpha = self.alpha * (1.0 - Self::g) + Self::g * M;
+		
+		self.window_end = Some(next_window_end);
+		
+		// Step 8: "Reset the byte counters: DCTCP.BytesAcked = DCTCP.BytesMarked = 0".
+		self.bytes_acknowledged = 0;
+		self.bytes_marked = 0;
+	}
+}
@@ -34,6 +34,24 @@ pub struct ExplicitCongestionNotificationState
 	///
 	/// In other words, we only set `CongestionWindowReduced` on an outgoing packet which has new data (and is not a zero window probe, which is, by definition, an old data packet filled with garbage).
 	congestion_window_was_reduced_so_set_congestion_window_reduced_on_first_new_data_packet: bool,
+	
+	/// DCTCP (RFC 8257): The explicit congestion echo flag is set on acknowledgments according to the CE codepoint of the most recently received data packet.
+	///
+	/// When `true`, `acknowledgments_should_explicit_congestion_echo` is 'DCTCP.CE' (RFC 8257 Section 3.2: "When sending an ACK, the ECE flag MUST be set if and only if DCTCP.CE is true"), and the `CongestionWindowReduced` flag received from the sender is ignored.
+	explicit_congestion_echo_per_segment: bool,
+	
+	/// Accurate ECN (RFC 9768), if negotiated; the explicit congestion echo (ECE) and congestion window reduced (CWR) flags of RFC 3168 are then replaced by the ACE field.
+	accurate_explicit_congestion_notification: Option<AccurateExplicitCongestionNotificationState>,
+	
+	/// Generalized ECN ('ECN++'), one of the experiments RFC 8311 permits: an ECN-capable transport (ECT) code point is also set on control packets and retransmissions, and not just on new data packets as RFC 3168 requires.
+	///
+	/// A Synchronize is only sent ECN-capable if Accurate ECN is being requested, and pure acknowledgments only if Accurate ECN has been negotiated, as only then is a CE mark on them fed back.
+	generalized_explicit_congestion_notification: bool,
+	
+	/// The path to the remote appears to drop or mangle packets with an ECN-capable transport (ECT) code point, so all packets are sent not-ECT.
+	///
+	/// Explicit congestion notification is still negotiated and CE marks on received packets are still fed back.
+	fall_back_to_not_capable_transport: bool,
 }
 
 impl ExplicitCongestionNotificationState
@@ -42,9 +60,172 @@ impl ExplicitCongestionNotificationState
 	#[inline(always)]
 	pub fn incoming_data_packet_had_congestion_window_reduced_flag_set(&mut self)
 	{
+		if self.explicit_congestion_echo_per_segment
+		{
+			return
+		}
+		
 		self.acknowledgments_should_explicit_congestion_echo = false
 	}
 	
+	/// Is the explicit congestion echo (ECE) flag set on acknowledgments according to the CE codepoint of the most recently received data packet, as for DCTCP (RFC 8257), rather than latched until the `CongestionWindowReduced` flag is received (RFC 3168)?
+	#[inline(always)]
+	pub fn explicit_congestion_echo_per_segment(&self) -> bool
+	{
+		self.explicit_congestion_echo_per_segment
+	}
+	
+	/// Has Accurate ECN (RFC 9768) been negotiated (or, for a client in the state SynchronizeSent, requested)?
+	#[inline(always)]
+	pub fn is_accurate(&self) -> bool
+	{
+		self.accurate_explicit_congestion_notification.is_some()
+	}
+	
+	/// Uses Accurate ECN (RFC 9768).
+	#[inline(always)]
+	pub fn use_accurate_explicit_congestion_notification(&mut self, accurate_explicit_congestion_notification_state: AccurateExplicitCongestionNotificationState)
+	{
+		self.accurate_explicit_congestion_notification = Some(accurate_explicit_congestion_notification_state)
+	}
+	
+	/// Falls back to classic ECN (RFC 3168).
+	#[inline(always)]
+	pub fn disable_accurate_explicit_congestion_notification(&mut self)
+	{
+		self.accurate_explicit_congestion_notification = None
+	}
+	
+	#[allow(missing_docs)]
+	#[inline(always)]
+	pub fn accurate_explicit_congestion_notification_mutable_reference(&mut self) -> Option<&mut AccurateExplicitCongestionNotificationState>
+	{
+		self.accurate_explicit_congestion_notification.as_mut()
+	}
+	
+	/// Uses generalized ECN ('ECN++').
+	#[inline(always)]
+	pub fn use_generalized_explicit_congestion_notification(&mut self)
+	{
+		self.generalized_explicit_congestion_notification = true
+	}
+	
+	/// Have we fallen back to sending all packets not-ECT?
+	#[inline(always)]
+	pub fn has_fallen_back_to_not_capable_transport(&self) -> bool
+	{
+		self.fall_back_to_not_capable_transport
+	}
+	
+	/// The path to the remote drops or mangles packets with an ECN-capable transport (ECT) code point; all further packets are sent not-ECT.
+	#[inline(always)]
+	pub fn fall_back_to_not_capable_transport(&mut self)
+	{
+		self.fall_back_to_not_capable_transport = true
+	}
+	
+	/// May the ECT(0) code point be set on a new data packet?
+	#[inline(always)]
+	pub fn may_set_capable_transport_on_new_data_packet(&self) -> bool
+	{
+		!self.fall_back_to_not_capable_transport
+	}
+	
+	/// May the ECT(0) code point be set on our Synchronize?
+	#[inline(always)]
+	pub fn may_set_capable_transport_on_synchronize(&self) -> bool
+	{
+		self.generalized_explicit_congestion_notification && self.is_accurate() && !self.fall_back_to_not_capable_transport
+	}
+	
+	/// May the ECT(0) code point be set on a control packet (such as a `FIN`, a pure acknowledgment or a zero window probe) or on a retransmission?
+	#[inline(always)]
+	pub fn may_set_capable_transport_on_control_or_retransmitted_packet(&self, is_a_pure_acknowledgment: bool) -> bool
+	{
+		self.generalized_explicit_congestion_notification && !self.fall_back_to_not_capable_transport && (!is_a_pure_acknowledgment || self.is_accurate())
+	}
+	
+	/// The retransmission timer has expired for our Synchronize.
+	///
+	/// If it was sent ECN-capable, it may have been discarded for that reason by a middlebox, or by a remote which follows RFC 3168 strictly, so we fall back to sending all packets, including the retransmitted Synchronize, not-ECT.
+	///
+	/// Returns `true` if we have now fallen back.
+	#[inline(always)]
+	pub fn synchronize_timed_out(&mut self) -> bool
+	{
+		if self.may_set_capable_transport_on_synchronize()
+		{
+			self.fall_back_to_not_capable_transport();
+			true
+		}
+		else
+		{
+			false
+		}
+	}
+	
+	/// RFC 9768: A packet without data has been received with an IP ECN field of `explicit_congestion_notification`; it is counted if CE marked.
+	///
+	/// Does nothing unless `is_accurate()`.
+	#[inline(always)]
+	pub fn incoming_packet_without_data_accurate(&mut self, explicit_congestion_notification: ExplicitCongestionNotification)
+	{
+		if let Some(ref mut accurate_explicit_congestion_notification) = self.accurate_explicit_congestion_notification
+		{
+			accurate_explicit_congestion_notification.incoming_packet_without_data(explicit_congestion_notification)
+		}
+	}
+	
+	/// RFC 9768: A data packet with a payload of `payload_size` bytes has been received with an IP ECN field of `explicit_congestion_notification`; the receiver's counters are updated.
+	///
+	/// Returns `true` if an immediate acknowledgment should be sent.
+	///
+	/// Must only be called if `is_accurate()`.
+	#[inline(always)]
+	pub fn incoming_data_packet_accurate(&mut self, explicit_congestion_notification: ExplicitCongestionNotification, payload_size: u32) -> bool
+	{
+		debug_assert!(self.is_accurate(), "accurate explicit congestion notification is not in use");
+		
+		match self.accurate_explicit_congestion_notification
+		{
+			None => false,
+			
+			Some(ref mut accurate_explicit_congestion_notification) => accurate_explicit_congestion_notification.incoming_data_packet(explicit_congestion_notification, payload_size),
+		}
+	}
+	
+	/// RFC 8257 Section 3.2: Would receiving a data packet with (or without) the CE codepoint change 'DCTCP.CE'?
+	///
+	/// If so, any delayed acknowledgment should be sent before the data packet is processed, as it must carry the current value of 'DCTCP.CE'.
+	///
+	/// Always `false` unless `explicit_congestion_echo_per_segment()`, and always `false` if `is_accurate()`, as the ACE field is a cumulative count.
+	#[inline(always)]
+	pub fn incoming_data_packet_would_change_explicit_congestion_echo(&self, congestion_encountered: bool) -> bool
+	{
+		self.explicit_congestion_echo_per_segment && !self.is_accurate() && self.acknowledgments_should_explicit_congestion_echo != congestion_encountered
+	}
+	
+	/// RFC 8257 Section 3.2:-
+	///
+	/// "1.  If the CE codepoint is set and DCTCP.CE is false, set DCTCP.CE to true and send an immediate ACK.
+	///
+	/// 2.  If the CE codepoint is not set and DCTCP.CE is true, set DCTCP.CE to false and send an immediate ACK.
+	///
+	/// 3.  Otherwise, ignore the CE codepoint".
+	///
+	/// Returns `true` if an immediate acknowledgment should be sent.
+	///
+	/// Must only be called if `explicit_congestion_echo_per_segment()`.
+	#[inline(always)]
+	pub fn incoming_data_packet_per_segment(&mut self, congestion_encountered: bool) -> bool
+	{
+		debug_assert!(self.explicit_congestion_echo_per_segment, "explicit congestion echo is not per segment");
+		
+		let send_an_immediate_acknowledgment = self.acknowledgments_should_explicit_congestion_echo != congestion_encountered;
+		self.acknowledgments_should_explicit_congestion_echo = congestion_encountered;
+		send_an_immediate_acknowledgment
+	}
+	
 	#[allow(missing_docs)]
 	#[inline(always)]
 	pub fn congestion_was_encountered(&mut self)
@@ -84,11 +265,19 @@ impl ExplicitCongestionNotificationState
 	
 	#[allow(missing_docs)]
 	#[inline(always)]
-	pub fn new(explicit_congestion_notification_supported: bool) -> Option<Self>
+	pub fn new(explicit_congestion_notification_supported: bool, explicit_congestion_echo_per_segment: bool, fall_back_to_not_capable_transport: bool) -> Option<Self>
 	{
 		if explicit_congestion_notification_supported
 		{
-			Some(Default::default())
+			Some
+			(
+				Self
+				{
+					explicit_congestion_echo_per_segment,
+					fall_back_to_not_capable_transport,
+					.. Default::default()
+				}
+			)
 		}
 		else
 		{
@@ -19,6 +19,100 @@ pub trait ExplicitCongestionNotificationTransmissionControlBlock: CongestionCont
 		self.congestion_control_reference().explicit_congestion_notification_state.is_none()
 	}
 	
+	/// Is Accurate ECN (RFC 9768) in use for this connection (or, in the state SynchronizeSent, being requested)?
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_supported(&self) -> bool
+	{
+		match self.explicit_congestion_notification_state_reference()
+		{
+			None => false,
+			
+			Some(explicit_congestion_notification_state) => explicit_congestion_notification_state.is_accurate(),
+		}
+	}
+	
+	/// Requests Accurate ECN (RFC 9768) in our Synchronize, or, for a server, uses it once the client has negotiated it.
+	///
+	/// Does nothing if explicit congestion notification is not supported for this connection.
+	#[inline(always)]
+	fn use_accurate_explicit_congestion_notification(&mut self, accurate_explicit_congestion_notification_state: AccurateExplicitCongestionNotificationState)
+	{
+		self.congestion_control_mutable_reference().use_accurate_explicit_congestion_notification(accurate_explicit_congestion_notification_state)
+	}
+	
+	/// The ACE field to send, instead of the CWR and ECE flags, on an acknowledgment (other than a SynchronizeAcknowledgment) if Accurate ECN (RFC 9768) is in use.
+	#[inline(always)]
+	fn accurate_explicit_congestion_echo_field(&mut self) -> Option<AccurateExplicitCongestionEchoField>
+	{
+		self.accurate_explicit_congestion_notification_state_mutable_reference().map(|accurate_explicit_congestion_notification_state| accurate_explicit_congestion_notification_state.acknowledgment_field())
+	}
+	
+	/// The AccECN option to send on an acknowledgment if Accurate ECN (RFC 9768) is in use and the byte counters have changed.
+	///
+	/// Must only be called if there is space for an option of `AccurateExplicitCongestionNotificationOption::MaximumLength`.
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_option(&mut self) -> Option<AccurateExplicitCongestionNotificationOption>
+	{
+		self.accurate_explicit_congestion_notification_state_mutable_reference().and_then(|accurate_explicit_congestion_notification_state| accurate_explicit_congestion_notification_state.option_to_send())
+	}
+	
+	/// Returns `None` if Accurate ECN (RFC 9768) is not in use, otherwise whether CE marks have been fed back.
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_acknowledgment_received(&mut self, accurate_explicit_congestion_echo_field: AccurateExplicitCongestionEchoField, accurate_explicit_congestion_notification_option: Option<&AccurateExplicitCongestionNotificationOption>) -> Option<bool>
+	{
+		self.congestion_control_mutable_reference().accurate_explicit_congestion_notification_acknowledgment_received(accurate_explicit_congestion_echo_field, accurate_explicit_congestion_notification_option)
+	}
+	
+	/// Uses generalized ECN ('ECN++'), setting an ECN-capable transport (ECT) code point on control packets and retransmissions as well as on new data packets.
+	///
+	/// Does nothing if explicit congestion notification is not supported for this connection.
+	#[inline(always)]
+	fn use_generalized_explicit_congestion_notification(&mut self)
+	{
+		if let Some(explicit_congestion_notification_state) = self.explicit_congestion_notification_state_mutable_reference()
+		{
+			explicit_congestion_notification_state.use_generalized_explicit_congestion_notification()
+		}
+	}
+	
+	/// Is our Synchronize sent with the ECT(0) code point?
+	#[inline(always)]
+	fn may_set_capable_transport_on_synchronize(&self) -> bool
+	{
+		match self.explicit_congestion_notification_state_reference()
+		{
+			None => false,
+			
+			Some(explicit_congestion_notification_state) => explicit_congestion_notification_state.may_set_capable_transport_on_synchronize(),
+		}
+	}
+	
+	/// The path to the remote drops or mangles packets with an ECN-capable transport (ECT) code point; all further packets are sent not-ECT.
+	///
+	/// Does nothing if explicit congestion notification is not supported for this connection.
+	#[inline(always)]
+	fn fall_back_to_not_capable_transport(&mut self)
+	{
+		if let Some(explicit_congestion_notification_state) = self.explicit_congestion_notification_state_mutable_reference()
+		{
+			explicit_congestion_notification_state.fall_back_to_not_capable_transport()
+		}
+	}
+	
+	/// Has this connection fallen back to sending all packets not-ECT?
+	///
+	/// Recorded in recent connection data so that later connections to the same remote do likewise.
+	#[inline(always)]
+	fn explicit_congestion_notification_fall_back(&self) -> bool
+	{
+		match self.explicit_congestion_notification_state_reference()
+		{
+			None => false,
+			
+			Some(explicit_congestion_notification_state) => explicit_congestion_notification_state.has_fallen_back_to_not_capable_transport(),
+		}
+	}
+	
 	/// Adds the flag Explicit Congestion Echo (ECE) to acknowledgments if the explicit congestion notification state requires it.
 	#[inline(always)]
 	fn add_explicit_congestion_echo_flag_to_acknowledgment_if_appropriate(&self, flags: Flags) -> Flags
@@ -42,6 +136,15 @@ pub trait ExplicitCongestionNotificationTransmissionControlBlock: CongestionCont
 		self.congestion_control_mutable_reference().disable_explicit_congestion_notification()
 	}
 	
+	/// Falls back from Accurate ECN (RFC 9768) to classic ECN (RFC 3168).
+	///
+	/// Can only be called in the state SynchronizeSent.
+	#[inline(always)]
+	fn disable_accurate_explicit_congestion_notification(&mut self)
+	{
+		self.congestion_control_mutable_reference().disable_accurate_explicit_congestion_notification()
+	}
+	
 	#[doc(hidden)]
 	#[inline(always)]
 	fn explicit_congestion_notification_state_reference(&self) -> Option<&ExplicitCongestionNotificationState>
@@ -55,4 +158,11 @@ pub trait ExplicitCongestionNotificationTransmissionControlBlock: CongestionCont
 	{
 		self.congestion_control_mutable_reference().explicit_congestion_notification_state.as_mut()
 	}
+	
+	#[doc(hidden)]
+	#[inline(always)]
+	fn accurate_explicit_congestion_notification_state_mutable_reference(&mut self) -> Option<&mut AccurateExplicitCongestionNotificationState>
+	{
+		self.explicit_congestion_notification_state_mutable_reference().and_then(|explicit_congestion_notification_state| explicit_congestion_notification_state.accurate_explicit_congestion_notification_mutable_reference())
+	}
 }
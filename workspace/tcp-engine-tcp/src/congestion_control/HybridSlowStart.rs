@@ -0,0 +1,178 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// HyStart++ (RFC 9406); exits slow start before loss occurs when round trip times increase, which indicates a queue is building.
+///
+/// When a delay increase is detected, slow start is not exited at once; instead `cwnd` grows more slowly in Conservative Slow Start (CSS) for up to `CSS_ROUNDS` rounds, and, if round trip times then fall again, the delay increase was spurious and slow start resumes.
+///
+/// A round is ended when a congestion window's worth of bytes, as it was at the start of the round, has been acknowledged.
+///
+/// RFC 9406 Section 4.3: HyStart++ is only used for the initial slow start; later slow starts, eg after a retransmission time out, use standard slow start, as `ssthresh` will then have been discovered.
+#[derive(Default, Debug)]
+pub(crate) struct HybridSlowStart
+{
+	bytes_acknowledged_in_current_round: u32,
+	
+	/// `cwnd` at the start of the current round.
+	current_round_length: u32,
+	
+	/// RFC 9406 Section 4.2: "lastRoundMinRTT", in milliseconds.
+	last_round_minimum_round_trip_time: Option<u64>,
+	
+	/// RFC 9406 Section 4.2: "currentRoundMinRTT", in milliseconds.
+	current_round_minimum_round_trip_time: Option<u64>,
+	
+	/// RFC 9406 Section 4.2: "rttSampleCount".
+	round_trip_time_sample_count: u32,
+	
+	/// RFC 9406 Section 4.2: "cssBaselineMinRtt", in milliseconds; `Some` whilst in Conservative Slow Start.
+	conservative_slow_start_baseline_minimum_round_trip_time: Option<u64>,
+	
+	/// The number of rounds started whilst in Conservative Slow Start.
+	conservative_slow_start_rounds: u32,
+	
+	/// Growth of `cwnd` in Conservative Slow Start which was lost to integer division by `CSS_GROWTH_DIVISOR`.
+	conservative_slow_start_growth_remainder: u32,
+	
+	initial_slow_start_ended: bool,
+}
+
+impl HybridSlowStart
+{
+	/// RFC 9406 Section 4.3: "MIN_RTT_THRESH = 4 msec".
+	const MinimumRoundTripTimeThreshold: u64 = 4;
+	
+	/// RFC 9406 Section 4.3: "MAX_RTT_THRESH = 16 msec".
+	const MaximumRoundTripTimeThreshold: u64 = 16;
+	
+	/// RFC 9406 Section 4.3: "MIN_RTT_DIVISOR = 8".
+	const MinimumRoundTripTimeDivisor: u64 = 8;
+	
+	/// RFC 9406 Section 4.3: "N_RTT_SAMPLE = 8".
+	const NumberOfRoundTripTimeSamples: u32 = 8;
+	
+	/// RFC 9406 Section 4.3: "CSS_GROWTH_DIVISOR = 4".
+	const ConservativeSlowStartGrowthDivisor: u32 = 4;
+	
+	/// RFC 9406 Section 4.3: "CSS_ROUNDS = 5".
+	const ConservativeSlowStartRounds: u32 = 5;
+	
+	/// Slow start is being re-entered after an idle period without having been exited; delay increase detection starts afresh.
+	#[inline(always)]
+	pub(crate) fn reset(&mut self)
+	{
+		let initial_slow_start_ended = self.initial_slow_start_ended;
+		*self = Self::default();
+		self.initial_slow_start_ended = initial_slow_start_ended;
+	}
+	
+	/// RFC 9406 Section 4.2: Loss or an ECN-Echo during standard slow start or Conservative Slow Start, or a retransmission time out, ends the initial slow start; the normal congestion response then applies.
+	#[inline(always)]
+	pub(crate) fn slow_start_ended(&mut self)
+	{
+		*self = Self::default();
+		self.initial_slow_start_ended = true;
+	}
+	
+	/// RFC 9406 Section 4.2: "For each arriving ACK in slow start, where N is the number of previously unacknowledged bytes acknowledged in the arriving ACK".
+	///
+	/// Increases `cwnd`, using `appropriate_byte_counting` for standard slow start.
+	///
+	/// Returns `true` if slow start should be exited, by setting `ssthresh` to `cwnd`, because Conservative Slow Start has lasted for `CSS_ROUNDS` rounds.
+	#[inline(always)]
+	pub(crate) fn slow_start(&mut self, appropriate_byte_counting: &mut AppropriateByteCounting, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32) -> bool
+	{
+		if self.initial_slow_start_ended
+		{
+			appropriate_byte_counting.slow_start(congestion_control_state_variables, bytes_acknowledged);
+			return false
+		}
+		
+		self.bytes_acknowledged_in_current_round = self.bytes_acknowledged_in_current_round.saturating_add(bytes_acknowledged);
+		
+		// RFC 9406 Section 4.2: "At the start of each round during standard slow start and CSS, initialize the variables used to compute the last round's and current round's minimum RTT".
+		if self.bytes_acknowledged_in_current_round >= self.current_round_length
+		{
+			self.bytes_acknowledged_in_current_round = 0;
+			self.current_round_length = congestion_control_state_variables.congestion_window();
+			self.last_round_minimum_round_trip_time = self.current_round_minimum_round_trip_time.take();
+			self.round_trip_time_sample_count = 0;
+			
+			// RFC 9406 Section 4.2: "If CSS_ROUNDS rounds are complete, enter congestion avoidance by setting the ssthresh to the current cwnd".
+			if self.conservative_slow_start_baseline_minimum_round_trip_time.is_some()
+			{
+				self.conservative_slow_start_rounds += 1;
+				if self.conservative_slow_start_rounds >= Self::ConservativeSlowStartRounds
+				{
+					self.slow_start_ended();
+					return true
+				}
+			}
+		}
+		
+		if self.conservative_slow_start_baseline_minimum_round_trip_time.is_some()
+		{
+			// RFC 9406 Section 4.2: "cwnd = cwnd + (min(N, L * SMSS) / CSS_GROWTH_DIVISOR)"; L is one, as for standard slow start using Appropriate Byte Counting.
+			let sender_maximum_segment_size = congestion_control_state_variables.sender_maximum_segment_size();
+			let growth = min(bytes_acknowledged, sender_maximum_segment_size) + self.conservative_slow_start_growth_remainder;
+			self.conservative_slow_start_growth_remainder = growth % Self::ConservativeSlowStartGrowthDivisor;
+			congestion_control_state_variables.increment_congestion_window(growth / Self::ConservativeSlowStartGrowthDivisor);
+		}
+		else
+		{
+			appropriate_byte_counting.slow_start(congestion_control_state_variables, bytes_acknowledged);
+		}
+		
+		false
+	}
+	
+	/// RFC 9406 Section 4.2: "Keep track of the minimum observed RTT".
+	#[inline(always)]
+	pub(crate) fn round_trip_time_sample(&mut self, measurement_of_round_trip_time: MillisecondDuration)
+	{
+		if self.initial_slow_start_ended
+		{
+			return
+		}
+		
+		let measurement_of_round_trip_time: u64 = measurement_of_round_trip_time.into();
+		
+		let current_round_minimum_round_trip_time = match self.current_round_minimum_round_trip_time
+		{
+			None => measurement_of_round_trip_time,
+			Some(current_round_minimum_round_trip_time) => min(current_round_minimum_round_trip_time, measurement_of_round_trip_time),
+		};
+		self.current_round_minimum_round_trip_time = Some(current_round_minimum_round_trip_time);
+		self.round_trip_time_sample_count += 1;
+		
+		if self.round_trip_time_sample_count < Self::NumberOfRoundTripTimeSamples
+		{
+			return
+		}
+		
+		match self.conservative_slow_start_baseline_minimum_round_trip_time
+		{
+			// RFC 9406 Section 4.2: "For rounds where at least N_RTT_SAMPLE RTT samples have been obtained and currentRoundMinRTT and lastRoundMinRTT are valid, check to see if delay increase triggers slow start exit".
+			None => if let Some(last_round_minimum_round_trip_time) = self.last_round_minimum_round_trip_time
+			{
+				// RFC 9406 Section 4.2: "RttThresh = max(MIN_RTT_THRESH, min(lastRoundMinRTT / MIN_RTT_DIVISOR, MAX_RTT_THRESH))".
+				let round_trip_time_threshold = max(Self::MinimumRoundTripTimeThreshold, min(last_round_minimum_round_trip_time / Self::MinimumRoundTripTimeDivisor, Self::MaximumRoundTripTimeThreshold));
+				
+				// RFC 9406 Section 4.2: "if (currentRoundMinRTT >= (lastRoundMinRTT + RttThresh)) cssBaselineMinRtt = currentRoundMinRTT; exit slow start and enter CSS".
+				if current_round_minimum_round_trip_time >= last_round_minimum_round_trip_time + round_trip_time_threshold
+				{
+					self.conservative_slow_start_baseline_minimum_round_trip_time = Some(current_round_minimum_round_trip_time);
+					self.conservative_slow_start_rounds = 0;
+					self.conservative_slow_start_growth_remainder = 0;
+				}
+			},
+			
+			// RFC 9406 Section 4.2: "if (currentRoundMinRTT < cssBaselineMinRtt) cssBaselineMinRtt = infinity; resume slow start including HyStart++".
+			Some(conservative_slow_start_baseline_minimum_round_trip_time) => if current_round_minimum_round_trip_time < conservative_slow_start_baseline_minimum_round_trip_time
+			{
+				self.conservative_slow_start_baseline_minimum_round_trip_time = None;
+			},
+		}
+	}
+}
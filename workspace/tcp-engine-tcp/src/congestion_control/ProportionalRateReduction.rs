@@ -0,0 +1,119 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Proportional Rate Reduction (PRR), as specified by RFC 6937, using the Slow Start Reduction Bound (PRR-SSRB).
+///
+/// During fast recovery, the data in flight is reduced towards `ssthresh` in proportion to the data delivered to the receiver, so that transmission is spread evenly across the recovery rather than stalling until enough data has left the network and then bursting.
+/// If losses have reduced the data in flight below `ssthresh`, it is increased towards `ssthresh` no faster than slow start would.
+///
+/// Without selective acknowledgments, the data delivered is estimated as one segment for each duplicate acknowledgment, and this estimate is corrected when the data is cumulatively acknowledged.
+#[derive(Default, Debug)]
+#[allow(non_snake_case)]
+pub(crate) struct ProportionalRateReduction
+{
+	/// "RecoverFS": `FlightSize` at the start of recovery.
+	RecoverFS: u32,
+	
+	/// "prr_delivered": The total number of bytes delivered to the receiver since the start of recovery.
+	prr_delivered: u64,
+	
+	/// "prr_out": The total number of bytes transmitted (including retransmitted) since the start of recovery.
+	prr_out: u64,
+	
+	/// With selective acknowledgments, "SACKd", the number of bytes selectively acknowledged, as of the most recent acknowledgment.
+	///
+	/// `None` until the first acknowledgment during recovery.
+	selectively_acknowledged: Option<u32>,
+	
+	/// Without selective acknowledgments, the number of bytes which duplicate acknowledgments are estimated to have delivered which have not yet been cumulatively acknowledged.
+	estimated_delivered_by_duplicate_acknowledgments: u32,
+}
+
+impl ProportionalRateReduction
+{
+	/// RFC 6937 Section 3.1: "Initialization on entering recovery".
+	///
+	/// `duplicate_acknowledgments_before_recovery` is the number of bytes estimated to have been delivered by duplicate acknowledgments received before recovery was entered (not including the one which caused it to be entered); it is zero if selective acknowledgments are in use.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub(crate) fn start(&mut self, RecoverFS: u32, duplicate_acknowledgments_before_recovery: u32)
+	{
+		self.RecoverFS = RecoverFS;
+		self.prr_delivered = 0;
+		self.prr_out = 0;
+		self.selectively_acknowledged = None;
+		self.estimated_delivered_by_duplicate_acknowledgments = duplicate_acknowledgments_before_recovery;
+	}
+	
+	/// RFC 6937 Section 3.1: "On any data transmission or retransmission: prr_out += (data sent)".
+	#[inline(always)]
+	pub(crate) fn sent(&mut self, bytes: u32)
+	{
+		self.prr_out += bytes as u64;
+	}
+	
+	/// RFC 6937 Section 3: "DeliveredData = change_in(snd.una) + change_in(SACKd)".
+	#[inline(always)]
+	pub(crate) fn delivered_data_with_selective_acknowledgments(&mut self, bytes_cumulatively_acknowledged: u32, selectively_acknowledged: u32) -> u32
+	{
+		let previously_selectively_acknowledged = self.selectively_acknowledged.unwrap_or(selectively_acknowledged);
+		self.selectively_acknowledged = Some(selectively_acknowledged);
+		
+		// Data cumulatively acknowledged which had been selectively acknowledged is no longer counted in 'SACKd', and so is not counted twice.
+		(bytes_cumulatively_acknowledged + selectively_acknowledged).saturating_sub(previously_selectively_acknowledged)
+	}
+	
+	/// Without selective acknowledgments, a duplicate acknowledgment is estimated to have delivered one segment.
+	#[inline(always)]
+	pub(crate) fn delivered_data_for_duplicate_acknowledgment(&mut self, sender_maximum_segment_size: u32) -> u32
+	{
+		self.estimated_delivered_by_duplicate_acknowledgments += sender_maximum_segment_size;
+		sender_maximum_segment_size
+	}
+	
+	/// Without selective acknowledgments, data cumulatively acknowledged which duplicate acknowledgments have already been estimated to have delivered is not counted again.
+	#[inline(always)]
+	pub(crate) fn delivered_data_for_cumulative_acknowledgment(&mut self, bytes_cumulatively_acknowledged: u32) -> u32
+	{
+		let delivered_data = bytes_cumulatively_acknowledged.saturating_sub(self.estimated_delivered_by_duplicate_acknowledgments);
+		self.estimated_delivered_by_duplicate_acknowledgments = self.estimated_delivered_by_duplicate_acknowledgments.saturating_sub(bytes_cumulatively_acknowledged);
+		delivered_data
+	}
+	
+	/// Without selective acknowledgments, 'pipe' is estimated as `FlightSize` less the data which duplicate acknowledgments are estimated to have delivered.
+	#[inline(always)]
+	pub(crate) fn pipe_without_selective_acknowledgments(&self, flight_size: u32) -> u32
+	{
+		flight_size.saturating_sub(self.estimated_delivered_by_duplicate_acknowledgments)
+	}
+	
+	/// RFC 6937 Section 3.1: "On every ACK during recovery"; returns 'sndcnt', the number of bytes which may be transmitted in response to this acknowledgment.
+	///
+	/// The caller should then set "cwnd = pipe + sndcnt".
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub(crate) fn send_count(&mut self, DeliveredData: u32, pipe: u32, ssthresh: u32, sender_maximum_segment_size: u32) -> u32
+	{
+		// "prr_delivered += DeliveredData".
+		self.prr_delivered += DeliveredData as u64;
+		
+		let sndcnt = if pipe > ssthresh
+		{
+			// "Proportional Rate Reduction: sndcnt = CEIL(prr_delivered * ssthresh / RecoverFS) - prr_out".
+			let RecoverFS = max(self.RecoverFS, 1) as u64;
+			let proportion = (self.prr_delivered * (ssthresh as u64) + RecoverFS - 1) / RecoverFS;
+			proportion.saturating_sub(self.prr_out)
+		}
+		else
+		{
+			// "PRR-SSRB: limit = MAX(prr_delivered - prr_out, DeliveredData) + MSS".
+			let limit = max(self.prr_delivered.saturating_sub(self.prr_out), DeliveredData as u64) + (sender_maximum_segment_size as u64);
+			
+			// "Attempt to catch up, as permitted by limit: sndcnt = MIN(ssthresh - pipe, limit)".
+			min((ssthresh - pipe) as u64, limit)
+		};
+		
+		min(sndcnt, ::std::u32::MAX as u64) as u32
+	}
+}
@@ -0,0 +1,108 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Reno, as specified by RFC 5681, using Appropriate Byte Counting (RFC 3465).
+///
+/// Slow start is exited early using HyStart++ (RFC 9406).
+///
+/// This is the default congestion control algorithm.
+#[derive(Default, Debug)]
+pub struct RenoCongestionControlAlgorithm
+{
+	appropriate_byte_counting: AppropriateByteCounting,
+	
+	hybrid_slow_start: HybridSlowStart,
+}
+
+impl CongestionControlAlgorithm for RenoCongestionControlAlgorithm
+{
+	/// RFC 5681: "... N is the number of previously unacknowledged bytes acknowledged in the incoming ACK".
+	///
+	/// `N` is `bytes_acked` in RFC 3465.
+	///
+	/// RFC 5681 Section 3.1 permits either slow start or congestion avoidance when cwnd and ssthresh are equal; congestion avoidance is used so that setting ssthresh to cwnd exits slow start.
+	#[inline(always)]
+	fn acknowledgment(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, bytes_acknowledged: u32, _now: MonotonicMillisecondTimestamp)
+	{
+		if congestion_control_state_variables.congestion_window() < congestion_control_state_variables.ssthresh()
+		{
+			// RFC 9406 Section 4.2: "If CSS_ROUNDS rounds are complete, enter congestion avoidance by setting the ssthresh to the current cwnd".
+			if self.hybrid_slow_start.slow_start(&mut self.appropriate_byte_counting, congestion_control_state_variables, bytes_acknowledged)
+			{
+				let congestion_window = congestion_control_state_variables.congestion_window();
+				congestion_control_state_variables.set_ssthresh(congestion_window);
+				self.appropriate_byte_counting.reset();
+			}
+		}
+		else
+		{
+			self.appropriate_byte_counting.congestion_avoidance(congestion_control_state_variables, bytes_acknowledged)
+		}
+	}
+	
+	/// RFC 5681 Section 3.2 Step 2: "When the third duplicate ACK is received, a TCP MUST set ssthresh to no more than the value given in equation (4)".
+	#[inline(always)]
+	fn loss(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		let ssthresh = congestion_control_state_variables.half_of_flight_size_but_at_least_two_segments();
+		congestion_control_state_variables.set_ssthresh(ssthresh);
+		
+		// Byte counting for congestion avoidance starts afresh once loss recovery is over.
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// RFC 3168 Section 6.1.2 Paragraph 1: "... the TCP source halves the congestion window "cwnd" and reduces the slow start threshold "ssthresh"".
+	#[inline(always)]
+	fn explicit_congestion_echo(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		let ssthresh = max(congestion_control_state_variables.congestion_window() / 2, 2 * congestion_control_state_variables.sender_maximum_segment_size());
+		congestion_control_state_variables.set_ssthresh(ssthresh);
+		congestion_control_state_variables.set_congestion_window(ssthresh);
+		
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// RFC 5681 Section 3.1 Page 7: "When a TCP sender detects segment loss using the retransmission timer and the given segment has not yet been resent by way of the retransmission timer, the value of ssthresh MUST be set to no more than the value given in equation (4): ssthresh = max (FlightSize / 2, 2*SMSS) where ... FlightSize is the amount of outstanding data in the network".
+	///
+	/// RFC 5681 Section 7 Paragraph 6: "...  ssthresh must be set to half the FlightSize on the first retransmission of a given segment and then is held constant on subsequent retransmissions of the same segment".
+	#[inline(always)]
+	fn retransmission_time_out(&mut self, congestion_control_state_variables: &mut CongestionControlStateVariables, is_first_retransmission: bool, _now: MonotonicMillisecondTimestamp)
+	{
+		if is_first_retransmission
+		{
+			let ssthresh = congestion_control_state_variables.half_of_flight_size_but_at_least_two_segments();
+			congestion_control_state_variables.set_ssthresh(ssthresh);
+		}
+		
+		// After a retransmission time out slow start is used again, so byte counting for congestion avoidance starts afresh once ssthresh is reached.
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.slow_start_ended();
+	}
+	
+	/// `cwnd` has been reduced to the restart window, so slow start is used again.
+	#[inline(always)]
+	fn restart_after_idle(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables, _now: MonotonicMillisecondTimestamp)
+	{
+		self.appropriate_byte_counting.reset();
+		self.hybrid_slow_start.reset();
+	}
+	
+	/// RFC 9406 Section 4.2: Measurements are used to detect a delay increase during slow start.
+	#[inline(always)]
+	fn round_trip_time_sample(&mut self, congestion_control_state_variables: &CongestionControlStateVariables, measurement_of_round_trip_time: MillisecondDuration, _smoothed_round_trip_time: MillisecondDuration, _now: MonotonicMillisecondTimestamp)
+	{
+		if congestion_control_state_variables.congestion_window() < congestion_control_state_variables.ssthresh()
+		{
+			self.hybrid_slow_start.round_trip_time_sample(measurement_of_round_trip_time)
+		}
+	}
+	
+	#[inline(always)]
+	fn congestion_response_undone(&mut self, _congestion_control_state_variables: &mut CongestionControlStateVariables)
+	{
+		self.appropriate_byte_counting.reset();
+	}
+}
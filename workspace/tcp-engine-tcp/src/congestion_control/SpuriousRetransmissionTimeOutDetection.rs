@@ -0,0 +1,73 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Detection of a spurious retransmission time out, ie one for which the segment retransmitted had been delayed (eg by a sudden increase in the round trip time) rather than lost.
+///
+/// If timestamps are in use, the Eifel detection algorithm (RFC 3522) is used: the first acknowledgment after the retransmission echoes the timestamp of the original transmission if that is what arrived.
+/// Otherwise, the basic F-RTO algorithm (RFC 5682 Section 2) is used: new data is transmitted instead of retransmitting, and if the acknowledgments which follow acknowledge data which was not retransmitted then the original transmissions arrived.
+#[derive(Debug)]
+pub(crate) struct SpuriousRetransmissionTimeOutDetection
+{
+	/// RFC 3522 Section 2 'RetransmitTS': When the segment was retransmitted.
+	retransmitted_at: MonotonicMillisecondTimestamp,
+	
+	/// RFC 5682 Section 2.1 Step 1: "recover", the highest sequence number transmitted when the retransmission timer expired.
+	recover: WrappingSequenceNumber,
+	
+	/// RFC 5682 Section 2.1 Step 2b: `cwnd` before it was raised to permit up to two new segments to be transmitted.
+	///
+	/// `None` until the first acknowledgment after the retransmission; `Some` whilst waiting for the second (Step 3).
+	congestion_window_before_transmitting_new_segments: Option<u32>,
+}
+
+impl SpuriousRetransmissionTimeOutDetection
+{
+	#[inline(always)]
+	pub(crate) fn new(retransmitted_at: MonotonicMillisecondTimestamp, recover: WrappingSequenceNumber) -> Self
+	{
+		Self
+		{
+			retransmitted_at,
+			recover,
+			congestion_window_before_transmitting_new_segments: None,
+		}
+	}
+	
+	/// RFC 3522 Section 2 'RetransmitTS'.
+	#[inline(always)]
+	pub(crate) fn retransmitted_at(&self) -> MonotonicMillisecondTimestamp
+	{
+		self.retransmitted_at
+	}
+	
+	/// RFC 5682 Section 2.1 Step 1: "recover".
+	#[inline(always)]
+	pub(crate) fn recover(&self) -> WrappingSequenceNumber
+	{
+		self.recover
+	}
+	
+	/// Is the first acknowledgment after the retransmission still awaited (RFC 5682 Section 2.1 Step 2), rather than the second (Step 3)?
+	#[inline(always)]
+	pub(crate) fn is_awaiting_first_acknowledgment(&self) -> bool
+	{
+		self.congestion_window_before_transmitting_new_segments.is_none()
+	}
+	
+	/// RFC 5682 Section 2.1 Step 2b: Up to two new segments are to be transmitted; `congestion_window` is `cwnd` before it is raised to permit this.
+	#[inline(always)]
+	pub(crate) fn transmitting_new_segments(&mut self, congestion_window: u32)
+	{
+		self.congestion_window_before_transmitting_new_segments = Some(congestion_window)
+	}
+	
+	/// `cwnd` to use if conventional recovery continues.
+	///
+	/// `None` if it was not raised to permit new segments to be transmitted.
+	#[inline(always)]
+	pub(crate) fn congestion_window_for_conventional_recovery(&self) -> Option<u32>
+	{
+		self.congestion_window_before_transmitting_new_segments
+	}
+}
@@ -0,0 +1,93 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// A windowed maximum filter, using Kathleen Nichols' algorithm for tracking the maximum value of a data stream over a window, which keeps the best, second best and third best values.
+///
+/// Times are in arbitrary, monotonically increasing, units, eg round trips.
+#[derive(Default, Debug)]
+pub(crate) struct WindowedMaximumFilter
+{
+	/// `(time, value)`.
+	estimates: [(u64, u64); 3],
+}
+
+impl WindowedMaximumFilter
+{
+	/// The current maximum.
+	#[inline(always)]
+	pub(crate) fn maximum(&self) -> u64
+	{
+		self.estimates[0].1
+	}
+	
+	/// Resets the filter to contain just `value` measured at `time`.
+	#[inline(always)]
+	pub(crate) fn reset(&mut self, time: u64, value: u64)
+	{
+		let estimate = (time, value);
+		self.estimates = [estimate, estimate, estimate];
+	}
+	
+	/// Updates the filter with a new `value` measured at `time`, and returns the maximum over the `window`.
+	#[inline(always)]
+	pub(crate) fn update(&mut self, window: u64, time: u64, value: u64) -> u64
+	{
+		let estimate = (time, value);
+		
+		// A new best value, or nothing left in the window.
+		if value >= self.estimates[0].1 || time - self.estimates[2].0 > window
+		{
+			self.reset(time, value);
+			return self.maximum()
+		}
+		
+		if value >= self.estimates[1].1
+		{
+			self.estimates[2] = estimate;
+			self.estimates[1] = estimate;
+		}
+		else if value >= self.estimates[2].1
+		{
+			self.estimates[2] = estimate;
+		}
+		
+		self.sub_window_update(window, estimate)
+	}
+	
+	/// As time passes, older estimates age out of the window and are replaced by the next best; if no new estimates arrive the second and third best are refreshed in quarters and halves of the window so that they are not all equally old.
+	#[inline(always)]
+	fn sub_window_update(&mut self, window: u64, estimate: (u64, u64)) -> u64
+	{
+		let time = estimate.0;
+		let elapsed = time - self.estimates[0].0;
+		
+		if elapsed > window
+		{
+			// The best estimate has aged out of the window; the second and third best move up.
+			self.estimates[0] = self.estimates[1];
+			self.estimates[1] = self.estimates[2];
+			self.estimates[2] = estimate;
+			
+			if time - self.estimates[0].0 > window
+			{
+				self.estimates[0] = self.estimates[1];
+				self.estimates[1] = self.estimates[2];
+				self.estimates[2] = estimate;
+			}
+		}
+		else if self.estimates[1].0 == self.estimates[0].0 && elapsed > window / 4
+		{
+			// A quarter of the window has passed without a second best estimate; use this one.
+			self.estimates[2] = estimate;
+			self.estimates[1] = estimate;
+		}
+		else if self.estimates[2].0 == self.estimates[1].0 && elapsed > window / 2
+		{
+			// Half of the window has passed without a third best estimate; use this one.
+			self.estimates[2] = estimate;
+		}
+		
+		self.maximum()
+	}
+}
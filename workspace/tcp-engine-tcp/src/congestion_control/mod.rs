@@ -6,8 +6,27 @@ use super::*;
 use super::recent_connection_data::RecentConnectionData;
 
 
+include!("AccurateExplicitCongestionNotificationState.rs");
+include!("AppropriateByteCounting.rs");
+include!("BbrCongestionControlAlgorithm.rs");
+include!("BbrState.rs");
 include!("CongestionControl.rs");
+include!("CongestionControlAlgorithm.rs");
+include!("CongestionControlAlgorithmConstructor.rs");
+include!("CongestionControlStateVariables.rs");
 include!("CongestionControlTransmissionControlBlock.rs");
+include!("CongestionWindowValidation.rs");
+include!("CubicCongestionControlAlgorithm.rs");
+include!("CubicEpoch.rs");
+include!("DctcpCongestionControlAlgorithm.rs");
 include!("ExplicitCongestionNotificationState.rs");
 include!("ExplicitCongestionNotificationTransmissionControlBlock.rs");
+include!("HybridSlowStart.rs");
 include!("InitialCongestionWindowAlgorithm.rs");
+include!("ProportionalRateReduction.rs");
+include!("RenoCongestionControlAlgorithm.rs");
+include!("SpuriousRetransmissionTimeOutDetection.rs");
+include!("WindowedMaximumFilter.rs");
+
+
+#[cfg(test)] mod tests;
@@ -0,0 +1,371 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+use super::*;
+
+
+const SenderMaximumSegmentSize: u32 = 1000;
+
+/// RFC 5681 Section 3.1: "if SMSS <= 1095 bytes: IW = 4 * SMSS bytes".
+const InitialWindow: u32 = 4 * SenderMaximumSegmentSize;
+
+/// Sends `bytes` of new data and then acknowledges all of it with one acknowledgment.
+#[allow(non_snake_case)]
+fn send_and_acknowledge(congestion_control: &mut CongestionControl, SND_UNA: &mut u32, bytes: u32, now: MonotonicMillisecondTimestamp)
+{
+	congestion_control.bytes_sent_in_payload_in_a_segment_which_is_not_a_zero_window_probe_or_retransmission(bytes);
+	
+	*SND_UNA += bytes;
+	let is_partial_acknowledgment_during_fast_recovery = congestion_control.increase_bytes_acknowledged(bytes, WrappingSequenceNumber::from(*SND_UNA), now);
+	assert!(!is_partial_acknowledgment_during_fast_recovery, "not in fast recovery");
+}
+
+/// No round trip times are measured, so HyStart++ never enters Conservative Slow Start and slow start is standard slow start.
+fn reno(ssthresh: u32) -> (CongestionControl, MonotonicMillisecondTimestamp)
+{
+	let now = MonotonicMillisecondTimestamp::now();
+	let recent_connection_data = RecentConnectionData::default().new(MillisecondDuration::from_milliseconds(100), MillisecondDuration::from_milliseconds(50), ssthresh, false);
+	
+	let congestion_control = CongestionControl::new(false, InitialCongestionWindowAlgorithm::RFC_5681, RenoCongestionControlAlgorithm::boxed, now, SenderMaximumSegmentSize as u16, &recent_connection_data);
+	assert_eq!(congestion_control.congestion_window(), InitialWindow, "initial window");
+	assert_eq!(congestion_control.ssthresh(), ssthresh, "ssthresh");
+	
+	(congestion_control, now)
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn slow_start_growth_is_bounded_by_appropriate_byte_counting()
+{
+	let (mut congestion_control, now) = reno(1_000_000);
+	let mut SND_UNA = 0;
+	
+	// RFC 3465 Section 2.3 and RFC 5681 Section 3.1: A stretch acknowledgment of a whole window increases cwnd by at most L * SMSS, where L is one (1).
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, InitialWindow, now);
+	assert_eq!(congestion_control.congestion_window(), InitialWindow + SenderMaximumSegmentSize);
+	
+	// An acknowledgment of less than SMSS increases cwnd by only the bytes acknowledged.
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize / 2, now);
+	assert_eq!(congestion_control.congestion_window(), InitialWindow + SenderMaximumSegmentSize + SenderMaximumSegmentSize / 2);
+	
+	// An acknowledgment for each full sized segment doubles cwnd in one round trip.
+	let congestion_window = congestion_control.congestion_window();
+	for _ in 0 .. congestion_window / SenderMaximumSegmentSize
+	{
+		let before = congestion_control.congestion_window();
+		send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+		assert_eq!(congestion_control.congestion_window(), before + SenderMaximumSegmentSize);
+	}
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn slow_start_is_exited_at_ssthresh()
+{
+	const ssthresh: u32 = InitialWindow + 2 * SenderMaximumSegmentSize;
+	
+	let (mut congestion_control, now) = reno(ssthresh);
+	let mut SND_UNA = 0;
+	
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+	assert_eq!(congestion_control.congestion_window(), InitialWindow + SenderMaximumSegmentSize);
+	
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+	assert_eq!(congestion_control.congestion_window(), ssthresh);
+	
+	// RFC 5681 Section 3.1: Once cwnd has reached ssthresh, congestion avoidance is used, so acknowledging less than cwnd does not increase cwnd.
+	for _ in 0 .. (ssthresh / SenderMaximumSegmentSize) - 1
+	{
+		send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+		assert_eq!(congestion_control.congestion_window(), ssthresh);
+	}
+	
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+	assert_eq!(congestion_control.congestion_window(), ssthresh + SenderMaximumSegmentSize);
+	assert_eq!(congestion_control.ssthresh(), ssthresh, "ssthresh is not changed by exiting slow start");
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn congestion_avoidance_grows_by_one_segment_per_congestion_window_of_data_acknowledged()
+{
+	// `ssthresh` is less than the initial window, so congestion avoidance is used from the start.
+	let (mut congestion_control, now) = reno(2 * SenderMaximumSegmentSize);
+	let mut SND_UNA = 0;
+	
+	for _ in 0 .. 4
+	{
+		let congestion_window = congestion_control.congestion_window();
+		let segments_per_congestion_window = congestion_window / SenderMaximumSegmentSize;
+		
+		for _ in 0 .. segments_per_congestion_window - 1
+		{
+			send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+			assert_eq!(congestion_control.congestion_window(), congestion_window, "cwnd increased before a congestion window of data was acknowledged");
+		}
+		
+		send_and_acknowledge(&mut congestion_control, &mut SND_UNA, SenderMaximumSegmentSize, now);
+		assert_eq!(congestion_control.congestion_window(), congestion_window + SenderMaximumSegmentSize, "cwnd did not increase by SMSS once a congestion window of data was acknowledged");
+	}
+	
+	// RFC 5681 Section 3.1: "... during congestion avoidance, cwnd MUST NOT be increased by more than SMSS bytes per RTT"; a stretch acknowledgment of several windows increases cwnd by only one SMSS.
+	let congestion_window = congestion_control.congestion_window();
+	send_and_acknowledge(&mut congestion_control, &mut SND_UNA, 3 * congestion_window, now);
+	assert_eq!(congestion_control.congestion_window(), congestion_window + SenderMaximumSegmentSize);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn proportional_rate_reduction_sends_ssthresh_bytes_in_proportion_to_data_delivered()
+{
+	const RecoverFS: u32 = 10 * SenderMaximumSegmentSize;
+	const ssthresh: u32 = RecoverFS / 2;
+	
+	let mut proportional_rate_reduction = ProportionalRateReduction::default();
+	proportional_rate_reduction.start(RecoverFS, 0);
+	
+	// Each segment delivered whilst 'pipe' exceeds ssthresh permits half a segment to be sent, so that once RecoverFS has been delivered, ssthresh has been sent.
+	const pipe: u32 = ssthresh + SenderMaximumSegmentSize;
+	let mut total_sent = 0;
+	for _ in 0 .. RecoverFS / SenderMaximumSegmentSize
+	{
+		let sndcnt = proportional_rate_reduction.send_count(SenderMaximumSegmentSize, pipe, ssthresh, SenderMaximumSegmentSize);
+		assert_eq!(sndcnt, SenderMaximumSegmentSize / 2);
+		
+		proportional_rate_reduction.sent(sndcnt);
+		total_sent += sndcnt;
+	}
+	
+	assert_eq!(total_sent, ssthresh);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn proportional_rate_reduction_rounds_up()
+{
+	let mut proportional_rate_reduction = ProportionalRateReduction::default();
+	proportional_rate_reduction.start(3 * SenderMaximumSegmentSize, 0);
+	
+	// "CEIL(prr_delivered * ssthresh / RecoverFS)": 1000 * 1000 / 3000 is 333.3.
+	assert_eq!(proportional_rate_reduction.send_count(SenderMaximumSegmentSize, 2 * SenderMaximumSegmentSize, SenderMaximumSegmentSize, SenderMaximumSegmentSize), 334);
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn proportional_rate_reduction_slow_start_reduction_bound_limits_catching_up_to_ssthresh()
+{
+	const ssthresh: u32 = 5 * SenderMaximumSegmentSize;
+	
+	let mut proportional_rate_reduction = ProportionalRateReduction::default();
+	proportional_rate_reduction.start(10 * SenderMaximumSegmentSize, 0);
+	
+	// Losses have reduced 'pipe' well below ssthresh; "limit = MAX(prr_delivered - prr_out, DeliveredData) + MSS", so no more than one segment more than was delivered is sent.
+	let sndcnt = proportional_rate_reduction.send_count(SenderMaximumSegmentSize, 2 * SenderMaximumSegmentSize, ssthresh, SenderMaximumSegmentSize);
+	assert_eq!(sndcnt, 2 * SenderMaximumSegmentSize);
+	proportional_rate_reduction.sent(sndcnt);
+	
+	// More has been sent than delivered, so the limit is DeliveredData + MSS.
+	let sndcnt = proportional_rate_reduction.send_count(SenderMaximumSegmentSize / 2, SenderMaximumSegmentSize, ssthresh, SenderMaximumSegmentSize);
+	assert_eq!(sndcnt, SenderMaximumSegmentSize / 2 + SenderMaximumSegmentSize);
+	proportional_rate_reduction.sent(sndcnt);
+	
+	// "sndcnt = MIN(ssthresh - pipe, limit)": no more is sent than is needed to reach ssthresh.
+	let sndcnt = proportional_rate_reduction.send_count(SenderMaximumSegmentSize, ssthresh - SenderMaximumSegmentSize / 4, ssthresh, SenderMaximumSegmentSize);
+	assert_eq!(sndcnt, SenderMaximumSegmentSize / 4);
+}
+
+#[test]
+fn proportional_rate_reduction_does_not_count_selectively_acknowledged_data_twice()
+{
+	let mut proportional_rate_reduction = ProportionalRateReduction::default();
+	proportional_rate_reduction.start(10 * SenderMaximumSegmentSize, 0);
+	
+	// The first acknowledgment in recovery; data selectively acknowledged beforehand is not counted as delivered by it.
+	assert_eq!(proportional_rate_reduction.delivered_data_with_selective_acknowledgments(0, 3 * SenderMaximumSegmentSize), 0);
+	
+	assert_eq!(proportional_rate_reduction.delivered_data_with_selective_acknowledgments(0, 4 * SenderMaximumSegmentSize), SenderMaximumSegmentSize);
+	
+	// A cumulative acknowledgment of a hole and of data already selectively acknowledged delivers only the hole.
+	assert_eq!(proportional_rate_reduction.delivered_data_with_selective_acknowledgments(3 * SenderMaximumSegmentSize, 2 * SenderMaximumSegmentSize), SenderMaximumSegmentSize);
+}
+
+/// Drives HyStart++ directly, as `RenoCongestionControlAlgorithm` does, with an acknowledgment for each full sized segment.
+struct HybridSlowStartTest
+{
+	hybrid_slow_start: HybridSlowStart,
+	appropriate_byte_counting: AppropriateByteCounting,
+	congestion_control_state_variables: CongestionControlStateVariables,
+}
+
+impl HybridSlowStartTest
+{
+	/// Round trip times in the first round are `100` milliseconds, so a delay increase is detected if they increase to `100 + 100 / MIN_RTT_DIVISOR`, ie `112`, or more.
+	const BaselineRoundTripTime: u64 = 100;
+	
+	const IncreasedRoundTripTime: u64 = Self::BaselineRoundTripTime + Self::BaselineRoundTripTime / 8;
+	
+	fn new() -> Self
+	{
+		let mut this = Self
+		{
+			hybrid_slow_start: HybridSlowStart::default(),
+			appropriate_byte_counting: AppropriateByteCounting::default(),
+			congestion_control_state_variables: CongestionControlStateVariables::new(InitialWindow, ::std::u32::MAX, SenderMaximumSegmentSize),
+		};
+		
+		assert!(!this.end_round());
+		this.round_trip_time_samples(Self::BaselineRoundTripTime);
+		assert!(!this.end_round());
+		
+		this
+	}
+	
+	fn round_trip_time_samples(&mut self, measurement_of_round_trip_time: u64)
+	{
+		for _ in 0 .. HybridSlowStart::NumberOfRoundTripTimeSamples
+		{
+			self.hybrid_slow_start.round_trip_time_sample(MillisecondDuration::from_milliseconds(measurement_of_round_trip_time))
+		}
+	}
+	
+	/// Returns the increase in `cwnd`.
+	fn acknowledge_segment(&mut self) -> u32
+	{
+		let before = self.congestion_control_state_variables.congestion_window();
+		assert!(!self.hybrid_slow_start.slow_start(&mut self.appropriate_byte_counting, &mut self.congestion_control_state_variables, SenderMaximumSegmentSize), "slow start was exited");
+		self.congestion_control_state_variables.congestion_window() - before
+	}
+	
+	/// Acknowledges segments until the next round starts; returns `true` if slow start should be exited.
+	fn end_round(&mut self) -> bool
+	{
+		loop
+		{
+			if self.hybrid_slow_start.slow_start(&mut self.appropriate_byte_counting, &mut self.congestion_control_state_variables, SenderMaximumSegmentSize)
+			{
+				return true
+			}
+			
+			if self.hybrid_slow_start.bytes_acknowledged_in_current_round == 0
+			{
+				return false
+			}
+		}
+	}
+}
+
+#[test]
+fn hybrid_slow_start_enters_conservative_slow_start_when_round_trip_times_increase()
+{
+	let mut test = HybridSlowStartTest::new();
+	
+	test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime - 1);
+	assert_eq!(test.acknowledge_segment(), SenderMaximumSegmentSize, "a delay increase below the threshold entered Conservative Slow Start");
+	assert!(!test.end_round());
+	
+	test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime + HybridSlowStartTest::IncreasedRoundTripTime / 8);
+	
+	// RFC 9406 Section 4.2: "cwnd = cwnd + (min(N, L * SMSS) / CSS_GROWTH_DIVISOR)".
+	assert_eq!(test.acknowledge_segment(), SenderMaximumSegmentSize / HybridSlowStart::ConservativeSlowStartGrowthDivisor);
+}
+
+#[test]
+fn hybrid_slow_start_resumes_slow_start_when_round_trip_times_fall_in_conservative_slow_start()
+{
+	let mut test = HybridSlowStartTest::new();
+	
+	test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime);
+	assert_eq!(test.acknowledge_segment(), SenderMaximumSegmentSize / HybridSlowStart::ConservativeSlowStartGrowthDivisor);
+	assert!(!test.end_round());
+	
+	// The delay increase was spurious.
+	test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime - 1);
+	assert_eq!(test.acknowledge_segment(), SenderMaximumSegmentSize);
+}
+
+#[test]
+fn hybrid_slow_start_exits_slow_start_after_conservative_slow_start_rounds()
+{
+	let mut test = HybridSlowStartTest::new();
+	
+	test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime);
+	for _ in 1 .. HybridSlowStart::ConservativeSlowStartRounds
+	{
+		assert!(!test.end_round(), "slow start was exited before CSS_ROUNDS rounds");
+		test.round_trip_time_samples(HybridSlowStartTest::IncreasedRoundTripTime);
+	}
+	assert!(test.end_round(), "slow start was not exited after CSS_ROUNDS rounds");
+	
+	// RFC 9406 Section 4.3: HyStart++ is only used for the initial slow start.
+	test.round_trip_time_samples(HybridSlowStartTest::BaselineRoundTripTime);
+	assert!(!test.end_round());
+	test.round_trip_time_samples(2 * HybridSlowStartTest::BaselineRoundTripTime);
+	assert_eq!(test.acknowledge_segment(), SenderMaximumSegmentSize);
+}
+
+/// `SRTT` is `100` milliseconds, so pipeACK samples are taken every `100` milliseconds and the pipeACK Sampling Period (PSP) is one second.
+fn congestion_window_validation() -> (CongestionWindowValidation, MonotonicMillisecondTimestamp)
+{
+	let mut congestion_window_validation = CongestionWindowValidation::default();
+	congestion_window_validation.round_trip_time_measured(MillisecondDuration::from_milliseconds(100));
+	(congestion_window_validation, MonotonicMillisecondTimestamp::now())
+}
+
+fn after(start: MonotonicMillisecondTimestamp, milliseconds: u64) -> MonotonicMillisecondTimestamp
+{
+	start + MillisecondDuration::from_milliseconds(milliseconds)
+}
+
+#[test]
+fn congestion_window_validation_enters_the_non_validated_phase_when_less_than_half_of_cwnd_is_used()
+{
+	const congestion_window: u32 = 10 * SenderMaximumSegmentSize;
+	
+	let (mut congestion_window_validation, start) = congestion_window_validation();
+	
+	congestion_window_validation.acknowledged(SenderMaximumSegmentSize, congestion_window, start);
+	assert!(congestion_window_validation.is_validated(congestion_window), "pipeACK is undefined until the first sample has been taken");
+	
+	congestion_window_validation.acknowledged(SenderMaximumSegmentSize, congestion_window, after(start, 100));
+	assert!(!congestion_window_validation.is_validated(congestion_window));
+	assert!(congestion_window_validation.is_validated(4 * SenderMaximumSegmentSize));
+	
+	// The non-validated period (NVP) is six minutes, and starts again once it has expired.
+	assert!(!congestion_window_validation.non_validated_period_expired(after(start, 100 + 360_000)));
+	assert!(congestion_window_validation.non_validated_period_expired(after(start, 100 + 360_001)));
+	assert!(!congestion_window_validation.non_validated_period_expired(after(start, 100 + 360_002)));
+	
+	// RFC 7661: "cwnd = (max(pipeACK,LossFlightSize) - R) / 2".
+	congestion_window_validation.loss_recovery_started(3 * SenderMaximumSegmentSize, congestion_window);
+	congestion_window_validation.retransmitted_during_loss_recovery(SenderMaximumSegmentSize);
+	assert_eq!(congestion_window_validation.loss_recovery_ended(), Some(SenderMaximumSegmentSize));
+	assert!(congestion_window_validation.is_validated(congestion_window), "pipeACK is undefined after congestion has been detected in the non-validated phase");
+}
+
+#[test]
+fn congestion_window_validation_pipe_acknowledged_decays_after_the_sampling_period()
+{
+	const congestion_window: u32 = 10 * SenderMaximumSegmentSize;
+	
+	let (mut congestion_window_validation, start) = congestion_window_validation();
+	
+	congestion_window_validation.acknowledged(8 * SenderMaximumSegmentSize, congestion_window, start);
+	congestion_window_validation.acknowledged(SenderMaximumSegmentSize, congestion_window, after(start, 100));
+	assert!(congestion_window_validation.is_validated(congestion_window));
+	
+	// Loss recovery which starts in the validated phase uses the standard response.
+	congestion_window_validation.loss_recovery_started(congestion_window, congestion_window);
+	assert_eq!(congestion_window_validation.loss_recovery_ended(), None);
+	
+	// The sender becomes rate-limited; the largest sample is kept for the pipeACK Sampling Period.
+	for sample in 1 .. 11
+	{
+		congestion_window_validation.acknowledged(SenderMaximumSegmentSize, congestion_window, after(start, 100 + sample * 100));
+		assert!(congestion_window_validation.is_validated(congestion_window), "pipeACK decayed before the pipeACK Sampling Period");
+	}
+	
+	congestion_window_validation.acknowledged(SenderMaximumSegmentSize, congestion_window, after(start, 100 + 11 * 100));
+	assert_eq!(congestion_window_validation.pipe_acknowledged.maximum(), SenderMaximumSegmentSize as u64);
+	assert!(!congestion_window_validation.is_validated(congestion_window));
+}
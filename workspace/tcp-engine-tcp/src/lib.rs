@@ -50,6 +50,7 @@ use ::std::cell::Cell;
 use ::std::cell::UnsafeCell;
 use ::std::cmp::max;
 use ::std::cmp::min;
+use ::std::fmt::Debug;
 #[allow(unused_imports)] use ::std::hash::Hasher;
 use ::std::mem::size_of;
 use ::std::mem::transmute;
@@ -60,5 +61,6 @@ use ::tcp_engine_internet_protocol::*;
 #[allow(unused_imports)] use ::tcp_engine_network_endian::NetworkEndian;
 use ::tcp_engine_ports::*;
 use ::tcp_engine_tcp_domain::*;
+use ::tcp_engine_tcp_domain::retransmission::DeliveryRateSample;
 use ::tcp_engine_tcp_domain::tcp_options::*;
 use ::tcp_engine_time::*;
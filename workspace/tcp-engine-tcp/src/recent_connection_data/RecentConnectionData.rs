@@ -14,6 +14,11 @@ pub struct RecentConnectionData
 	
 	/// RFC 5681: Section 3.1: "... the slow start threshold (ssthresh), is used to determine whether the slow start or congestion avoidance algorithm is used to control data transmission ..."
 	ssthresh: u32,
+	
+	/// Generalized ECN: A packet sent to this remote with an ECN-capable transport (ECT) code point was lost or had its code point mangled, so packets to it are sent not-ECT.
+	///
+	/// Once set, it is only cleared when this data expires, after which ECT is tried again.
+	explicit_congestion_notification_fall_back: bool,
 }
 
 impl Default for RecentConnectionData
@@ -35,17 +40,20 @@ impl RecentConnectionData
 		
 		// RFC 5681: Section 3.1: "The initial value of ssthresh SHOULD be set arbitrarily high (e.g., to the size of the largest possible advertised window)".
 		ssthresh: WindowSize::Maximum.value(),
+		
+		explicit_congestion_notification_fall_back: false,
 	};
 	
 	/// Creates a new instance.
 	#[inline(always)]
-	pub fn new(&self, smoothed_round_trip_time: MillisecondDuration, round_trip_time_variance: MillisecondDuration, ssthresh: u32) -> RecentConnectionData
+	pub fn new(&self, smoothed_round_trip_time: MillisecondDuration, round_trip_time_variance: MillisecondDuration, ssthresh: u32, explicit_congestion_notification_fall_back: bool) -> RecentConnectionData
 	{
 		Self
 		{
 			smoothed_round_trip_time,
 			round_trip_time_variance,
 			ssthresh,
+			explicit_congestion_notification_fall_back,
 		}
 	}
 	
@@ -63,11 +71,25 @@ impl RecentConnectionData
 		max(2 * sender_maximum_segment_size, self.ssthresh)
 	}
 	
+	/// Generalized ECN: Should packets to this remote be sent not-ECT, because a packet sent with an ECN-capable transport (ECT) code point was recently lost or had its code point mangled?
+	#[inline(always)]
+	pub fn explicit_congestion_notification_fall_back(&self) -> bool
+	{
+		self.explicit_congestion_notification_fall_back
+	}
+	
+	#[inline(always)]
+	pub(crate) fn explicit_congestion_notification_fell_back(&mut self)
+	{
+		self.explicit_congestion_notification_fall_back = true
+	}
+	
 	#[inline(always)]
 	pub(crate) fn update(&mut self, recent_connection_data_from_transmission_control_block: Self)
 	{
 		self.update_retransmission_time_out(recent_connection_data_from_transmission_control_block.smoothed_round_trip_time, recent_connection_data_from_transmission_control_block.round_trip_time_variance);
-		self.update_ssthresh(recent_connection_data_from_transmission_control_block.ssthresh)
+		self.update_ssthresh(recent_connection_data_from_transmission_control_block.ssthresh);
+		self.explicit_congestion_notification_fall_back |= recent_connection_data_from_transmission_control_block.explicit_congestion_notification_fall_back
 	}
 	
 	#[inline(always)]
@@ -55,6 +55,24 @@ impl<Address: InternetProtocolAddress> RecentConnectionDataCache<Address>
 		}
 	}
 	
+	/// Generalized ECN: Records that a connection to `remote_internet_protocol_address` has fallen back to sending not-ECT.
+	///
+	/// This is recorded immediately, rather than when the connection is destroyed, so that connections made in the meantime do likewise.
+	#[inline(always)]
+	pub fn explicit_congestion_notification_fell_back(&self, now: MonotonicMillisecondTimestamp, remote_internet_protocol_address: &Address)
+	{
+		if let Some(cached_connection_data) = self.cache().get_mut(now, remote_internet_protocol_address)
+		{
+			cached_connection_data.explicit_congestion_notification_fell_back();
+		}
+		else
+		{
+			let mut recent_connection_data = RecentConnectionData::Default;
+			recent_connection_data.explicit_congestion_notification_fell_back();
+			self.cache().insert(now, *remote_internet_protocol_address, recent_connection_data);
+		}
+	}
+	
 	#[inline(always)]
 	fn cache(&self) -> &mut LeastRecentlyUsedCacheWithExpiry<Address, RecentConnectionData>
 	{
@@ -46,10 +46,22 @@ pub trait SendPacketTransmissionControlBlock<Address: InternetProtocolAddress>:
 	#[inline(always)]
 	fn RCV_NXT(&self) -> WrappingSequenceNumber;
 	
+	/// The maximum payload that can be placed in a segment, given the size of its (padded) options, the congestion window and the send window.
+	#[inline(always)]
+	fn maximum_payload_size_excluding_synchronize_and_finish(&mut self, now: MonotonicMillisecondTimestamp, padded_options_size: usize) -> u32;
+	
 	/// `SEG.WND` for an outgoing segment.
 	#[inline(always)]
 	fn receive_segment_window_size(&self) -> SegmentWindowSize;
 	
+	/// A SACK option of at most `maximum_number_of_blocks` blocks describing out-of-order data held, and any duplicate segment received (D-SACK), for an outgoing acknowledgment.
+	///
+	/// Any duplicate segment is reported only once.
+	///
+	/// `None` if selective acknowledgments were not negotiated or there is neither a duplicate segment to report nor out-of-order data.
+	#[inline(always)]
+	fn selective_acknowledgment_option(&mut self, maximum_number_of_blocks: usize) -> Option<SelectiveAcknowledgmentOption>;
+	
 	/// Add to the retransmission queue.
 	#[inline(always)]
 	fn transmitted(&mut self, now: MonotonicMillisecondTimestamp, starts_at: WrappingSequenceNumber, data_length_excluding_length_of_synchronize_and_finish_controls: u32, flags: Flags);
@@ -14,6 +14,13 @@ pub struct Interface<TCBA: TransmissionControlBlockAbstractions>
 	syn_cookie_protection: SynCookieProtection,
 	alarms: Alarms<TCBA>,
 	authentication_pre_shared_secret_keys: AuthenticationPreSharedSecretKeys,
+	time_wait_assassination_protection: Cell<bool>,
+	recent_acknowledgment_loss_detection: Cell<bool>,
+	pacing: Cell<bool>,
+	congestion_window_validation: Cell<bool>,
+	accurate_explicit_congestion_notification: Cell<bool>,
+	generalized_explicit_congestion_notification: Cell<bool>,
+	listening_server_congestion_control_algorithms: RefCell<ListeningServerCongestionControlAlgorithms>,
 }
 
 /// Public API.
@@ -23,7 +30,7 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 	///
 	/// Below calling this, it is important that the `libnuma` method `numa_set_localalloc()` has been called, so that allocation is local to the allocating CPU.
 	#[inline(always)]
-	pub fn new(transmission_control_block_abstractions: TCBA, path_maximum_transmission_unit_table: TCBA::PMTUTable, check_sum_layering: CheckSumLayering, listening_server_port_combination_validity: PortCombinationValidity, local_internet_protocol_address: TCBA::Address, transmission_control_blocks_map_capacity: usize, maximum_recent_connections_capacity: usize, authentication_pre_shared_secret_keys: AuthenticationPreSharedSecretKeys) -> Self
+	pub fn new(transmission_control_block_abstractions: TCBA, path_maximum_transmission_unit_table: TCBA::PMTUTable, check_sum_layering: CheckSumLayering, listening_server_port_combination_validity: PortCombinationValidity, local_internet_protocol_address: TCBA::Address, transmission_control_blocks_map_capacity: usize, maximum_recent_connections_capacity: usize, reassembly_buffers_capacity: usize, authentication_pre_shared_secret_keys: AuthenticationPreSharedSecretKeys) -> Self
 	{
 		const SendBufferSize: usize = 256 * 1024;
 		
@@ -39,13 +46,100 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 			},
 			listening_server_port_combination_validity,
 			local_internet_protocol_address,
-			transmission_control_blocks: TransmissionControlBlocks::new(transmission_control_blocks_map_capacity, maximum_recent_connections_capacity),
+			transmission_control_blocks: TransmissionControlBlocks::new(transmission_control_blocks_map_capacity, maximum_recent_connections_capacity, reassembly_buffers_capacity),
 			syn_cookie_protection: SynCookieProtection::new(now),
 			alarms: Alarms::new(now),
 			authentication_pre_shared_secret_keys,
+			time_wait_assassination_protection: Cell::new(true),
+			recent_acknowledgment_loss_detection: Cell::new(true),
+			pacing: Cell::new(false),
+			congestion_window_validation: Cell::new(false),
+			accurate_explicit_congestion_notification: Cell::new(true),
+			generalized_explicit_congestion_notification: Cell::new(false),
+			listening_server_congestion_control_algorithms: RefCell::new(ListeningServerCongestionControlAlgorithms::default()),
 		}
 	}
 	
+	/// RFC 1337 TIME-WAIT assassination protection (fix (F1), "Ignore RST segments in TIME-WAIT state").
+	///
+	/// Enabled by default; when disabled, a Reset received in the TimeWait state closes the connection as described in RFC 793 Page 70.
+	///
+	/// This is not without controversy and is disabled by default in Linux (see <https://serverfault.com/questions/787624/why-isnt-net-ipv4-tcp-rfc1337-enabled-by-default>).
+	#[inline(always)]
+	pub fn set_time_wait_assassination_protection(&self, enabled: bool)
+	{
+		self.time_wait_assassination_protection.set(enabled)
+	}
+	
+	/// RACK-TLP (RFC 8985): Losses are detected using the times at which segments were transmitted, and a tail loss probe is sent if the acknowledgments for the last segments of a flight do not arrive, rather than waiting for the retransmission timer to expire.
+	///
+	/// Enabled by default; when disabled, losses are detected by counting duplicate acknowledgments (RFC 6675).
+	///
+	/// Only used for connections which permit selective acknowledgments; it applies to connections established after it is changed.
+	#[inline(always)]
+	pub fn set_recent_acknowledgment_loss_detection(&self, enabled: bool)
+	{
+		self.recent_acknowledgment_loss_detection.set(enabled)
+	}
+	
+	/// Pacing of outgoing segments at the rate set by the congestion control algorithm, or, if it does not set one, at a rate derived from `cwnd / SRTT`.
+	///
+	/// Disabled by default; when disabled, as much buffered data as the congestion and send windows permit is transmitted back-to-back.
+	///
+	/// Pacing is to a precision of one alarm tick (128 milliseconds): once transmission is held back, at most a small burst is transmitted per tick.
+	/// It is therefore only of use for connections whose round trip times are much longer than a tick; for shorter round trip times it limits throughput rather than smoothing it.
+	#[inline(always)]
+	pub fn set_pacing(&self, enabled: bool)
+	{
+		self.pacing.set(enabled)
+	}
+	
+	/// Congestion Window Validation for rate-limited senders (RFC 7661, 'new-CWV'): `cwnd` is not increased whilst less than half of it is being used, is preserved, rather than being reduced to the restart window, after an idle period, and is reduced after a loss to reflect the data actually in flight.
+	///
+	/// Disabled by default, as RFC 7661 is experimental; enabling it suits connections, such as those used for streaming, which are limited by the rate at which the application supplies data most of the time.
+	///
+	/// It applies to connections established after it is changed.
+	#[inline(always)]
+	pub fn set_congestion_window_validation(&self, enabled: bool)
+	{
+		self.congestion_window_validation.set(enabled)
+	}
+	
+	/// Accurate ECN (RFC 9768, 'AccECN'): Outbound connections which request explicit congestion notification request Accurate ECN, and listening server ports agree to it if requested, falling back to classic ECN (RFC 3168) if the remote does not support it.
+	///
+	/// With Accurate ECN, the remote feeds back how many packets and bytes were CE marked rather than just that congestion occurred, as is needed by DCTCP (`DctcpCongestionControlAlgorithm`).
+	///
+	/// Enabled by default; it applies to connections established after it is changed.
+	#[inline(always)]
+	pub fn set_accurate_explicit_congestion_notification(&self, enabled: bool)
+	{
+		self.accurate_explicit_congestion_notification.set(enabled)
+	}
+	
+	/// Generalized ECN ('ECN++'): Connections which negotiate explicit congestion notification set an ECN-capable transport (ECT) code point on SynchronizeAcknowledgments (RFC 5562), retransmissions, zero window probes and `FIN`s, and, if Accurate ECN is negotiated, on Synchronizes and pure acknowledgments, rather than only on new data packets as RFC 3168 requires.
+	///
+	/// If a Synchronize sent ECN-capable is lost, or the remote's Accurate ECN feedback shows that the code point was changed on the path, packets to that remote are sent not-ECT; this is remembered in the recent connection data for the remote.
+	///
+	/// Disabled by default, as RFC 8311 only permits this as an experiment; it applies to connections established after it is changed.
+	#[inline(always)]
+	pub fn set_generalized_explicit_congestion_notification(&self, enabled: bool)
+	{
+		self.generalized_explicit_congestion_notification.set(enabled)
+	}
+	
+	/// Chooses the congestion control algorithm for connections accepted on the listening server port `local_port`.
+	///
+	/// Connections accepted on a port for which no algorithm has been chosen use Reno (`RenoCongestionControlAlgorithm`).
+	///
+	/// For a port only used for traffic within a data centre, DCTCP (`DctcpCongestionControlAlgorithm::boxed`) can be chosen; it requires explicit congestion notification.
+	///
+	/// At most eight (8) distinct algorithms, including Reno, can be chosen for an interface; returns an error if choosing `congestion_control_algorithm_constructor` would exceed this.
+	#[inline(always)]
+	pub fn set_listening_server_congestion_control_algorithm(&self, local_port: NetworkEndianU16, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor) -> Result<(), ()>
+	{
+		self.listening_server_congestion_control_algorithms.borrow_mut().set(local_port, congestion_control_algorithm_constructor)
+	}
+	
 	/// Progresses alarms and returns a monotonic millisecond timestamp that can be used as an input to `incoming_segment()`.
 	#[inline(always)]
 	pub fn progress_alarms(&self) -> MonotonicMillisecondTimestamp
@@ -53,9 +147,10 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 		self.alarms.progress(self)
 	}
 	
+	/// `congestion_control_algorithm_constructor` chooses the congestion control algorithm for this connection, eg `RenoCongestionControlAlgorithm::boxed`.
 	// TODO: If there are multiple Interface 'clones', one per RSS thread, then we will need to apply the RSS hash algorithm to correctly choose which Interface to create an outbound connection on.
 	#[inline(always)]
-	pub fn new_outbound_connection(&self, remote_internet_protocol_address: TCBA::Address, remote_port: NetworkEndianU16, now: MonotonicMillisecondTimestamp, explicit_congestion_notification_supported: bool, connection_time_out: MillisecondDuration) -> Result<(), ()>
+	pub fn new_outbound_connection(&self, remote_internet_protocol_address: TCBA::Address, remote_port: NetworkEndianU16, now: MonotonicMillisecondTimestamp, explicit_congestion_notification_supported: bool, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor, connection_time_out: MillisecondDuration) -> Result<(), ()>
 	{
 		if self.transmission_control_blocks.at_maximum_capacity()
 		{
@@ -64,13 +159,36 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 		
 		let (packet, our_tcp_segment) = self.create_for_tcp_segment(&remote_internet_protocol_address)?;
 		
-		let transmission_control_block = self.transmission_control_blocks.new_transmission_control_block_for_outgoing_client_connection(remote_internet_protocol_address, remote_port, now, explicit_congestion_notification_supported, connection_time_out, &self.listening_server_port_combination_validity, &self.authentication_pre_shared_secret_keys, &self.maximum_segment_size_table, &self.local_internet_protocol_address)?;
+		let transmission_control_block = self.transmission_control_blocks.new_transmission_control_block_for_outgoing_client_connection(remote_internet_protocol_address, remote_port, now, explicit_congestion_notification_supported, congestion_control_algorithm_constructor, connection_time_out, &self.listening_server_port_combination_validity, &self.authentication_pre_shared_secret_keys, &self.maximum_segment_size_table, &self.local_internet_protocol_address)?;
+		
+		if explicit_congestion_notification_supported && self.accurate_explicit_congestion_notification()
+		{
+			transmission_control_block.use_accurate_explicit_congestion_notification(AccurateExplicitCongestionNotificationState::for_client());
+		}
+		
+		if self.generalized_explicit_congestion_notification()
+		{
+			transmission_control_block.use_generalized_explicit_congestion_notification();
+		}
 		
 		self.send_synchronize(packet, our_tcp_segment, transmission_control_block, now);
 		
 		Ok(())
 	}
 	
+	/// Half-closes a connection (also known as 'shutdown write'): a `FIN` is sent once all previously buffered data has been transmitted, but data continues to be received until the remote sends its `FIN`.
+	///
+	/// Returns an error if there is no such connection, if it is not yet established or if it is already closing.
+	#[inline(always)]
+	pub fn half_close(&self, key: &TransmissionControlBlockKey<TCBA::Address>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
+	{
+		match self.transmission_control_blocks.find_transmission_control_block(key)
+		{
+			None => Err(()),
+			Some(transmission_control_block) => transmission_control_block.half_close(self, now),
+		}
+	}
+	
 	/// NOTE: RFC 2675 IPv6 jumbograms are not supported.
 	///
 	/// This logic DOES NOT validate:-
@@ -102,7 +220,101 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 	#[inline(always)]
 	pub(crate) fn new_transmission_control_block_for_incoming_segment(&self, source_internet_protocol_address: &TCBA::Address, SEG: &ParsedTcpSegment<TCBA>, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie, now: MonotonicMillisecondTimestamp, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>) -> &mut TransmissionControlBlock<TCBA>
 	{
-		self.transmission_control_blocks.new_transmission_control_block_for_incoming_segment(source_internet_protocol_address, SEG.SEG, SEG.WND, tcp_options, parsed_syncookie, now, md5_authentication_key, &self.maximum_segment_size_table)
+		let congestion_control_algorithm_constructor = self.listening_server_congestion_control_algorithm(SEG.remote_port_local_port().local_port());
+		
+		// RFC 9768: A client which negotiated Accurate ECN never sends an ACE field of zero on the acknowledgment which completes the three-way handshake, whereas one using classic ECN (RFC 3168) does.
+		let accurate_explicit_congestion_echo_field = SEG.SEG.accurate_explicit_congestion_echo_field();
+		let accurate_explicit_congestion_notification_negotiated = parsed_syncookie.explicit_congestion_notification_supported && self.accurate_explicit_congestion_notification() && !accurate_explicit_congestion_echo_field.is_zero();
+		
+		let synchronize_acknowledgment_was_capable_transport = self.synchronize_acknowledgment_is_capable_transport(parsed_syncookie.explicit_congestion_notification_supported, source_internet_protocol_address, now);
+		
+		let transmission_control_block = self.transmission_control_blocks.new_transmission_control_block_for_incoming_segment(source_internet_protocol_address, SEG.SEG, SEG.WND, tcp_options, parsed_syncookie, now, md5_authentication_key, congestion_control_algorithm_constructor, &self.maximum_segment_size_table);
+		
+		if self.recent_acknowledgment_loss_detection()
+		{
+			transmission_control_block.use_recent_acknowledgment_loss_detection_if_selective_acknowledgments_are_permitted();
+		}
+		
+		if self.congestion_window_validation()
+		{
+			transmission_control_block.use_congestion_window_validation();
+		}
+		
+		if self.generalized_explicit_congestion_notification()
+		{
+			transmission_control_block.use_generalized_explicit_congestion_notification();
+		}
+		
+		if accurate_explicit_congestion_notification_negotiated
+		{
+			transmission_control_block.use_accurate_explicit_congestion_notification(AccurateExplicitCongestionNotificationState::for_server());
+			
+			// The ACE field of the acknowledgment which completes the three-way handshake reflects the IP ECN field of our SynchronizeAcknowledgment.
+			let explicit_congestion_notification_sent = if synchronize_acknowledgment_was_capable_transport
+			{
+				ExplicitCongestionNotification::CapableTransportEctZero
+			}
+			else
+			{
+				ExplicitCongestionNotification::NotCapableTransport
+			};
+			if accurate_explicit_congestion_echo_field.handshake_reflection_is_mangled(explicit_congestion_notification_sent)
+			{
+				self.fall_back_to_not_capable_transport(transmission_control_block, now);
+			}
+		}
+		
+		if synchronize_acknowledgment_was_capable_transport
+		{
+			// RFC 5562: A client which receives a CE marked SynchronizeAcknowledgment says so (by the ECE flag, or, with Accurate ECN, in the ACE field) and the server responds as for any other congestion indication.
+			let synchronize_acknowledgment_was_congestion_experienced = if accurate_explicit_congestion_notification_negotiated
+			{
+				accurate_explicit_congestion_echo_field.handshake_reflection_is_congestion_experienced()
+			}
+			else
+			{
+				SEG.SEG.all_flags().contains(Flags::ExplicitCongestionEcho)
+			};
+			
+			if synchronize_acknowledgment_was_congestion_experienced
+			{
+				if let Some(explicit_congestion_notification_state) = transmission_control_block.explicit_congestion_notification_state_mutable_reference()
+				{
+					explicit_congestion_notification_state.incoming_data_packet_had_explicit_congestion_echo_flag_set();
+				}
+				transmission_control_block.congestion_control_mutable_reference().explicit_congestion_echo_received(now);
+			}
+		}
+		
+		transmission_control_block
+	}
+	
+	/// Generalized ECN (RFC 5562): Is a SynchronizeAcknowledgment to `remote_internet_protocol_address` which agrees to explicit congestion notification sent with the ECT(0) code point?
+	#[inline(always)]
+	pub(crate) fn synchronize_acknowledgment_is_capable_transport(&self, explicit_congestion_notification_supported: bool, remote_internet_protocol_address: &TCBA::Address, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		explicit_congestion_notification_supported && self.generalized_explicit_congestion_notification() && !self.transmission_control_blocks.explicit_congestion_notification_fall_back(now, remote_internet_protocol_address)
+	}
+	
+	/// Generalized ECN: `transmission_control_block` has found that the path to its remote drops or mangles ECN-capable packets; it, and later connections to the same remote, send all packets not-ECT.
+	#[inline(always)]
+	pub(crate) fn fall_back_to_not_capable_transport(&self, transmission_control_block: &mut TransmissionControlBlock<TCBA>, now: MonotonicMillisecondTimestamp)
+	{
+		transmission_control_block.fall_back_to_not_capable_transport();
+		self.explicit_congestion_notification_fell_back(transmission_control_block.remote_internet_protocol_address(), now)
+	}
+	
+	/// Generalized ECN: A connection to `remote_internet_protocol_address` has fallen back to sending not-ECT; this is recorded in recent connection data immediately.
+	#[inline(always)]
+	pub(crate) fn explicit_congestion_notification_fell_back(&self, remote_internet_protocol_address: &TCBA::Address, now: MonotonicMillisecondTimestamp)
+	{
+		self.transmission_control_blocks.explicit_congestion_notification_fell_back(now, remote_internet_protocol_address)
+	}
+	
+	#[inline(always)]
+	fn listening_server_congestion_control_algorithm(&self, local_port: NetworkEndianU16) -> CongestionControlAlgorithmConstructor
+	{
+		self.listening_server_congestion_control_algorithms.borrow().get(local_port)
 	}
 	
 	#[inline(always)]
@@ -137,6 +349,42 @@ impl<TCBA: TransmissionControlBlockAbstractions> Interface<TCBA>
 	{
 		self.alarms.maximum_zero_window_probe_time_exceeded(time_that_has_elapsed_since_send_window_last_updated)
 	}
+	
+	#[inline(always)]
+	pub(crate) fn time_wait_assassination_protection(&self) -> bool
+	{
+		self.time_wait_assassination_protection.get()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn recent_acknowledgment_loss_detection(&self) -> bool
+	{
+		self.recent_acknowledgment_loss_detection.get()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn pacing(&self) -> bool
+	{
+		self.pacing.get()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn congestion_window_validation(&self) -> bool
+	{
+		self.congestion_window_validation.get()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn accurate_explicit_congestion_notification(&self) -> bool
+	{
+		self.accurate_explicit_congestion_notification.get()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn generalized_explicit_congestion_notification(&self) -> bool
+	{
+		self.generalized_explicit_congestion_notification.get()
+	}
 }
 
 /// Authentication.
@@ -0,0 +1,73 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// The congestion control algorithm chosen for each listening server port.
+///
+/// Memory is fixed: each port holds an index into a small table of the distinct algorithms chosen, so choosing an algorithm for a port never allocates.
+pub(crate) struct ListeningServerCongestionControlAlgorithms
+{
+	congestion_control_algorithm_constructors: [CongestionControlAlgorithmConstructor; ListeningServerCongestionControlAlgorithms::MaximumNumberOfCongestionControlAlgorithms],
+	number_of_congestion_control_algorithms: usize,
+	congestion_control_algorithm_index_by_local_port: Box<[u8; ListeningServerCongestionControlAlgorithms::NumberOfPorts]>,
+}
+
+impl Default for ListeningServerCongestionControlAlgorithms
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			congestion_control_algorithm_constructors: [Self::DefaultCongestionControlAlgorithm; Self::MaximumNumberOfCongestionControlAlgorithms],
+			number_of_congestion_control_algorithms: 1,
+			congestion_control_algorithm_index_by_local_port: Box::new([Self::DefaultCongestionControlAlgorithmIndex; Self::NumberOfPorts]),
+		}
+	}
+}
+
+impl ListeningServerCongestionControlAlgorithms
+{
+	/// The maximum number of distinct congestion control algorithms which can be chosen, including the default, Reno.
+	pub(crate) const MaximumNumberOfCongestionControlAlgorithms: usize = 8;
+	
+	const NumberOfPorts: usize = 65_536;
+	
+	const DefaultCongestionControlAlgorithm: CongestionControlAlgorithmConstructor = RenoCongestionControlAlgorithm::boxed;
+	
+	const DefaultCongestionControlAlgorithmIndex: u8 = 0;
+	
+	/// Returns an error if `MaximumNumberOfCongestionControlAlgorithms` distinct algorithms have already been chosen and `congestion_control_algorithm_constructor` is not one of them.
+	#[inline(always)]
+	pub(crate) fn set(&mut self, local_port: NetworkEndianU16, congestion_control_algorithm_constructor: CongestionControlAlgorithmConstructor) -> Result<(), ()>
+	{
+		let number_of_congestion_control_algorithms = self.number_of_congestion_control_algorithms;
+		
+		let index = match self.congestion_control_algorithm_constructors[.. number_of_congestion_control_algorithms].iter().position(|existing| *existing == congestion_control_algorithm_constructor)
+		{
+			Some(index) => index,
+			
+			None =>
+			{
+				if unlikely!(number_of_congestion_control_algorithms == Self::MaximumNumberOfCongestionControlAlgorithms)
+				{
+					return Err(())
+				}
+				
+				self.congestion_control_algorithm_constructors[number_of_congestion_control_algorithms] = congestion_control_algorithm_constructor;
+				self.number_of_congestion_control_algorithms += 1;
+				number_of_congestion_control_algorithms
+			}
+		};
+		
+		self.congestion_control_algorithm_index_by_local_port[local_port.to_native_endian() as usize] = index as u8;
+		Ok(())
+	}
+	
+	#[inline(always)]
+	pub(crate) fn get(&self, local_port: NetworkEndianU16) -> CongestionControlAlgorithmConstructor
+	{
+		let index = self.congestion_control_algorithm_index_by_local_port[local_port.to_native_endian() as usize];
+		self.congestion_control_algorithm_constructors[index as usize]
+	}
+}
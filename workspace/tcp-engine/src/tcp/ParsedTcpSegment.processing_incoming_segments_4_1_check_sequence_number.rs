@@ -9,6 +9,12 @@ macro_rules! check_sequence_number_segment_is_unacceptable
 		{
 			if $self.reset_flag_unset()
 			{
+				// RFC 2883 Section 4: Duplicate segments are reported using a D-SACK block, but only if the use of SACK was negotiated.
+				if $transmission_control_block.selective_acknowledgments_permitted
+				{
+					$transmission_control_block.RCV.duplicate_segment_received($self);
+				}
+				
 				$self.interface.send_acknowledgment($self.reuse_packet(), $transmission_control_block, $self.now, Flags::Acknowledgment, $transmission_control_block.SND.NXT(), $transmission_control_block.RCV.NXT());
 			}
 			invalid!($self, $reason)
@@ -41,6 +47,13 @@ macro_rules! check_sequence_number_4_1_1_2_r2
 	{
 		if !$self.processing_incoming_segments_4_1_3_r2_segment_is_acceptable_because_it_occupies_a_portion_of_valid_receive_sequence_space($transmission_control_block)
 		{
+			// RFC 793 Page 73: In TIME-WAIT, "The only thing that can arrive in this state is a retransmission of the remote FIN. Acknowledge it, and restart the 2 MSL timeout".
+			// Such a retransmission ends at RCV.NXT and so is not acceptable; it is recognised here, after authentication and the timestamps check (RFC 7323 Section 5.3 Point R1), and is acknowledged below.
+			if $transmission_control_block.state() == State::TimeWait && $self.is_a_retransmission_of_the_remote_finish($transmission_control_block)
+			{
+				$transmission_control_block.restart_time_wait_alarm($self.interface.alarms());
+			}
+			
 			check_sequence_number_segment_is_unacceptable!($self, $transmission_control_block, "TCP segment was not acceptable as it did not occupy a portion of the valid receive sequence space")
 		}
 	}
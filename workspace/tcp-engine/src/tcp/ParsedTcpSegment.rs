@@ -47,24 +47,7 @@ impl<TCBA: TransmissionControlBlockAbstractions, I: NetworkDeviceInterface<TCBA>
 	#[inline(always)]
 	fn received_synchronize_when_state_is_listen_or_synchronize_received(&mut self, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, explicit_congestion_notification_supported: bool)
 	{
-		if self.has_data()
-		{
-			invalid!(self, "TCP Synchronize packets are not supported with payloads as we use syncookies");
-		}
-		
-		validate_authentication!(self);
-		
-		if unlikely!(self.SEG.ACK() != WrappingSequenceNumber::Zero)
-		{
-			invalid!(self, "TCP Synchronize packets should have an initial ACK of zero (0)");
-		}
-		
-		let maximum_segment_size = self.tcp_options.maximum_segment_size;
-		let window_scale = self.tcp_options.window_scale;
-		let selective_acknowledgment_permitted = self.tcp_options.selective_acknowledgment_permitted;
-		let timestamps = self.tcp_options.timestamps;
-		
-		self.interface.send_synchronize_acknowledgment(self.now, self.reuse_packet(), self.source_internet_protocol_address, self, maximum_segment_size, window_scale, selective_acknowledgment_permitted, timestamps, explicit_congestion_notification_supported, md5_authentication_key);
+		self.send_synchronize_acknowledgment_with_syncookie(md5_authentication_key, explicit_congestion_notification_supported)
 	}
 	
 	#[inline(always)]
@@ -160,6 +143,47 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 				invalid!(SEG, "TCP ResetAcknowledgment in violation of RFC 5961, possible RST attack")
 			},
 			
+			// Processing Incoming Segments 3.1.1, 3.4.1
+			// and
+			// RFC 9768: A server which supports Accurate ECN replies to an AccECN setup Synchronize with a SynchronizeAcknowledgment whose ACE field reflects the IP ECN field of the Synchronize; any ACE field other than 0b000 (no ECN) or 0b001 (classic ECN) negotiates Accurate ECN.
+			Flags::SynchronizeAcknowledgment | Flags::SynchronizeAcknowledgmentCongestionWindowReduced | Flags::SynchronizeAcknowledgmentExplicitCongestionEcho | Flags::SynchronizeAcknowledgmentExplicitCongestionEchoCongestionWindowReduced if transmission_control_block.accurate_explicit_congestion_notification_supported() =>
+			{
+				let accurate_explicit_congestion_echo_field = self.SEG.accurate_explicit_congestion_echo_field();
+				
+				if accurate_explicit_congestion_echo_field.is_zero()
+				{
+					transmission_control_block.disable_explicit_congestion_notification();
+				}
+				else if accurate_explicit_congestion_echo_field == AccurateExplicitCongestionEchoField::ClassicExplicitCongestionNotificationSynchronizeAcknowledgment
+				{
+					transmission_control_block.disable_accurate_explicit_congestion_notification();
+				}
+				else
+				{
+					// Generalized ECN: The ACE field reflects the IP ECN field of our Synchronize as it arrived at the server.
+					let explicit_congestion_notification_sent = if transmission_control_block.may_set_capable_transport_on_synchronize()
+					{
+						ExplicitCongestionNotification::CapableTransportEctZero
+					}
+					else
+					{
+						ExplicitCongestionNotification::NotCapableTransport
+					};
+					if accurate_explicit_congestion_echo_field.handshake_reflection_is_mangled(explicit_congestion_notification_sent)
+					{
+						self.interface.fall_back_to_not_capable_transport(transmission_control_block, self.now);
+					}
+					
+					let explicit_congestion_notification = self.packet.explicit_congestion_notification();
+					if let Some(accurate_explicit_congestion_notification_state) = transmission_control_block.accurate_explicit_congestion_notification_state_mutable_reference()
+					{
+						accurate_explicit_congestion_notification_state.synchronize_acknowledgment_received(explicit_congestion_notification)
+					}
+				}
+				
+				self.synchronize_sent_received_acknowledgment(transmission_control_block)
+			}
+			
 			// Processing Incoming Segments 3.1.1, 3.4.1
 			Flags::SynchronizeAcknowledgment =>
 			{
@@ -189,6 +213,15 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 					}
 				}
 				
+				// RFC 5562: If an ECN-capable SynchronizeAcknowledgment was CE marked, the acknowledgment completing the three-way handshake has the ECE flag set, so that the server reduces its initial congestion window.
+				if self.packet.explicit_congestion_notification().congestion_encountered()
+				{
+					if let Some(explicit_congestion_notification_state) = transmission_control_block.explicit_congestion_notification_state_mutable_reference()
+					{
+						explicit_congestion_notification_state.congestion_was_encountered()
+					}
+				}
+				
 				self.synchronize_sent_received_acknowledgment(transmission_control_block)
 			}
 			
@@ -205,17 +238,7 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		let SND = &mut transmission_control_block.SND;
 		let MAX = &mut transmission_control_block.MAX;
 		
-		// In the following it is assumed that the segment is the idealized segment that begins at RCV.NXT and does not exceed the window.
-		// Segments with higher begining sequence numbers SHOULD be held for later processing.
-		// TODO: If the segment lies to the right of the end of the receive window, we need to hold on to it in an inbound (received out-of-order) queue.
-		// Likewise, currently, we send an ACK for an unacceptable incoming segment; we could queue.
-		// This area needs some finesse.
-		
-			// We can process this queue at the end of processing this tcp segment. We just need to know from where to start...
-			// We may be able to part-process it, eg to send an ACK now.
-		
-		
-		
+		// Segments with higher begining sequence numbers than RCV.NXT are held for later processing in the reassembly queue; see Processing Incoming Segments 4.7.1.
 		
 		let timestamps_option = processing_incoming_segments_4_1_check_sequence_number!(self, transmission_control_block);
 		
@@ -236,9 +259,12 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
-		self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true);
+		if self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true)
+		{
+			return
+		}
 		
-		self.processing_incoming_segments_4_8_2_1_transition_to_close_wait_if_finish_flag_set();
+		self.processing_incoming_segments_4_8_2_1_transition_to_close_wait_if_finish_flag_set(transmission_control_block);
 	}
 	
 	#[inline(always)]
@@ -293,8 +319,17 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		
 		rfc_5961_5_2_acknowledgment_is_acceptable!(self, transmission_control_block);
 		
-		// ACK ON
-		// TODO: The only thing that can arrive in this state is an acknowledgment of our FIN. If our FIN is now acknowledged, delete the TCB, enter the CLOSED state, and return.
+		if unlikely!(self.processing_incoming_segments_4_5_2_2_established_and_similar_for_other_states_process_acknowledgment(transmission_control_block))
+		{
+			return;
+		}
+		
+		// RFC 793 Page 73: "The only thing that can arrive in this state is an acknowledgment of our FIN. If our FIN is now acknowledged, delete the TCB, enter the CLOSED state, and return".
+		if transmission_control_block.SND.finish_has_been_acknowledged()
+		{
+			transmission_control_block.closed(self.interface, self.now);
+			return
+		}
 		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
@@ -327,13 +362,21 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		{
 			return;
 		}
-		// TODO: In addition to the processing for the ESTABLISHED state, if our FIN is now acknowledged then enter FIN-WAIT-2 and continue processing in that state.
+		
+		// RFC 793 Page 73: "In addition to the processing for the ESTABLISHED state, if our FIN is now acknowledged then enter FIN-WAIT-2 and continue processing in that state".
+		if transmission_control_block.SND.finish_has_been_acknowledged()
+		{
+			transmission_control_block.set_state(State::FinishWait2);
+		}
 		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
-		self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true);
+		if self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true)
+		{
+			return
+		}
 		
-		self.processing_incoming_segments_4_8_2_2_transition_to_time_wait_or_closing_if_finish_flag_set();
+		self.processing_incoming_segments_4_8_2_2_transition_to_time_wait_or_closing_if_finish_flag_set(transmission_control_block);
 	}
 	
 	#[inline(always)]
@@ -360,13 +403,19 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		{
 			return;
 		}
-		// TODO: In addition to the processing for the ESTABLISHED state, if the retransmission queue is empty, the user’s CLOSE can be acknowledged (“ok”) but do not delete the TCB.
+		
+		// RFC 793 Page 73: "In addition to the processing for the ESTABLISHED state, if the retransmission queue is empty, the user's CLOSE can be acknowledged ("ok") but do not delete the TCB".
+		//
+		// The user is told that the close has completed by `closed()`, which occurs when TIME-WAIT ends.
 		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
-		self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true);
+		if self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, true)
+		{
+			return
+		}
 		
-		self.processing_incoming_segments_4_8_2_3_transition_to_time_wait_if_finish_flag_set();
+		self.processing_incoming_segments_4_8_2_3_transition_to_time_wait_if_finish_flag_set(transmission_control_block);
 	}
 	
 	#[inline(always)]
@@ -393,7 +442,16 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		{
 			return;
 		}
-		// TODO: In addition to the processing for the ESTABLISHED state, if the ACK acknowledges our FIN then enter the TIME-WAIT state, otherwise ignore the segment.
+		
+		// RFC 793 Page 73: "In addition to the processing for the ESTABLISHED state, if the ACK acknowledges our FIN then enter the TIME-WAIT state, otherwise ignore the segment".
+		if transmission_control_block.SND.finish_has_been_acknowledged()
+		{
+			transmission_control_block.enter_state_time_wait(self.interface.alarms());
+		}
+		else
+		{
+			return
+		}
 		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
@@ -402,24 +460,63 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		self.processing_incoming_segments_4_8_2_do_nothing_if_finish_flag_set();
 	}
 	
-	// TODO: https://tools.ietf.org/html/rfc6191 - Reducing the TIME-WAIT State Using TCP Timestamps
-	// TODO: RFC 1122 Section 4.2.2.13 Paragraph 4: "When a connection is closed actively, it MUST linger in TIME-WAIT state for a time 2xMSL (Maximum Segment Lifetime).
+	// RFC 1122 Section 4.2.2.13 Paragraph 4: "When a connection is closed actively, it MUST linger in TIME-WAIT state for a time 2xMSL (Maximum Segment Lifetime).
 	// However, it MAY accept a new SYN from the remote TCP to reopen the connection directly from TIME-WAIT state, if it:-
 	// (1) assigns its initial sequence number for the newconnection to be larger than the largest sequencenumber it used on the previous connection incarnation, and
 	// (2) returns to TIME-WAIT state if the SYN turns out to be an old duplicate".
-	// TODO
+	//
+	// RFC 6191 Section 2: "When a connection request is received with a four-tuple that is in use by a connection in the TIME-WAIT state, the connection request could be accepted if the ... conditions are met".
 	#[inline(always)]
 	fn time_wait(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
 	{
 		reject_synchronize_finish!(self);
 		
+		if self.synchronize_flag_set()
+		{
+			// TIME-WAIT is entered by the side which closed first, whichever side originated the connection.
+			// RFC 6191 reuse only applies here when we are the server, which closed first and then receives a SYN from the client for a new incarnation of the same four-tuple; our local port is then a listening port and so we can respond with a syncookie.
+			// As a client our local port is not a listening port, so a SYN can only be an old duplicate or an attempted simultaneous open, which is not supported.
+			if unlikely!(transmission_control_block.we_are_the_client())
+			{
+				invalid!(self, "TCP Synchronize segment received in TimeWait state by client")
+			}
+			
+			let explicit_congestion_notification_supported = match self.all_flags()
+			{
+				Flags::Synchronize => false,
+				Flags::SynchronizeExplicitCongestionEchoCongestionWindowReduced => true,
+				_ => invalid!(self, "TCP Synchronize segment received in TimeWait state has flags other than Synchronize or Synchronize-ECE-CWR"),
+			};
+			
+			// RFC 6191 Section 2: "... the connection request is not accepted and the TIME-WAIT state is preserved; the incoming SYN segment is processed as specified in RFC 793".
+			//
+			// RFC 793's processing would either send a challenge acknowledgment (RFC 5961) or a Reset; as the segment is most likely an old duplicate, we simply ignore it.
+			if !transmission_control_block.rfc_6191_synchronize_is_for_a_new_incarnation(self.SEQ, self.tcp_options.timestamps.as_ref())
+			{
+				invalid!(self, "TCP Synchronize segment received in TimeWait state is an old duplicate (RFC 6191)")
+			}
+			
+			// The SYN is validated in full before the old incarnation is closed, so that a SYN which would not be answered can not assassinate TIME-WAIT; if it is not valid, TIME-WAIT and its alarm are kept.
+			validate_synchronize_for_syncookie!(self);
+			
+			// Since we use syncookies, accepting the SYN means closing the old incarnation and then responding exactly as if the state were Listen.
+			// If the SYN turns out to be an old duplicate, the syncookie in our SYN-ACK will never be validated.
+			let md5_authentication_key = self.interface.find_md5_authentication_key(self.source_internet_protocol_address, self.remote_port_local_port()).cloned();
+			transmission_control_block.closed(self.interface, self.now);
+			return self.send_synchronize_acknowledgment_with_syncookie_for_validated_synchronize(md5_authentication_key, explicit_congestion_notification_supported)
+		}
+		
 		let SEG = self;
 		let SND = &mut transmission_control_block.SND;
 		
 		let timestamps_option = processing_incoming_segments_4_1_check_sequence_number!(self, transmission_control_block);
 		
-		// TODO: RFC 1337 Section 4: "Of the three fixes described in the previous section, fix (F1), ignoring RST segments in TIME-WAIT state, seems like the best short-term solution.
+		// RFC 1337 Section 4: "Of the three fixes described in the previous section, fix (F1), ignoring RST segments in TIME-WAIT state, seems like the best short-term solution.
 		// NOTE: This is not without controversy and is disabled by default in Linux. Linux however does not conform to RFC 793 either - see https://serverfault.com/questions/787624/why-isnt-net-ipv4-tcp-rfc1337-enabled-by-default .
+		if self.reset_flag_set() && self.interface.time_wait_assassination_protection()
+		{
+			invalid!(self, "TCP Reset received in TimeWait state ignored (RFC 1337 TIME-WAIT assassination protection)")
+		}
 		
 		processing_incoming_segments_4_2_check_the_rst_bit_closing_last_acknowledgment_time_wait!(self, transmission_control_block);
 		
@@ -431,20 +528,52 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		
 		rfc_5961_5_2_acknowledgment_is_acceptable!(self, transmission_control_block);
 		
-		// ACK ON
-		// TODO: The only thing that can arrive in this state is a retransmission of the remote FIN. Acknowledge it, and restart the 2 MSL timeout.
-		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
 		processing_incoming_segments_4_7_2_ignore_the_segment_text!(self);
 		
-		self.processing_incoming_segments_4_8_2_7_restart_time_wait_time_out_if_finish_flag_set();
+		self.processing_incoming_segments_4_8_2_7_restart_time_wait_time_out_if_finish_flag_set(transmission_control_block);
 	}
 }
 
 /// Supporting logic.
 impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b, TCBA>
 {
+	#[inline(always)]
+	fn send_synchronize_acknowledgment_with_syncookie(&mut self, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, explicit_congestion_notification_supported: bool)
+	{
+		validate_synchronize_for_syncookie!(self);
+		
+		self.send_synchronize_acknowledgment_with_syncookie_for_validated_synchronize(md5_authentication_key, explicit_congestion_notification_supported)
+	}
+	
+	/// The Synchronize must have already been checked with `validate_synchronize_for_syncookie!`.
+	#[inline(always)]
+	fn send_synchronize_acknowledgment_with_syncookie_for_validated_synchronize(&mut self, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, explicit_congestion_notification_supported: bool)
+	{
+		let maximum_segment_size = self.tcp_options.maximum_segment_size;
+		let window_scale = self.tcp_options.window_scale;
+		let selective_acknowledgment_permitted = self.tcp_options.selective_acknowledgment_permitted;
+		let timestamps = self.tcp_options.timestamps;
+		
+		// RFC 9768: An AccECN setup Synchronize has the AE, CWR and ECE flags all set; the SynchronizeAcknowledgment reflects the IP ECN field of the Synchronize in its ACE field.
+		//
+		// There is no space in a syncookie to record that Accurate ECN was negotiated; instead, the non-zero ACE field of the acknowledgment which completes the three-way handshake reveals it.
+		let accurate_explicit_congestion_echo_field = if explicit_congestion_notification_supported && self.SEG.has_accurate_explicit_congestion_notification_flag() && self.interface.accurate_explicit_congestion_notification()
+		{
+			Some(AccurateExplicitCongestionEchoField::handshake_reflection_of(self.packet.explicit_congestion_notification()))
+		}
+		else
+		{
+			None
+		};
+		
+		// RFC 5562: A SynchronizeAcknowledgment which agrees to explicit congestion notification may be sent ECN-capable.
+		let capable_transport = self.interface.synchronize_acknowledgment_is_capable_transport(explicit_congestion_notification_supported, self.source_internet_protocol_address, self.now);
+		
+		self.interface.send_synchronize_acknowledgment(self.now, self.reuse_packet(), self.source_internet_protocol_address, self, maximum_segment_size, window_scale, selective_acknowledgment_permitted, timestamps, explicit_congestion_notification_supported, accurate_explicit_congestion_echo_field, capable_transport, md5_authentication_key);
+	}
+	
 	fn synchronize_sent_received_acknowledgment(&mut self, transmission_control_block: &TransmissionControlBlock<TCBA>)
 	{
 		// TODO: Zero window nonsense - not a problem for sending this ack, but potentially a problem thereafter.
@@ -453,7 +582,8 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		x;
 		
 		
-		if cfg!(not(feature = "rfc-8311-permit-explicit-congenstion-markers-on-all-packets"))
+		// RFC 9768: With Accurate ECN, the IP ECN field of the SynchronizeAcknowledgment is fed back to the server, so it is not an error for it to be set.
+		if cfg!(not(feature = "rfc-8311-permit-explicit-congenstion-markers-on-all-packets")) && !transmission_control_block.accurate_explicit_congestion_notification_supported()
 		{
 			// RFC 3168 Section 6.1.1: "A host MUST NOT set ECT on SYN or SYN-ACK packets".
 			if unlikely!(self.packet.explicit_congestion_notification().is_ect_or_congestion_experienced_set())
@@ -496,21 +626,37 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 			None => transmission_control_block.disable_timestamping(),
 		}
 		
-		if self.tcp_options.selective_acknowledgments_permitted
+		if self.tcp_options.selective_acknowledgment_permitted
 		{
 			transmission_control_block.selective_acknowledgments_permitted = true;
+			
+			if self.interface.recent_acknowledgment_loss_detection()
+			{
+				transmission_control_block.use_recent_acknowledgment_loss_detection_if_selective_acknowledgments_are_permitted();
+			}
 		}
 		else
 		{
 			transmission_control_block.selective_acknowledgments_permitted = false;
 		}
 		
+		if self.interface.congestion_window_validation()
+		{
+			transmission_control_block.use_congestion_window_validation();
+		}
+		
 		transmission_control_block.SND.set_window(SEG, self.now);
 		
 		transmission_control_block.enter_state_established();
 		
 		self.interface.send_final_acknowledgment_of_three_way_handshake(self.reuse_packet(), transmission_control_block, self.now, Flags::Acknowledgment, transmission_control_block.SND.NXT(), transmission_control_block.RCV.NXT());
 		
+		// RFC 793 Page 66: "Data or controls which were queued for transmission may be included".
+		if unlikely!(transmission_control_block.transmit_buffered_data_returning_true_if_failed(self.interface, self.now))
+		{
+			return
+		}
+		
 		self.processing_incoming_segments_4_6_check_the_urg_bit();
 		
 		self.processing_incoming_segments_4_7_1_process_the_segment_text(transmission_control_block, false);
@@ -551,11 +697,15 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 		// TODO: We are recording when the send window was last updated.
 		transmission_control_block.keep_alive_alarm.record_last_acknowledgment_occurred_at(now);
 		
+		// RFC 6675 Section 5 Step (A): "An incoming cumulative ACK or a duplicate ACK ... The scoreboard MUST be updated via the Update () routine".
+		let selective_acknowledgment_option_had_new_information = self.update_selective_acknowledgment_scoreboard(transmission_control_block);
+		
+		// RFC 9768: Every acknowledgment, including a duplicate acknowledgment, feeds back Accurate ECN counters.
+		let explicit_congestion_echo = self.explicit_congestion_feedback(transmission_control_block);
 		
 		if self.acknowledgment_is_acceptable_after_applying_rfc_5961_section_5_2_paragraph_1(transmission_control_block)
 		{
 			let timestamps_option = self.tcp_options.timestamps_option.as_ref();
-			let explicit_congestion_echo = self.explicit_congestion_echo_flag_set();
 			transmission_control_block.acknowledgment_of_new_data_returning_true_if_failed(self.interface, SEG, self.now, timestamps_option, explicit_congestion_echo)
 		}
 		else
@@ -585,22 +735,55 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 			// * (e) the advertised window in the incoming acknowledgment equals the advertised window in the last incoming acknowledgment".
 			//
 			// \* The `SYN` bit is already checked for in Processing Incoming Segments 4.4.
-			let is_a_duplicate_acknowledgment = transmission_control_block.has_data_unacknowledged() && self.does_not_have_data() && self.finish_flag_unset() && segment_acknowledgment_number_is_equal_to_the_greatest && transmission_control_block.SND.advertised_window_in_the_incoming_acknowledgment_equals_the_advertised_window_in_the_last_incoming_acknowledgment(self);
+			//
+			// RFC 6675 Section 2: "DupAcks: ... an ACK that (a) arrives carrying a SACK block that identifies previously unacknowledged and un-SACKed octets between HighACK and HighData ...".
+			//
+			// Hence, when using selective acknowledgments, an acknowledgment with a SACK option but without any new SACK information in it (eg one duplicated by a switch or as part of a denial-of-service attack) is not a duplicate acknowledgment.
+			let is_a_duplicate_acknowledgment = transmission_control_block.has_data_unacknowledged() && segment_acknowledgment_number_is_equal_to_the_greatest && if transmission_control_block.selective_acknowledgments_permitted && self.tcp_options.has_selective_acknowledgment()
+			{
+				selective_acknowledgment_option_had_new_information
+			}
+			else
+			{
+				self.does_not_have_data() && self.finish_flag_unset() && transmission_control_block.SND.advertised_window_in_the_incoming_acknowledgment_equals_the_advertised_window_in_the_last_incoming_acknowledgment(self)
+			};
 			if is_a_duplicate_acknowledgment
 			{
-				transmission_control_block.increment_duplicate_acknowledgments_received_without_any_intervening_acknwoledgments_which_moved_SND_UNA();
-				
-				// TODO: If 3 dupacks receved, enter fast retransmit congestion control
-				x;
+				return transmission_control_block.duplicate_acknowledgment_received_returning_true_if_failed(self.interface, self.now)
 			}
 			
-			// TODO: Following 2 kinds of acks should not affect dupack counting: 1) Old acks [test above should cover this] 2) Acks with SACK but without any new SACK information in them. These could result from any anomaly in the network like a switch duplicating packets or a possible DoS attack.
-			x;
-			
 			false
 		}
 	}
 	
+	/// Returns `true` if the segment has a `SACK` option which contained new information.
+	///
+	/// A D-SACK block (RFC 2883 Section 4) is passed on so that spurious retransmissions can be detected (RFC 3708); it never contains new information.
+	#[inline(always)]
+	fn update_selective_acknowledgment_scoreboard(&self, transmission_control_block: &mut TransmissionControlBlock<TCBA>) -> bool
+	{
+		if !transmission_control_block.selective_acknowledgments_permitted
+		{
+			return false
+		}
+		
+		match self.tcp_options.selective_acknowledgment
+		{
+			None => false,
+			
+			Some(ref selective_acknowledgment_option) =>
+			{
+				if let Some(duplicate_selective_acknowledgment_block) = selective_acknowledgment_option.duplicate_selective_acknowledgment_block(self.ACK)
+				{
+					transmission_control_block.duplicate_selective_acknowledgment_received(duplicate_selective_acknowledgment_block)
+				}
+				
+				let timestamps_option = self.tcp_options.timestamps.as_ref();
+				transmission_control_block.selectively_acknowledged(selective_acknowledgment_option, timestamps_option, self.now)
+			}
+		}
+	}
+	
 	/// Processing Incoming Segments 4.6: Check the URG bit.
 	fn processing_incoming_segments_4_6_check_the_urg_bit(&self)
 	{
@@ -614,136 +797,205 @@ impl<'a, 'b, TCBA: TransmissionControlBlockAbstractions> ParsedTcpSegment<'a, 'b
 	}
 	
 	/// Processing Incoming Segments 4.7.1.
+	///
+	/// Returns `true` if this segment was out-of-order (to the right of `RCV.NXT`); a `FIN` on such a segment must not yet be processed.
 	#[inline(always)]
-	fn processing_incoming_segments_4_7_1_process_the_segment_text(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>, this_is_after_syn_ack: bool)
+	fn processing_incoming_segments_4_7_1_process_the_segment_text(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>, this_is_after_syn_ack: bool) -> bool
 	{
 		if self.does_not_have_data()
 		{
-			return
+			// RFC 9768: CE marks on packets without data, which a remote using generalized ECN may send ECN-capable, are counted too.
+			if likely!(this_is_after_syn_ack)
+			{
+				if let Some(explicit_congestion_notification_state) = transmission_control_block.explicit_congestion_notification_state_mutable_reference()
+				{
+					explicit_congestion_notification_state.incoming_packet_without_data_accurate(self.packet.explicit_congestion_notification());
+				}
+			}
+			
+			return self.SEQ != transmission_control_block.RCV.NXT()
 		}
 		
 		// TODO: CWR flag should only be set on data segments that have not been re-txmtd and not on zero window probes.
 		// TODO: ECN flag should only be set on ACKs.
 		
+		// RFC 3168 Section 6.1.1: "A host MUST NOT set ECT on SYN or SYN-ACK packets"; hence congestion_encountered() is false and the CWR flag should not be set.
+		let congestion_encountered = likely!(this_is_after_syn_ack) && self.packet.explicit_congestion_notification().congestion_encountered();
+		
+		// RFC 8257 Section 3.2: A delayed acknowledgment covers data received before the CE codepoint changed, so it is sent with the explicit congestion echo flag it had before the change.
+		let explicit_congestion_echo_would_change = match transmission_control_block.explicit_congestion_notification_state_reference()
+		{
+			None => false,
+			Some(explicit_congestion_notification_state) => likely!(this_is_after_syn_ack) && explicit_congestion_notification_state.incoming_data_packet_would_change_explicit_congestion_echo(congestion_encountered),
+		};
+		if unlikely!(explicit_congestion_echo_would_change)
+		{
+			transmission_control_block.send_delayed_acknowledgment_now(self.interface, self.now);
+		}
+		
+		let mut send_an_immediate_acknowledgment_for_explicit_congestion_echo = false;
 		if let Some(explicit_congestion_notification_state) = transmission_control_block.explicit_congestion_notification_state_mutable_reference()
 		{
-			// RFC 3168 Section 6.1.1: "A host MUST NOT set ECT on SYN or SYN-ACK packets"; hence congestion_encountered() is false and the CWR flag should not be set.
 			if likely!(this_is_after_syn_ack)
 			{
-				if self.congestion_window_reduced_flag_set()
+				if explicit_congestion_notification_state.is_accurate()
 				{
-					explicit_congestion_notification_state.incoming_data_packet_had_congestion_window_reduced_flag_set();
+					// RFC 9768: The receiver counts CE marked packets and the payload bytes of each IP ECN code point; the CWR flag is part of the ACE field.
+					send_an_immediate_acknowledgment_for_explicit_congestion_echo = explicit_congestion_notification_state.incoming_data_packet_accurate(self.packet.explicit_congestion_notification(), self.payload_length as u32);
 				}
-				
-				// RFC 3168 Section 6.5: "ECN-capable TCP implementations MUST NOT set either ECT codepoint (ECT(0) or ECT(1)) in the IP header for retransmitted data packets, and that the TCP data receiver SHOULD ignore the ECN field on arriving data packets that are outside of the receiver's current window".
-				//
-				// processing_incoming_segments_4_1_3_r2! checks for data packets that are outside of the current window, so it should not be possible for the SHOULD in the above statement to be violated.
-				if self.packet.explicit_congestion_notification().congestion_encountered()
+				else if explicit_congestion_notification_state.explicit_congestion_echo_per_segment()
 				{
-					explicit_congestion_notification_state.congestion_was_encountered()
+					send_an_immediate_acknowledgment_for_explicit_congestion_echo = explicit_congestion_notification_state.incoming_data_packet_per_segment(congestion_encountered);
+				}
+				else
+				{
+					if self.congestion_window_reduced_flag_set()
+					{
+						explicit_congestion_notification_state.incoming_data_packet_had_congestion_window_reduced_flag_set();
+					}
+					
+					// RFC 3168 Section 6.5: "ECN-capable TCP implementations MUST NOT set either ECT codepoint (ECT(0) or ECT(1)) in the IP header for retransmitted data packets, and that the TCP data receiver SHOULD ignore the ECN field on arriving data packets that are outside of the receiver's current window".
+					//
+					// processing_incoming_segments_4_1_3_r2! checks for data packets that are outside of the current window, so it should not be possible for the SHOULD in the above statement to be violated.
+					if congestion_encountered
+					{
+						explicit_congestion_notification_state.congestion_was_encountered()
+					}
 				}
 			}
 		}
 		
 		
 		
-		// NOTE: RCV.NXT <= SEG.SEQ, ie this segment might be out-of-order but WITHIN the window.
-		
-		/*
+		let RCV_NXT = transmission_control_block.RCV.NXT();
 		
-		RFC 1122 Section 4.2.2.21  Acknowledging Queued Segments: RFC-793 Section 3.9
-
-            A TCP MAY send an ACK segment acknowledging RCV.NXT when a
-            valid segment arrives that is in the window but not at the
-            left window edge.
-
-
-
-
-Internet Engineering Task Force                                [Page 94]
-
-
-RFC1122                  TRANSPORT LAYER -- TCP             October 1989
-
-
-            DISCUSSION:
-                 RFC-793 (see page 74) was ambiguous about whether or
-                 not an ACK segment should be sent when an out-of-order
-                 segment was received, i.e., when SEG.SEQ was unequal to
-                 RCV.NXT.
-
-                 One reason for ACKing out-of-order segments might be to
-                 support an experimental algorithm known as "fast
-                 retransmit".   With this algorithm, the sender uses the
-                 "redundant" ACK's to deduce that a segment has been
-                 lost before the retransmission timer has expired.  It
-                 counts the number of times an ACK has been received
-                 with the same value of SEG.ACK and with the same right
-                 window edge.  If more than a threshold number of such
-                 ACK's is received, then the segment containing the
-                 octets starting at SEG.ACK is assumed to have been lost
-                 and is retransmitted, without awaiting a timeout.  The
-                 threshold is chosen to compensate for the maximum
-                 likely segment reordering in the Internet.  There is
-                 not yet enough experience with the fast retransmit
-                 algorithm to determine how useful it is.
-                 
-                 RJC: This is the algorithm used in FreeBSD.
-		
-		*/
+		// RFC 1122 Section 4.2.2.21: "A TCP MAY send an ACK segment acknowledging RCV.NXT when a valid segment arrives that is in the window but not at the left window edge".
+		//
+		// RFC 5681 Section 4.2: "A TCP receiver SHOULD send an immediate duplicate ACK when an out-of-order segment arrives".
+		let (segment_is_out_of_order, acknowledgment_is_delayed) = if self.SEQ > RCV_NXT
+		{
+			// RFC 793 Page 69: "Segments with higher begining sequence numbers may be held for later processing".
+			//
+			// If the segment can not be held it is discarded; the remote will retransmit it.
+			transmission_control_block.RCV.hold_out_of_order_data(self.SEQ, self.payload());
+			
+			// The remote is likely to be recovering from loss; do not delay its acknowledgments once the gap is filled.
+			transmission_control_block.RCV.enter_quick_acknowledgment_mode();
+			(true, false)
+		}
+		else
+		{
+			// RFC 5681 Section 4.2: "... a TCP receiver SHOULD send an immediate ACK when the incoming segment fills in all or part of a gap in the sequence space".
+			let fills_a_gap = transmission_control_block.RCV.has_out_of_order_data();
+			
+			// Processing Incoming Segments 4.1 guarantees that at least part of this segment is at or after RCV.NXT; any part before it has already been received.
+			let already_received = min((RCV_NXT - self.SEQ) as usize, self.payload_length);
+			transmission_control_block.received_in_order_data(&self.payload()[already_received .. ]);
+			
+			let acknowledgment_is_delayed = !fills_a_gap && !send_an_immediate_acknowledgment_for_explicit_congestion_echo && self.finish_flag_unset() && transmission_control_block.delay_acknowledgment_of_received_data(self.interface.alarms(), self.now, self.payload_length);
+			(false, acknowledgment_is_delayed)
+		};
 		
 		// TODO: SEE RFC 5961 Section 5: "Blind Data Injection Attack" mitigations.
 		
-		xxxx;
-		
 		// Once the TCP takes responsibility for the data it advances RCV.NXT over the data accepted, and adjusts RCV.WND as apporopriate to the current buffer availability.
 		// The total of RCV.NXT and RCV.WND should not be reduced.
 		
 		// Send an acknowledgment of the form: <SEQ=SND.NXT><ACK=RCV.NXT><CTL=ACK>.
 		// This acknowledgment should be piggybacked on a segment being transmitted if possible without incurring undue delay.
-		self.interface.send_acknowledgment(self.reuse_packet(), transmission_control_block, self.now, Flags::Acknowledgment, transmission_control_block.SND.NXT(), transmission_control_block.RCV.NXT())
+		//
+		// An in-order `FIN` is acknowledged instead by Processing Incoming Segments 4.8 once RCV.NXT has been advanced over it.
+		//
+		// A delayed acknowledgment is either piggybacked on data we send or sent when the delayed acknowledgment alarm goes off.
+		if segment_is_out_of_order || (self.finish_flag_unset() && !acknowledgment_is_delayed)
+		{
+			self.interface.send_acknowledgment(self.reuse_packet(), transmission_control_block, self.now, Flags::Acknowledgment, transmission_control_block.SND.NXT(), transmission_control_block.RCV.NXT());
+			transmission_control_block.acknowledgment_sent(self.interface.alarms());
+		}
 		
 		// Please note the window management suggestions in section 3.7.
+		
+		segment_is_out_of_order
 	}
 	
+	/// Processing Incoming Segments 4.8.1.
+	///
+	/// RFC 793 Page 75: "If the FIN bit is set, signal the user "connection closing" and return any pending RECEIVEs with same message, advance RCV.NXT over the FIN, and send an acknowledgment for the FIN".
 	#[inline(always)]
-	fn processing_incoming_segments_4_8_2_1_transition_to_close_wait_if_finish_flag_set(&self)
+	fn processing_incoming_segments_4_8_1_acknowledge_finish(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
+	{
+		transmission_control_block.peer_finished();
+		
+		transmission_control_block.RCV.advance_NXT_over_finish();
+		
+		self.interface.send_acknowledgment(self.reuse_packet(), transmission_control_block, self.now, Flags::Acknowledgment, transmission_control_block.SND.NXT(), transmission_control_block.RCV.NXT());
+		transmission_control_block.acknowledgment_sent(self.interface.alarms())
+	}
+	
+	#[inline(always)]
+	fn processing_incoming_segments_4_8_2_1_transition_to_close_wait_if_finish_flag_set(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
 	{
 		if self.finish_flag_set()
 		{
-			// TODO: Whatever we should do for a close-wait transition.
+			self.processing_incoming_segments_4_8_1_acknowledge_finish(transmission_control_block);
+			
+			// RFC 793 Page 75: "Enter the CLOSE-WAIT state".
+			transmission_control_block.set_state(State::CloseWait);
 		}
 	}
 	
 	#[inline(always)]
-	fn processing_incoming_segments_4_8_2_2_transition_to_time_wait_or_closing_if_finish_flag_set(&self)
+	fn processing_incoming_segments_4_8_2_2_transition_to_time_wait_or_closing_if_finish_flag_set(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
 	{
 		if self.finish_flag_set()
 		{
-			// TODO: If our FIN has been ACKed (perhaps in this segment), then enter TIME-WAIT, start the time-wait timer, turn off the other timers
-			// TODO: else enter CLOSING state.
+			self.processing_incoming_segments_4_8_1_acknowledge_finish(transmission_control_block);
+			
+			// RFC 793 Page 75: "If our FIN has been ACKed (perhaps in this segment), then enter TIME-WAIT, start the time-wait timer, turn off the other timers; otherwise enter the CLOSING state".
+			if transmission_control_block.SND.finish_has_been_acknowledged()
+			{
+				transmission_control_block.enter_state_time_wait(self.interface.alarms());
+			}
+			else
+			{
+				transmission_control_block.set_state(State::Closing);
+			}
 		}
 	}
 	
 	#[inline(always)]
-	fn processing_incoming_segments_4_8_2_3_transition_to_time_wait_if_finish_flag_set(&self)
+	fn processing_incoming_segments_4_8_2_3_transition_to_time_wait_if_finish_flag_set(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
 	{
 		if self.finish_flag_set()
 		{
-			// TODO: ??? Enter the Time-wait state, start the time-wait timer, make sure all other timers are turned off.
-			// We should have per-state timers, replacing the user_time_out timer, which auto-kill and forcibly close the connection on expiry. These may or may not send a reset.
+			self.processing_incoming_segments_4_8_1_acknowledge_finish(transmission_control_block);
+			
+			// RFC 793 Page 75: "Enter the TIME-WAIT state. Start the time-wait timer, turn off the other timers".
+			transmission_control_block.enter_state_time_wait(self.interface.alarms());
 		}
 	}
 	
 	#[inline(always)]
-	fn processing_incoming_segments_4_8_2_7_restart_time_wait_time_out_if_finish_flag_set(&self)
+	fn processing_incoming_segments_4_8_2_7_restart_time_wait_time_out_if_finish_flag_set(&mut self, transmission_control_block: &mut TransmissionControlBlock<TCBA>)
 	{
 		if self.finish_flag_set()
 		{
-			// TODO: Restart the 2 MSL time-wait timeout.
+			// RFC 793 Page 73: "The only thing that can arrive in this state is a retransmission of the remote FIN. Acknowledge it, and restart the 2 MSL timeout".
+			self.interface.send_acknowledgment(self.reuse_packet(), transmission_control_block, self.now, Flags::Acknowledgment, transmission_control_block.SND.NXT(), transmission_control_block.RCV.NXT());
+			
+			transmission_control_block.restart_time_wait_alarm(self.interface.alarms());
 		}
 	}
 	
+	/// Does this segment carry a `FIN` which, together with any data before it, ends at `RCV.NXT`, ie was the `FIN` already received, and does it acknowledge all that we have sent?
+	#[inline(always)]
+	fn is_a_retransmission_of_the_remote_finish(&self, transmission_control_block: &TransmissionControlBlock<TCBA>) -> bool
+	{
+		const FinishSequenceNumberLength: u32 = 1;
+		
+		self.finish_flag_set() && self.acknowledgment_flag_set() && self.reset_flag_unset() && self.SEQ + (self.payload_length as u32) + FinishSequenceNumberLength == transmission_control_block.RCV.NXT() && self.ACK == transmission_control_block.SND.NXT()
+	}
+	
 	#[inline(always)]
 	fn ignore(&self)
 	{
@@ -768,6 +1020,12 @@ RFC1122                  TRANSPORT LAYER -- TCP             October 1989
 		self.SEG.payload_data_pointer(self.options_length)
 	}
 	
+	#[inline(always)]
+	fn payload(&self) -> &[u8]
+	{
+		unsafe { from_raw_parts(self.payload_data_pointer().as_ptr() as *const u8, self.payload_length) }
+	}
+	
 	#[inline(always)]
 	fn reuse_packet(&mut self) -> TCBA::Packet
 	{
@@ -838,6 +1096,20 @@ impl<'a, 'b, TCBA: 'a + TransmissionControlBlockAbstractions> ParsedTcpSegment<'
 		self.all_flags().does_not_contain(Flags::Finish)
 	}
 	
+	/// Returns `true` if congestion was fed back, either by the explicit congestion echo (ECE) flag (RFC 3168) or, if Accurate ECN (RFC 9768) is in use, by an increase in the count of CE marked packets.
+	#[inline(always)]
+	fn explicit_congestion_feedback(&self, transmission_control_block: &mut TransmissionControlBlock<TCBA>) -> bool
+	{
+		let accurate_explicit_congestion_echo_field = self.SEG.accurate_explicit_congestion_echo_field();
+		
+		match transmission_control_block.accurate_explicit_congestion_notification_acknowledgment_received(accurate_explicit_congestion_echo_field, self.tcp_options.accurate_explicit_congestion_notification.as_ref())
+		{
+			None => self.explicit_congestion_echo_flag_set(),
+			
+			Some(congestion_experienced) => congestion_experienced,
+		}
+	}
+	
 	#[inline(always)]
 	fn explicit_congestion_echo_flag_set(&self) -> bool
 	{
@@ -0,0 +1,24 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2018 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Checks that a Synchronize segment may be answered with a syncookie, ie that it has no payload, is authenticated if authentication is required and has an initial ACK of zero.
+macro_rules! validate_synchronize_for_syncookie
+{
+	($self: ident) =>
+	{
+		{
+			if $self.has_data()
+			{
+				invalid!($self, "TCP Synchronize packets are not supported with payloads as we use syncookies");
+			}
+			
+			validate_authentication!($self);
+			
+			if unlikely!($self.SEG.ACK() != WrappingSequenceNumber::Zero)
+			{
+				invalid!($self, "TCP Synchronize packets should have an initial ACK of zero (0)");
+			}
+		}
+	}
+}
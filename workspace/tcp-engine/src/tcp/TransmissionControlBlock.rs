@@ -20,6 +20,9 @@ pub(crate) struct TransmissionControlBlock<TCBA: TransmissionControlBlockAbstrac
 	keep_alive_alarm: Alarm<KeepAliveAlarmBehaviour<TCBA>, TCBA>,
 	retransmission_and_zero_window_probe_alarm: Alarm<RetransmissionAndZeroWindowProbeAlarmBehaviour<TCBA>, TCBA>,
 	user_time_out_alarm: Alarm<UserTimeOutAlarmBehaviour<TCBA>, TCBA>,
+	time_wait_alarm: Alarm<TimeWaitAlarmBehaviour<TCBA>, TCBA>,
+	delayed_acknowledgment_alarm: Alarm<DelayedAcknowledgmentAlarmBehaviour<TCBA>, TCBA>,
+	pacing_alarm: Alarm<PacingAlarmBehaviour<TCBA>, TCBA>,
 	
 	timestamping: Option<Timestamping>,
 	
@@ -46,18 +49,21 @@ pub(crate) struct TransmissionControlBlock<TCBA: TransmissionControlBlockAbstrac
 impl<TCBA: TransmissionControlBlockAbstractions> CreateTransmissionControlBlock<TCBA::Address> for TransmissionControlBlock<TCBA>
 {
 	#[inline(always)]
-	fn new_for_closed_to_synchronize_sent(key: TransmissionControlBlockKey, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, congestion_control: CongestionControl, ISS: WrappingSequenceNumber) -> Self
+	fn new_for_closed_to_synchronize_sent(key: TransmissionControlBlockKey, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, reassembly_queue: ReassemblyQueue, congestion_control: CongestionControl, ISS: WrappingSequenceNumber) -> Self
 	{
 		Self
 		{
 			events_receiver: TCBA::EventReceiverCreator::create(&key),
 			key,
 			state: State::SynchronizeSent,
-			RCV: TransmissionControlBlockReceive::new_for_closed_to_synchronize_sent(),
+			RCV: TransmissionControlBlockReceive::new_for_closed_to_synchronize_sent(reassembly_queue),
 			SND: TransmissionControlBlockSend::new_for_closed_to_synchronize_sent(magic_ring_buffer, now, ISS),
 			keep_alive_alarm: Default::default(),
 			retransmission_and_zero_window_probe_alarm: Alarm::new(RetransmissionAndZeroWindowProbeAlarmBehaviour::new(recent_connection_data, true)),
 			user_time_out_alarm: Default::default(),
+			time_wait_alarm: Default::default(),
+			delayed_acknowledgment_alarm: Default::default(),
+			pacing_alarm: Default::default(),
 			timestamping: Timestamping::new_for_closed_to_synchronize_sent(),
 			we_are_the_listener: false,
 			maximum_segment_size_to_send_to_remote,
@@ -68,7 +74,7 @@ impl<TCBA: TransmissionControlBlockAbstractions> CreateTransmissionControlBlock<
 	}
 	
 	#[inline(always)]
-	fn new_for_sychronize_received_to_established(key: TransmissionControlBlockKey, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, congestion_control: CongestionControl, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie) -> Self
+	fn new_for_sychronize_received_to_established(key: TransmissionControlBlockKey, now: MonotonicMillisecondTimestamp, maximum_segment_size_to_send_to_remote: u16, recent_connection_data: &RecentConnectionData, md5_authentication_key: Option<Rc<Md5PreSharedSecretKey>>, magic_ring_buffer: MagicRingBuffer, reassembly_queue: ReassemblyQueue, congestion_control: CongestionControl, SEG_WND: SegmentWindowSize, tcp_options: &TcpOptions, parsed_syncookie: ParsedSynCookie) -> Self
 	{
 		let (RCV_WND, RCV_Wind_Shift, SND_WND, SND_Wind_Shift) = match parsed_syncookie.their_window_scale
 		{
@@ -93,11 +99,14 @@ impl<TCBA: TransmissionControlBlockAbstractions> CreateTransmissionControlBlock<
 			events_receiver: TCBA::EventReceiverCreator::create(&key),
 			key,
 			state: State::Established,
-			RCV: TransmissionControlBlockReceive::new_for_sychronize_received_to_established(RCV_NXT, RCV_WND, RCV_Wind_Shift),
+			RCV: TransmissionControlBlockReceive::new_for_sychronize_received_to_established(reassembly_queue, RCV_NXT, RCV_WND, RCV_Wind_Shift),
 			SND: TransmissionControlBlockSend::new_for_sychronize_received_to_established(magic_ring_buffer, now, ISS, IRS, SND_WND, SND_Wind_Shift),
 			keep_alive_alarm: Default::default(),
 			retransmission_and_zero_window_probe_alarm: Alarm::new(RetransmissionAndZeroWindowProbeAlarmBehaviour::new(recent_connection_data, false)),
 			user_time_out_alarm: Default::default(),
+			time_wait_alarm: Default::default(),
+			delayed_acknowledgment_alarm: Default::default(),
+			pacing_alarm: Default::default(),
 			timestamping: Timestamping::new_for_sychronize_received_to_established(tcp_options, now, RCV_NXT),
 			we_are_the_listener: true,
 			maximum_segment_size_to_send_to_remote,
@@ -133,6 +142,12 @@ impl<TCBA: TransmissionControlBlockAbstractions> ConnectionIdentification<TCBA::
 	{
 		self.we_are_the_listener
 	}
+	
+	#[inline(always)]
+	fn source_port_may_be_recycled_immediately(&self) -> bool
+	{
+		self.state() == State::TimeWait || self.timestamping.is_some()
+	}
 }
 
 impl<TCBA: TransmissionControlBlockAbstractions> RecentConnectionDataProvider<TCBA::Address> for TransmissionControlBlock<TCBA>
@@ -141,7 +156,7 @@ impl<TCBA: TransmissionControlBlockAbstractions> RecentConnectionDataProvider<TC
 	fn recent_connection_data(&self) -> RecentConnectionData
 	{
 		let (smoothed_round_trip_time, round_trip_time_variance) = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().smoothed_round_trip_time_and_round_trip_time_variance();
-		RecentConnectionData::new(smoothed_round_trip_time, round_trip_time_variance, self.congestion_control.ssthresh())
+		RecentConnectionData::new(smoothed_round_trip_time, round_trip_time_variance, self.congestion_control.ssthresh(), self.explicit_congestion_notification_fall_back())
 	}
 }
 
@@ -265,12 +280,32 @@ impl<TCBA: TransmissionControlBlockAbstractions> SendPacketTransmissionControlBl
 		self.RCV.NXT()
 	}
 	
+	#[inline(always)]
+	fn maximum_payload_size_excluding_synchronize_and_finish(&mut self, now: MonotonicMillisecondTimestamp, padded_options_size: usize) -> u32
+	{
+		TransmissionControlBlock::maximum_payload_size_excluding_synchronize_and_finish(self, now, padded_options_size)
+	}
+	
 	#[inline(always)]
 	fn receive_segment_window_size(&self) -> SegmentWindowSize
 	{
 		self.RCV.segment_window_size()
 	}
 	
+	#[inline(always)]
+	fn selective_acknowledgment_option(&mut self, maximum_number_of_blocks: usize) -> Option<SelectiveAcknowledgmentOption>
+	{
+		// RFC 2018 Section 4: "If the data receiver has received a SACK-Permitted option on the SYN for this connection, the data receiver MAY elect to generate SACK options as described below".
+		if self.selective_acknowledgments_permitted
+		{
+			self.RCV.selective_acknowledgment_option(maximum_number_of_blocks)
+		}
+		else
+		{
+			None
+		}
+	}
+	
 	#[inline(always)]
 	fn transmitted(&mut self, now: MonotonicMillisecondTimestamp, starts_at: SequenceNumber, data_length_excluding_length_of_synchronize_and_finish_controls: u32, flags: Flags)
 	{
@@ -283,6 +318,11 @@ impl<TCBA: TransmissionControlBlockAbstractions> SendPacketTransmissionControlBl
 			flags
 		};
 		
+		if unlikely!(flags.contains(Flags::Finish))
+		{
+			self.SND.finish_transmitted(starts_at + data_length_excluding_length_of_synchronize_and_finish_controls);
+		}
+		
 		self.SND.transmitted(now, starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags)
 	}
 }
@@ -313,22 +353,150 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 		self.set_state(State::Established);
 		self.events_receiver.entered_state_established();
 	}
+	
+	/// RFC 793 Page 75: "Enter the TIME-WAIT state. Start the time-wait timer, turn off the other timers".
+	#[inline(always)]
+	pub(crate) fn enter_state_time_wait(&mut self, alarms: &Alarms<TCBA>)
+	{
+		self.keep_alive_alarm.cancel(alarms);
+		self.cancel_retransmission_and_zero_window_probe_alarm(alarms);
+		self.user_time_out_alarm.cancel(alarms);
+		self.pacing_alarm.cancel(alarms);
+		self.delayed_acknowledgment_alarm.cancel(alarms);
+		self.set_state(State::TimeWait);
+		self.time_wait_alarm.schedule(alarms, alarms.time_wait_time);
+	}
+	
+	/// RFC 793 Page 73: "The only thing that can arrive in this state is a retransmission of the remote FIN. Acknowledge it, and restart the 2 MSL timeout".
+	#[inline(always)]
+	pub(crate) fn restart_time_wait_alarm(&mut self, alarms: &Alarms<TCBA>)
+	{
+		self.time_wait_alarm.cancel(alarms);
+		self.time_wait_alarm.schedule(alarms, alarms.time_wait_time);
+	}
+	
+	/// RFC 6191 Section 2: Can a `SYN` received in the TimeWait state be honoured because it is for a new incarnation of the connection?
+	///
+	/// This uses timestamps if they were in use on the previous incarnation or are requested for the new incarnation; otherwise it falls back to the sequence number test of RFC 1122 Section 4.2.2.13.
+	#[inline(always)]
+	pub(crate) fn rfc_6191_synchronize_is_for_a_new_incarnation(&self, SEG_SEQ: WrappingSequenceNumber, SEG_timestamps: Option<&TimestampsOption>) -> bool
+	{
+		debug_assert_eq!(self.state(), State::TimeWait, "state is not TimeWait");
+		
+		// "... the Sequence Number of the incoming SYN segment is greater than the last sequence number seen on the previous incarnation of the connection (for that direction of the data transfer) ...".
+		let sequence_number_is_greater = SEG_SEQ > self.RCV.NXT();
+		
+		match (self.timestamping.as_ref(), SEG_timestamps)
+		{
+			// "If TCP Timestamps would be enabled for the new incarnation of the connection, and the timestamp contained in the incoming SYN segment is greater than the last timestamp seen on the previous incarnation of the connection ... honor the connection request".
+			// "If TCP Timestamps would be enabled for the new incarnation of the connection, the timestamp contained in the incoming SYN segment is equal to the last timestamp seen on the previous incarnation of the connection ... and the Sequence Number of the incoming SYN segment is greater than the last sequence number seen ... honor the connection request".
+			(Some(timestamping), Some(SEG_timestamps)) =>
+			{
+				let SEG_TSval = SEG_timestamps.TSval;
+				timestamping.is_TS_Recent_less_than(SEG_TSval) || (timestamping.is_TS_Recent_equal_to(SEG_TSval) && sequence_number_is_greater)
+			}
+			
+			// "If TCP Timestamps would not be enabled for the new incarnation of the connection, but the Sequence Number of the incoming SYN segment is greater than the last sequence number seen ... honor the connection request".
+			(_, None) => sequence_number_is_greater,
+			
+			// "If the previous incarnation of the connection did not use Timestamps ... If TCP Timestamps would be enabled for the new incarnation of the connection, honor the incoming connection request".
+			(None, Some(_)) => true,
+		}
+	}
 }
 
-/// New connections and related functionality.
+/// Receipt.
 impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 {
+	/// Delivers in-order data (which starts at `RCV.NXT`) to the application, then any held out-of-order data which has now become contiguous, and advances `RCV.NXT` over both.
 	#[inline(always)]
-	pub(crate) fn acknowledgment_of_new_data_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, SEG: &ParsedTcpSegment<TCBA>, now: MonotonicMillisecondTimestamp, timestamps_option: Option<&TimestampsOption>, explicit_congestion_echo: bool) -> bool
+	pub(crate) fn received_in_order_data(&mut self, data: &[u8])
 	{
-		// TODO: RFC 6298 Section 5: "(5.1) Every time a packet containing data is sent (including a retransmission), if the timer is not running, start it running so that it will expire after RTO seconds (for the current value of RTO)
-		//
-		// This advice can also be interpreted for zero-window probes.
-		x;
+		if likely!(!data.is_empty())
+		{
+			self.events_receiver.received(data);
+		}
+		
+		let events_receiver = &self.events_receiver;
+		self.RCV.advance_NXT_over_in_order_data(data.len(), |released_data| events_receiver.received(released_data));
+	}
+	
+	#[inline(always)]
+	pub(crate) fn peer_finished(&self)
+	{
+		self.events_receiver.peer_finished();
+	}
+}
+
+/// Delayed acknowledgments.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	/// Returns `true` if the acknowledgment of in-order data just received has been delayed, ie should not be sent now.
+	///
+	/// RFC 1122 Section 4.2.3.2: "A TCP SHOULD implement a delayed ACK, but an ACK should not be excessively delayed; in particular, the delay MUST be less than 0.5 seconds, and in a stream of full-sized segments there SHOULD be an ACK for at least every second segment".
+	#[inline(always)]
+	pub(crate) fn delay_acknowledgment_of_received_data(&mut self, alarms: &Alarms<TCBA>, now: MonotonicMillisecondTimestamp, payload_length: usize) -> bool
+	{
+		let retransmission_time_out = self.retransmission_time_out();
+		
+		if self.RCV.acknowledgment_of_received_data_should_not_be_delayed(now, retransmission_time_out, payload_length)
+		{
+			return false
+		}
 		
+		if self.delayed_acknowledgment_alarm.is_cancelled()
+		{
+			self.delayed_acknowledgment_alarm.schedule(alarms, alarms.delayed_acknowledgment_time)
+		}
+		
+		true
+	}
+	
+	/// An acknowledgment of `RCV.NXT` has been sent, either by itself or piggybacked on a segment containing data or a `FIN`; any delayed acknowledgment is no longer needed.
+	#[inline(always)]
+	pub(crate) fn acknowledgment_sent(&mut self, alarms: &Alarms<TCBA>)
+	{
+		self.RCV.acknowledgment_sent();
+		self.delayed_acknowledgment_alarm.cancel(alarms);
+	}
+	
+	/// Sends any delayed acknowledgment now, eg because the explicit congestion echo flag it should carry is about to change (RFC 8257 Section 3.2).
+	#[inline(always)]
+	pub(crate) fn send_delayed_acknowledgment_now(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp)
+	{
+		if self.delayed_acknowledgment_alarm.is_cancelled()
+		{
+			return
+		}
 		
+		self.delayed_acknowledgment_alarm.cancel(interface.alarms());
+		self.send_delayed_acknowledgment(interface, now)
+	}
+	
+	/// RFC 5681 Section 4.2: "... MUST be generated within 500 ms of the arrival of the first unacknowledged packet".
+	///
+	/// If a packet can not be allocated the acknowledgment will be sent with the next segment or be provoked by the remote retransmitting.
+	#[inline(always)]
+	pub(crate) fn send_delayed_acknowledgment(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp)
+	{
+		if likely!(interface.send_acknowledgment_without_packet_to_reuse(self, now).is_ok())
+		{
+			self.RCV.acknowledgment_sent();
+		}
+	}
+}
+
+/// New connections and related functionality.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	#[inline(always)]
+	pub(crate) fn acknowledgment_of_new_data_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, SEG: &ParsedTcpSegment<TCBA>, now: MonotonicMillisecondTimestamp, timestamps_option: Option<&TimestampsOption>, explicit_congestion_echo: bool) -> bool
+	{
+		let SEG_ACK = SEG.ACK;
 		self.SND.update_window(SEG, now);
-		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.SND.move_UNA(SEG, explicit_congestion_echo);
+		let acknowledgment_had_explicit_congestion_echo = explicit_congestion_echo;
+		let round_trip_time_measured_using_timestamps = self.measurement_of_round_trip_time_using_timestamps(now, timestamps_option);
+		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.SND.move_UNA(SEG, explicit_congestion_echo, now, round_trip_time_measured_using_timestamps);
 		
 		// RFC 5681 Section 3.2 Paragraph 2: "The fast retransmit algorithm uses the arrival of 3 duplicate ACKs (as defined in section 2, without any intervening ACKs which move SND.UNA) as an indication that a segment has been lost.
 		// After receiving 3 duplicate ACKs, TCP performs a retransmission of what appears to be the missing segment, without waiting for the retransmission timer to expire".
@@ -348,7 +516,53 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			}
 		}
 		
-		self.congestion_control.increase_bytes_acknowledged(bytes_acknowledged);
+		if let Some(delivery_rate_sample) = self.SND.delivery_rate_sample()
+		{
+			self.congestion_control.delivery_rate_sampled(&delivery_rate_sample, now);
+		}
+		
+		self.congestion_control.explicit_congestion_notification_feedback(bytes_acknowledged, acknowledgment_had_explicit_congestion_echo, now);
+		
+		let was_in_fast_recovery = self.congestion_control.is_in_fast_recovery();
+		let is_a_partial_acknowledgment_during_fast_recovery = self.congestion_control.increase_bytes_acknowledged(bytes_acknowledged, SEG_ACK, now);
+		if was_in_fast_recovery && !self.congestion_control.is_in_fast_recovery()
+		{
+			self.SND.recent_acknowledgment_loss_recovery_exited();
+		}
+		
+		self.detect_spurious_retransmission_time_out(SEG_ACK, bytes_acknowledged, timestamps_option, acknowledgment_had_explicit_congestion_echo);
+		
+		self.tail_loss_probe_acknowledged(SEG_ACK, now);
+		
+		// RFC 8985 Section 6.2: Losses are detected on every acknowledgment; if in loss recovery, segments marked as lost are retransmitted below.
+		if self.detect_losses_using_recent_acknowledgment(interface.alarms(), now) && !self.congestion_control.is_in_fast_recovery()
+		{
+			if unlikely!(self.enter_loss_recovery_because_segments_were_detected_as_lost_returning_true_if_failed(interface, now))
+			{
+				return true
+			}
+		}
+		
+		if is_a_partial_acknowledgment_during_fast_recovery
+		{
+			let failed = if self.congestion_control.is_in_selective_acknowledgment_loss_recovery()
+			{
+				self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(bytes_acknowledged);
+				
+				// RFC 6675 Section 5 Step (C): "If cwnd - pipe >= 1 SMSS, the sender SHOULD transmit one or more segments".
+				self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+			}
+			else
+			{
+				// RFC 6582 Section 3.2 Step 3: "Partial acknowledgments: ... In this case, retransmit the first unacknowledged segment".
+				self.retransmit_earliest_unacknowledged_segment_returning_true_if_failed(interface, now)
+			};
+			
+			if unlikely!(failed)
+			{
+				return true
+			}
+		}
 		
 		// RFC 3168 Section 6.1.2 Paragraph 2: "TCP should not react to congestion indications more than once every window of data (or more loosely, more than once every round-trip time).
 		// That is, the TCP sender's congestion window should be reduced only once in response to a series of dropped and/or CE packets from a single window of data.
@@ -359,6 +573,8 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			{
 				explicit_congestion_notification_state.incoming_data_packet_had_explicit_congestion_echo_flag_set();
 				
+				self.congestion_control.explicit_congestion_echo_received(now);
+				
 				// TODO: RFC 3168 6.1.2 Paragraph 3: "... the sending TCP MUST reset the retransmit timer on receiving the ECN-Echo packet when the congestion window is one".
 				//
 				// \* A congestion window of one (1) means `cwnd` is 1 x (Sending) MSS.
@@ -371,33 +587,30 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			}
 		}
 		
-		if self.all_data_acknowledged()
+		if self.all_data_acknowledged() && self.send_window_is_zero() && self.SND.has_data_buffered_but_not_transmitted()
 		{
-			if self.send_window_is_zero()
+			if unlikely!(self.send_zero_window_probe_returning_true_if_failed(interface, now, true))
 			{
-				if unlikely!(self.send_zero_window_probe_returning_true_if_failed(interface, now, true))
-				{
-					return true;
-				}
+				return true;
 			}
-			else
+		}
+		else
+		{
+			// We have some space to send some data after ACKing; we may have data buffered but not sent, eg due to SND.WND or congestion control.
+			if unlikely!(self.transmit_buffered_data_returning_true_if_failed(interface, now))
 			{
-				// TODO: we have some space to send some data after ACKing. We may have data buffered but not sent, eg due to SND.WND or congestion control.
-				// But see RFC 5681 - and whether we're allowed to.
-				// self.SND.data_buffered_but_not_transmitted()
+				return true;
 			}
 		}
-		else
+		
+		// Acknowledging data creates send buffer space.
+		// TODO: This could be inefficient if not using LRO / GRO, so we may want a combined 'last woke up' / 'minimum data space available'; we could combine this with small window / slow send to try to defeat slowloris like attacks.
+		if bytes_acknowledged != 0
 		{
-			// TODO: we have some space to send some data after ACKing. We may have data buffered but not sent, eg due to SND.WND or congestion control.
-			// But see RFC 5681 - and whether we're allowed to.
-			// self.SND.data_buffered_but_not_transmitted()
+			let send_buffer_space_available = self.SND.send_buffer_space_available();
+			self.events_receiver.writable(send_buffer_space_available);
 		}
 		
-		// TODO: Waking up for write() (or close)
-		// We ought to wake up the sender every time we ack data, as it creates send buffer space.
-		// This could be inefficient if not using LRO / GRO, so we may want a combined 'last woke up' / 'minimum data space available'
-		// We could combine this with small window / slow send to try to defeat slowloris like attacks.
 		// RFC 5681 Section 4.1: "TCP SHOULD set cwnd to no more than RW (the restart window) before beginning transmission if the TCP has not sent data in an interval exceeding the retransmission timeout".
 		
 		// RFC 6296 Section 5: "An implementation MUST manage the retransmission timer(s) in such a way that a segment is never retransmitted too early, i.e., less than one RTO after the previous transmission of that segment".
@@ -408,17 +621,57 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 		//
 		// RFC 1122 Section 4.2.2.17: hints that we should turn-on the zero window probe timer when all outstanding data has been acknowledged and the send window is zero.
 		
-		// TODO: Post-FIN, we no longer send zero window probes.
 		self.schedule_or_cancel_retransmission_and_zero_window_probe_alarm_as_appropriate(interface.alarms());
 		
+		// RFC 8985 Section 7.2: "... the sender SHOULD start or restart a loss probe PTO timer ... after receiving an ACK ...".
+		self.schedule_tail_loss_probe_if_appropriate(interface.alarms());
+		
 		// TODO: do we do an immediate re-transmit here for all packets in the retransmit queue whose timestamp exceeds the RTO before starting the timer?
 		// ie go through retransmit queue, check for packets (originally transmitted - now) >= RTO, resend; set the timer for ((originally transmitted) + RTO) for the first packet otherwise. This will handle stretch ACKs and TSO better I think.
 		
 		false
 	}
 	
+	/// Data is delivered to the events receiver as soon as it is in order, so there is never any data 'left to pass to receive'; this call only reports whether more data may yet arrive.
+	///
+	/// Returns an error if the remote has sent its `FIN` (RFC 793 Section 3.7 Page 59: "error: connection closing").
+	#[inline(always)]
+	pub(crate) fn RECEIVE(&mut self, interface: &Interface<TCBA>) -> Result<(), ()>
+	{
+		use self::State::*;
+		
+		match self.state()
+		{
+			Closed => unreachable_synthetic_state!("TCP state Closed is never actually used"),
+			
+			Listen => unreachable_synthetic_state!("TCP state Listen is replaced with SYN flood defences"),
+			
+			// RFC 793 Section 3.7 Page 58: "Queue for processing after entering ESTABLISHED state".
+			SynchronizeSent => Ok(()),
+			
+			SynchronizeReceived => unreachable_synthetic_state!("TCP state SynchronizeReceived is replaced with SYN flood defences"),
+			
+			// RFC 793 Section 3.7 Page 58: "If insufficient incoming segments are queued to satisfy the request, queue the request".
+			//
+			// After a half-close, FinishWait1 and FinishWait2 continue to receive data until the remote's FIN arrives.
+			Established | FinishWait1 | FinishWait2 => Ok(()),
+			
+			// RFC 793 Section 3.7 Page 59: "Since the remote side has already sent FIN, RECEIVEs must be satisfied by text already on hand, but not yet delivered to the user.
+			// If no text is awaiting delivery, the RECEIVE will get a "error: connection closing" response".
+			CloseWait => Err(()),
+			
+			// RFC 793 Section 3.7 Page 59: "Return "error: connection closing"".
+			Closing | LastAcknowledgment | TimeWait => Err(()),
+		}
+	}
+	
+	/// Half-close, also known as 'shutdown write'.
+	///
+	/// This is the RFC 793 CLOSE call for the Established and CloseWait states: a `FIN` is queued to be sent after all preceding SENDs, and data continues to be received (in FinishWait1 and FinishWait2) until the remote's `FIN` arrives.
+	///
+	/// Unlike CLOSE, this is an error if the connection is not yet established or if a `FIN` has already been queued.
 	#[inline(always)]
-	pub(crate) fn RECEIVE(&mut self, interface: &Interface<TCBA>)
+	pub(crate) fn half_close(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> Result<(), ()>
 	{
 		use self::State::*;
 		
@@ -428,32 +681,35 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			
 			Listen => unreachable_synthetic_state!("TCP state Listen is replaced with SYN flood defences"),
 			
-			SynchronizeSent =>
-			{
-				// TODO: Queue data.
-			}
+			SynchronizeSent => Err(()),
 			
 			SynchronizeReceived => unreachable_synthetic_state!("TCP state SynchronizeReceived is replaced with SYN flood defences"),
 			
-			Established | FinishWait1 | FinishWait2 =>
+			Established => if unlikely!(self.queue_finish_and_enter_state_returning_true_if_failed(interface, now, FinishWait1))
 			{
-				// TODO: OK
+				Err(())
 			}
-			
-			CloseWait =>
+			else
 			{
-				// TODO: Error connection closing if nothing left to pass to receive
-			}
+				Ok(())
+			},
 			
-			Closing | LastAcknowledgment | TimeWait =>
+			CloseWait => if unlikely!(self.queue_finish_and_enter_state_returning_true_if_failed(interface, now, LastAcknowledgment))
 			{
-				// TODO: Error connection closing
+				Err(())
 			}
+			else
+			{
+				Ok(())
+			},
+			
+			FinishWait1 | FinishWait2 | Closing | LastAcknowledgment | TimeWait => Err(()),
 		}
 	}
 	
+	/// Returns `true` if transmitting the `FIN` failed; the connection will then have been aborted.
 	#[inline(always)]
-	pub(crate) fn CLOSE(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp)
+	pub(crate) fn CLOSE(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
 	{
 		use self::State::*;
 		
@@ -463,7 +719,11 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			
 			Listen => unreachable_synthetic_state!("TCP state Listen is replaced with SYN flood defences"),
 			
-			SynchronizeSent => self.closed(interface, now),
+			SynchronizeSent =>
+			{
+				self.closed(interface, now);
+				false
+			}
 			
 			SynchronizeReceived => unreachable_synthetic_state!("TCP state SynchronizeReceived is replaced with SYN flood defences"),
 			
@@ -471,26 +731,41 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			{
 				// RFC 793 Section 3.7 Page 60: "Queue this until all preceding SENDs have been segmentized, then form a FIN segment and send it.
 				// In any case, enter FIN-WAIT-1 state".
+				self.queue_finish_and_enter_state_returning_true_if_failed(interface, now, FinishWait1)
 			}
 			
 			FinishWait1 | FinishWait2 =>
 			{
 				// RFC 793 Section 3.7 Page 60: "Strictly speaking, this is an error and should receive a "error: connection closing" response.
 				// An "ok" response would be cceptable, too, as long as a second FIN is not emitted (the firstFIN may be retransmitted though)".
+				false
 			}
 			
 			CloseWait =>
 			{
-				//  RFC 793 Section 3.7 Page 60: "Queue this request until all preceding SENDs have been segmentized; then send a FIN segment, enter CLOSING state".
-				// TODO: Error connection closing if nothing left to pass to receive
+				// RFC 793 Section 3.7 Page 60: "Queue this request until all preceding SENDs have been segmentized; then send a FIN segment, enter CLOSING state".
+				//
+				// RFC 1122 Section 4.2.2.20 (a) and RFC 793 Errata 3301 correct this to be the LAST-ACK state; CLOSING is only entered on a simultaneous close.
+				self.queue_finish_and_enter_state_returning_true_if_failed(interface, now, LastAcknowledgment)
 			}
 			
 			Closing | LastAcknowledgment | TimeWait =>
 			{
-				// TODO: Error connection closing
+				// RFC 793 Section 3.7 Page 61: "Respond with "error: connection closing"".
+				false
 			}
 		}
 	}
+	
+	/// If transmission fails the connection will have been aborted.
+	#[inline(always)]
+	fn queue_finish_and_enter_state_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp, state: State) -> bool
+	{
+		self.SND.queue_finish();
+		self.set_state(state);
+		
+		self.transmit_buffered_data_returning_true_if_failed(interface, now)
+	}
 }
 
 /// State change.
@@ -527,7 +802,7 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 	}
 	
 	#[inline(always)]
-	fn closed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp)
+	pub(crate) fn closed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp)
 	{
 		self.events_receiver.closed();
 		interface.destroy_transmission_control_block(&self.key)
@@ -546,12 +821,22 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 		self.keep_alive_alarm.cancel(alarms);
 		self.retransmission_and_zero_window_probe_alarm.cancel(alarms);
 		self.user_time_out_alarm.cancel(alarms);
+		self.time_wait_alarm.cancel(alarms);
+		self.delayed_acknowledgment_alarm.cancel(alarms);
+		self.pacing_alarm.cancel(alarms);
 	}
 }
 
 /// Transmission
 impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 {
+	/// Congestion Window Validation (RFC 7661) is used for connections which are often limited by the rate at which the application supplies data rather than by `cwnd`.
+	#[inline(always)]
+	pub(crate) fn use_congestion_window_validation(&mut self)
+	{
+		self.congestion_control.use_congestion_window_validation()
+	}
+	
 	#[inline(always)]
 	pub(crate) fn maximum_payload_size_excluding_synchronize_and_finish(&mut self, now: MonotonicMillisecondTimestamp, padded_options_size: usize) -> u32
 	{
@@ -566,8 +851,9 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 		self.congestion_control.maximum_data(self.SND.rwnd())
 	}
 	
+	/// Returns the number of bytes buffered, or an error if the connection is closing (RFC 793 Section 3.7 Page 57: "Return "error: connection closing" and do not service request").
 	#[inline(always)]
-	pub(crate) fn SEND<DataWriter: Fn(&mut [u8]) -> usize>(&mut self, interface: &Interface<TCBA>, data_writer: DataWriter, now: MonotonicMillisecondTimestamp)
+	pub(crate) fn SEND<DataWriter: Fn(&mut [u8]) -> usize>(&mut self, interface: &Interface<TCBA>, data_writer: DataWriter, now: MonotonicMillisecondTimestamp) -> Result<usize, ()>
 	{
 		use self::State::*;
 		
@@ -577,133 +863,820 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 			
 			Listen => unreachable_synthetic_state!("TCP state Listen is replaced with SYN flood defences"),
 			
-			SynchronizeSent => self.SND.buffer_data_to_send(data_writer),
+			SynchronizeSent => Ok(self.SND.buffer_data_to_send(data_writer)),
 			
 			SynchronizeReceived => unreachable_synthetic_state!("TCP state SynchronizeReceived is replaced with SYN flood defences 'process_for_acknowledgment_of_syncookie'"),
 			
 			Established | CloseWait =>
 			{
-				self.SND.buffer_data_to_send(data_writer);
-				
-				let mut maximum_data = Bytes(self.maximum_data(now));
-				
-				/*
-					Create an rte_mbuf chain
-						- header
-						- data (one or two) [upto self.maximum_segment_payload_size() for each segment]
-						- header
-						- data (one or two)
-						- etc
-					
-					
-					Create 'packet' objects.
-					Need one packet for header, one for payload, potentially one extra for payload if using physical addressing.
-				
-				if (unsafe { rte_eal_iova_mode() } == rte_iova_mode::RTE_IOVA_VA)
-				{
-				}
-				
-				magic_ring_buffer.read_buffer_slice() or magic_ring_buffer.physical_read_buffers_slice()
-				
-				*/
-				
-				self.SND.magic_ring_buffer.read_buffer_slice(maximum_data);
-				self.SND.magic_ring_buffer.physical_read_buffers_slice(maximum_data);
-				
-				
-				
-				
-				// TODO: self.congestion_control.last_sent_data_at(now);
-				
-				
-				
-				
-				
-				
-				DDDDDD
+				let buffered = self.SND.buffer_data_to_send(data_writer);
 				
-				
-				
-				
-				
-				
-				
-				
-				
-				self.data_to_transmit_commit(total_sent);
-				
-				// How much data can we transmit?
-				if self.SND.retransmission_queue_is_not_full()
+				if unlikely!(self.transmit_buffered_data_returning_true_if_failed(interface, now))
 				{
-					// what's the cwnd budget?
-					
-					// it's a case of filling segments..
+					return Err(())
 				}
 				
-				// populate self.congestion_control.last_sent_data_at = now;
-				xxx;
+				Ok(buffered)
 			}
 			
-			FinishWait1 | FinishWait2 | Closing | LastAcknowledgment | TimeWait => panic!("Connection closing"),
+			FinishWait1 | FinishWait2 | Closing | LastAcknowledgment | TimeWait => Err(()),
 		}
 	}
-}
-
-/// User time out.
-impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
-{
-	#[inline(always)]
-	pub(crate) fn schedule_user_time_out_alarm_for_connection(&mut self, alarms: &Alarms<TCBA>, connection_time_out: MillisecondDuration)
-	{
-		self.debug_assert_action_is_only_valid_in_sychronize_sent_state();
-		self.debug_assert_we_are_the_client();
-		
-		self.user_time_out_alarm.schedule(alarms, TickDuration::milliseconds_to_ticks_rounded_up(connection_time_out))
-	}
-}
-
-/// Retransmission and Zero-Window Probing.
-impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
-{
-	#[inline(always)]
-	fn cancel_retransmission_and_zero_window_probe_alarm(&mut self, alarms: &Alarms<TCBA>)
-	{
-		self.retransmission_and_zero_window_probe_alarm.cancel(alarms);
-		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().reset_retransmissions();
-	}
 	
+	/// Segmentizes data which has been buffered but not yet transmitted and sends as much of it as the congestion window (`cwnd`), the send window (`SND.WND`, also known as `rwnd`) and the retransmission queue permit.
+	///
+	/// Each segment is no larger than `maximum_payload_size_excluding_synchronize_and_finish()`.
+	///
+	/// If a `FIN` has been queued by `CLOSE` and all buffered data has now been transmitted, the `FIN` is sent.
+	///
+	/// If sending fails (eg because a packet could not be allocated), the connection is aborted.
 	#[inline(always)]
-	pub(crate) fn schedule_or_cancel_retransmission_and_zero_window_probe_alarm_as_appropriate(&mut self, alarms: &Alarms<TCBA>)
+	pub(crate) fn transmit_buffered_data_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
 	{
-		if self.retransmission_and_zero_window_probe_alarm.is_scheduled() && self.all_data_acknowledged() && self.send_window_is_non_zero()
+		// RFC 5681 Section 2: "At any given time, a TCP MUST NOT send data with a sequence number higher than the sum of the highest acknowledged sequence number and the minimum of cwnd and rwnd".
+		let maximum_data = self.maximum_data(now);
+		
+		let pacing_rate = self.pacing_rate(interface);
+		let mut held_back_by_pacing = false;
+		
+		let mut transmitted_data = false;
+		while self.SND.has_data_buffered_but_not_transmitted() && self.SND.retransmission_queue_is_not_full()
 		{
-			self.cancel_retransmission_and_zero_window_probe_alarm(alarms);
+			let sequence_numbers_in_flight = self.SND.sequence_numbers_in_flight();
+			if sequence_numbers_in_flight >= maximum_data
+			{
+				break
+			}
+			let usable_window = maximum_data - sequence_numbers_in_flight;
+			
+			if let Some(pacing_rate) = pacing_rate
+			{
+				let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+				if !self.pacing_alarm_behaviour_mutable_reference().may_transmit(now, pacing_rate, sender_maximum_segment_size)
+				{
+					held_back_by_pacing = true;
+					break
+				}
+			}
+			
+			if unlikely!(self.transmit_new_data_returning_true_if_failed(interface, now, usable_window, pacing_rate))
+			{
+				return true
+			}
+			transmitted_data = true;
 		}
-		else
+		
+		self.schedule_or_cancel_pacing_alarm_as_appropriate(interface.alarms(), pacing_rate, held_back_by_pacing);
+		
+		// draft-cheng-iccrg-delivery-rate-estimation Section 3.4: All buffered data has been sent but the congestion window and send window would permit more; delivery rate samples are marked as application limited until the data now in flight has been delivered.
+		if !self.SND.has_data_buffered_but_not_transmitted() && self.SND.sequence_numbers_in_flight() < maximum_data
 		{
-			if self.retransmission_and_zero_window_probe_alarm.is_cancelled()
+			self.SND.application_limited();
+		}
+		
+		// RFC 793 Section 3.7 Page 60: "Queue this until all preceding SENDs have been segmentized, then form a FIN segment and send it".
+		let transmitted_finish = if self.SND.finish_is_ready_to_transmit()
+		{
+			if unlikely!(interface.send_finish(self, now).is_err())
 			{
-				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().reset_retransmissions();
-				self.retransmission_and_zero_window_probe_alarm.schedule(alarms, self.retransmission_time_out_tick_duration())
+				self.aborted(interface, now);
+				return true
 			}
+			true
 		}
-	}
-	
-	/// Processing Incoming Segments 4.5.2.2: "... compute a new estimate of round-trip time.
-	/// If Snd.TS.OK bit is on, use Snd.TSclock - SEG.TSecr; otherwise, use the elapsed time since the first segment in the retransmission queue was sent".
+		else
+		{
+			false
+		};
+		
+		if transmitted_data
+		{
+			self.congestion_control.last_sent_data_at(now);
+		}
+		
+		if transmitted_data || transmitted_finish
+		{
+			// RFC 1122 Section 4.2.3.2: "... an ACK should be piggybacked on a segment being transmitted if possible".
+			self.acknowledgment_sent(interface.alarms());
+			
+			// RFC 6298 Section 5: "(5.1) Every time a packet containing data is sent (including a retransmission), if the timer is not running, start it running so that it will expire after RTO seconds (for the current value of RTO)".
+			//
+			// A `FIN` occupies sequence space and so is retransmitted in the same way as data.
+			self.start_retransmission_and_zero_window_probe_alarm_if_not_running(interface.alarms());
+			
+			// RFC 8985 Section 7.2: "... the sender SHOULD start or restart a loss probe PTO timer after transmitting new data ...".
+			self.schedule_tail_loss_probe_if_appropriate(interface.alarms());
+		}
+		else if self.send_window_is_zero() && self.all_data_acknowledged() && self.SND.has_data_buffered_but_not_transmitted()
+		{
+			// RFC 1122 Section 4.2.2.17: "Probing of zero (offered) windows MUST be supported".
+			self.start_retransmission_and_zero_window_probe_alarm_if_not_running(interface.alarms());
+		}
+		
+		false
+	}
+	
+	/// Transmits one segment of previously unsent data of no more than `usable_window` bytes.
+	///
+	/// If segments are being paced (`pacing_rate` is `Some`), the segment is charged against the pacing credit.
+	///
+	/// If sending fails (eg because a packet could not be allocated), the connection is aborted.
 	#[inline(always)]
-	fn compute_a_new_estimate_of_round_trip_time_for_a_fully_acknowledged_segment(&mut self, now: MonotonicMillisecondTimestamp, fully_acknowledged_segment_timestamp: MonotonicMillisecondTimestamp, timestamps_option: Option<&TimestampsOption>)
+	fn transmit_new_data_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp, usable_window: u32, pacing_rate: Option<u64>) -> bool
+	{
+		// The memory of the magic ring buffer is not owned by `self`, so it can be aliased whilst `self` is mutably borrowed to send.
+		let data_to_transmit = self.SND.data_to_transmit(usable_window) as *const [u8];
+		
+		match interface.send_data(unsafe { &*data_to_transmit }, self, now)
+		{
+			Err(()) =>
+			{
+				self.aborted(interface, now);
+				true
+			}
+			
+			Ok(payload_size) =>
+			{
+				self.SND.data_to_transmit_commit(payload_size);
+				
+				if pacing_rate.is_some()
+				{
+					self.pacing_alarm_behaviour_mutable_reference().transmitted(payload_size);
+				}
+				
+				false
+			}
+		}
+	}
+}
+
+/// Pacing.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	/// `None` if segments are not to be paced, including before a round trip time has been measured.
+	#[inline(always)]
+	fn pacing_rate(&self, interface: &Interface<TCBA>) -> Option<u64>
+	{
+		if !interface.pacing()
+		{
+			return None
+		}
+		
+		let (smoothed_round_trip_time, _round_trip_time_variance) = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().smoothed_round_trip_time_and_round_trip_time_variance();
+		self.congestion_control.pacing_rate(smoothed_round_trip_time).filter(|&pacing_rate| pacing_rate != 0)
+	}
+	
+	/// The pacing alarm is only scheduled whilst transmission is being held back, so idle connections do not cost anything when alarms are progressed.
+	#[inline(always)]
+	fn schedule_or_cancel_pacing_alarm_as_appropriate(&mut self, alarms: &Alarms<TCBA>, pacing_rate: Option<u64>, held_back_by_pacing: bool)
+	{
+		match pacing_rate
+		{
+			Some(pacing_rate) if held_back_by_pacing =>
+			{
+				let time_until_may_transmit = self.pacing_alarm.alarm_behaviour_reference().holding_back(pacing_rate);
+				if self.pacing_alarm.is_cancelled()
+				{
+					self.pacing_alarm.schedule(alarms, TickDuration::milliseconds_to_ticks_rounded_up(time_until_may_transmit))
+				}
+			}
+			
+			_ => self.pacing_alarm.cancel(alarms),
+		}
+	}
+	
+	#[inline(always)]
+	fn pacing_alarm_behaviour_mutable_reference(&mut self) -> &mut PacingAlarmBehaviour<TCBA>
+	{
+		self.pacing_alarm.alarm_behaviour_mutable_reference()
+	}
+}
+
+/// Fast retransmit and fast recovery.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	/// RFC 5681 Section 3.2, as modified by RFC 6582 Section 3.2 (NewReno), or, if selective acknowledgments are in use, RFC 6675 Section 5.
+	#[inline(always)]
+	pub(crate) fn duplicate_acknowledgment_received_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		self.congestion_control.increment_duplicate_acknowledgments_received_without_any_intervening_acknwoledgments_which_moved_SND_UNA();
+		
+		self.congestion_control.duplicate_acknowledgment_after_retransmission_time_out();
+		
+		if self.selective_acknowledgments_permitted
+		{
+			return self.selective_acknowledgment_duplicate_acknowledgment_received_returning_true_if_failed(interface, now)
+		}
+		
+		if self.congestion_control.is_in_fast_recovery()
+		{
+			self.congestion_control.additional_duplicate_acknowledgment_during_fast_recovery();
+			
+			// RFC 5681 Section 3.2 Step 5: "When previously unsent data is available and the new value of cwnd and the receiver's advertised window allow, a TCP SHOULD send 1*SMSS bytes of previously unsent data".
+			return self.transmit_buffered_data_returning_true_if_failed(interface, now)
+		}
+		
+		let SND_UNA_less_one = self.SND.UNA_less_one();
+		let SND_NXT = self.SND.NXT();
+		if self.congestion_control.enter_fast_retransmit_and_fast_recovery_if_third_duplicate_acknowledgment(SND_UNA_less_one, SND_NXT, now)
+		{
+			// RFC 5681 Section 3.2 Step 3: "The lost segment starting at SND.UNA MUST be retransmitted ...".
+			if unlikely!(self.retransmit_earliest_unacknowledged_segment_returning_true_if_failed(interface, now))
+			{
+				return true
+			}
+			
+			// RFC 5681 Section 3.2 Step 5 applies equally after the retransmission, as far as proportional rate reduction (RFC 6937) permits.
+			return self.transmit_buffered_data_returning_true_if_failed(interface, now)
+		}
+		
+		false
+	}
+	
+	/// RFC 6675 Section 5.
+	#[inline(always)]
+	fn selective_acknowledgment_duplicate_acknowledgment_received_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		// RFC 8985: Loss recovery is entered when a segment is marked as lost, rather than when enough duplicate acknowledgments have been counted.
+		if self.SND.is_using_recent_acknowledgment_loss_detection()
+		{
+			let any_segments_newly_marked_as_lost = self.detect_losses_using_recent_acknowledgment(interface.alarms(), now);
+			
+			if self.congestion_control.is_in_fast_recovery()
+			{
+				self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(0);
+				return self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+			}
+			
+			if any_segments_newly_marked_as_lost
+			{
+				return self.enter_loss_recovery_because_segments_were_detected_as_lost_returning_true_if_failed(interface, now)
+			}
+			
+			return false
+		}
+		
+		// RFC 6675 Section 5 Step (C): "If cwnd - pipe >= 1 SMSS, the sender SHOULD transmit one or more segments".
+		if self.congestion_control.is_in_fast_recovery()
+		{
+			self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(0);
+			return self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+		}
+		
+		let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+		let earliest_unacknowledged_segment_is_lost = self.SND.earliest_unacknowledged_segment_is_lost(sender_maximum_segment_size);
+		let SND_UNA_less_one = self.SND.UNA_less_one();
+		let SND_NXT = self.SND.NXT();
+		if self.congestion_control.enter_selective_acknowledgment_loss_recovery_if_appropriate(SND_UNA_less_one, SND_NXT, earliest_unacknowledged_segment_is_lost, now)
+		{
+			self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(0);
+			
+			// RFC 6675 Section 5 Step (4.3): "Retransmit the first data segment presumed dropped -- the segment starting with sequence number HighACK + 1".
+			if unlikely!(self.retransmit_earliest_unacknowledged_segment_returning_true_if_failed(interface, now))
+			{
+				return true
+			}
+			
+			// RFC 6675 Section 5 Step (4.4): "Run SetPipe () ... If (cwnd - pipe) >= 1 SMSS, there exists previously unsent data, and the receiver's advertised window allows, transmit up to 1 SMSS of data starting with the octet HighData+1 ...".
+			return self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+		}
+		
+		false
+	}
+	
+	/// RFC 6937 Section 3.1: "On every ACK during recovery"; `bytes_cumulatively_acknowledged` is zero for a duplicate acknowledgment.
+	#[inline(always)]
+	fn proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(&mut self, bytes_cumulatively_acknowledged: u32)
+	{
+		let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+		let selectively_acknowledged = self.SND.sequence_numbers_selectively_acknowledged();
+		let pipe = self.SND.pipe(sender_maximum_segment_size);
+		self.congestion_control.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(bytes_cumulatively_acknowledged, selectively_acknowledged, pipe)
+	}
+	
+	/// Updates the selective acknowledgment scoreboard from an incoming `SACK` option; returns `true` if the option contained new information.
+	#[inline(always)]
+	pub(crate) fn selectively_acknowledged(&mut self, selective_acknowledgment_option: &SelectiveAcknowledgmentOption, timestamps_option: Option<&TimestampsOption>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		let round_trip_time_measured_using_timestamps = self.measurement_of_round_trip_time_using_timestamps(now, timestamps_option);
+		self.SND.selectively_acknowledged(selective_acknowledgment_option, now, round_trip_time_measured_using_timestamps)
+	}
+	
+	/// RFC 2883 Section 4 / RFC 3708 Section 3: A D-SACK block reporting a duplicate segment has been received.
+	///
+	/// If this shows that all the retransmissions of the current loss recovery episode were spurious, the congestion response is undone and the retransmission time out is no longer backed off.
+	///
+	/// RFC 8985 Section 6.2 Step 4 increases the reordering window, and Section 7.4.2 considers a tail loss probe which was a retransmission to have been unnecessary if it is the segment reported.
+	#[inline(always)]
+	pub(crate) fn duplicate_selective_acknowledgment_received(&mut self, duplicate_selective_acknowledgment_block: SelectiveAcknowledgmentBlock)
+	{
+		if self.congestion_control.duplicate_selective_acknowledgment_received(duplicate_selective_acknowledgment_block)
+		{
+			self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().reset_retransmissions();
+		}
+		
+		self.SND.recent_acknowledgment_duplicate_selective_acknowledgment_received();
+		
+		if let Some((tail_loss_probe_ends_at, true)) = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().tail_loss_probe()
+		{
+			if duplicate_selective_acknowledgment_block.right_edge_of_block == tail_loss_probe_ends_at
+			{
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().tail_loss_probe_ended();
+			}
+		}
+	}
+	
+	/// RFC 6675 Section 5 Step (C): "If cwnd - pipe >= 1 SMSS, the sender SHOULD transmit one or more segments as follows:
+	/// (C.1) The scoreboard MUST be queried via NextSeg () for the sequence number range of the next segment to transmit (if any), and the given segment sent.
+	/// ...
+	/// (C.5) If cwnd - pipe >= 1 SMSS, return to (C.1)".
+	#[inline(always)]
+	fn transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+		
+		let pacing_rate = self.pacing_rate(interface);
+		let mut held_back_by_pacing = false;
+		
+		let mut transmitted_data = false;
+		let mut transmitted_new_data = false;
+		loop
+		{
+			let pipe = self.SND.pipe(sender_maximum_segment_size);
+			if !self.congestion_control.congestion_window_permits_transmitting_a_segment_during_loss_recovery(pipe)
+			{
+				break
+			}
+			
+			// RFC 6675 Section 4 NextSeg () (1): "If there exists a smallest unSACKed sequence number 'S2' that meets the following three criteria for determining loss, the sequence range of one segment of up to SMSS octets starting with S2 MUST be returned".
+			if let Some((SEQ, data, flags)) = self.SND.next_segment_to_retransmit(sender_maximum_segment_size, true, now)
+			{
+				if unlikely!(self.retransmit_segment_returning_true_if_failed(interface, now, SEQ, data, flags))
+				{
+					return true
+				}
+				transmitted_data = true;
+				continue
+			}
+			
+			// RFC 6675 Section 4 NextSeg () (2): "If no sequence number 'S2' per rule (1) exists but there exists available unsent data and the receiver's advertised window allows, the sequence range of one segment of up to SMSS octets of previously unsent data starting with sequence number HighData+1 MUST be returned".
+			//
+			// Previously unsent data is paced as it is outside of loss recovery; retransmissions are not held back.
+			if self.SND.has_data_buffered_but_not_transmitted() && self.SND.retransmission_queue_is_not_full() && !held_back_by_pacing
+			{
+				let sequence_numbers_in_flight = self.SND.sequence_numbers_in_flight();
+				let rwnd = self.SND.rwnd();
+				if sequence_numbers_in_flight < rwnd
+				{
+					let may_transmit = match pacing_rate
+					{
+						None => true,
+						Some(pacing_rate) => self.pacing_alarm_behaviour_mutable_reference().may_transmit(now, pacing_rate, sender_maximum_segment_size),
+					};
+					
+					if may_transmit
+					{
+						let usable_window = min(rwnd - sequence_numbers_in_flight, sender_maximum_segment_size);
+						
+						if unlikely!(self.transmit_new_data_returning_true_if_failed(interface, now, usable_window, pacing_rate))
+						{
+							return true
+						}
+						transmitted_data = true;
+						transmitted_new_data = true;
+						continue
+					}
+					
+					held_back_by_pacing = true;
+				}
+			}
+			
+			// RFC 6675 Section 4 NextSeg () (3): "If the conditions for rules (1) and (2) fail, but there exists an unSACKed sequence number 'S3' that meets the criteria for detecting loss given in steps (1.a) and (1.b) above (specifically excluding step (1.c)), then one segment of up to SMSS octets starting with S3 SHOULD be returned".
+			//
+			// Rule (4), the rescue retransmission, is optional and not implemented.
+			if let Some((SEQ, data, flags)) = self.SND.next_segment_to_retransmit(sender_maximum_segment_size, false, now)
+			{
+				if unlikely!(self.retransmit_segment_returning_true_if_failed(interface, now, SEQ, data, flags))
+				{
+					return true
+				}
+				transmitted_data = true;
+				continue
+			}
+			
+			break
+		}
+		
+		// The pacing alarm is left as it is unless new data has been held back, as it may have been scheduled by `transmit_buffered_data_returning_true_if_failed()`.
+		if held_back_by_pacing
+		{
+			self.schedule_or_cancel_pacing_alarm_as_appropriate(interface.alarms(), pacing_rate, held_back_by_pacing);
+		}
+		
+		if transmitted_new_data
+		{
+			self.congestion_control.last_sent_data_at(now);
+		}
+		
+		if transmitted_data
+		{
+			// RFC 6298 Section 5: "(5.1) Every time a packet containing data is sent (including a retransmission), if the timer is not running, start it running so that it will expire after RTO seconds (for the current value of RTO)".
+			self.start_retransmission_and_zero_window_probe_alarm_if_not_running(interface.alarms());
+		}
+		
+		false
+	}
+	
+	/// RFC 6298 Section 5: "(5.4) Retransmit the earliest segment that has not been acknowledged by the TCP receiver".
+	///
+	/// Also used by fast retransmit (RFC 5681 Section 3.2 Step 3) and for partial acknowledgments during fast recovery (RFC 6582 Section 3.2 Step 3).
+	#[inline(always)]
+	fn retransmit_earliest_unacknowledged_segment_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		match self.SND.earliest_unacknowledged_segment_to_retransmit(now)
+		{
+			None => false,
+			
+			Some((SEQ, data, flags)) => self.retransmit_segment_returning_true_if_failed(interface, now, SEQ, data, flags),
+		}
+	}
+	
+	#[inline(always)]
+	fn retransmit_segment_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp, SEQ: WrappingSequenceNumber, data: *const [u8], flags: Flags) -> bool
+	{
+		if unlikely!(flags.contains(Flags::Synchronize))
+		{
+			return self.retransmit_synchronize_returning_true_if_failed(interface, now, flags)
+		}
+		
+		let flags = if unlikely!(flags.contains(Flags::Finish))
+		{
+			Flags::FinishAcknowledgment
+		}
+		else
+		{
+			Flags::Acknowledgment
+		};
+		
+		match interface.retransmit_data(unsafe { &*data }, SEQ, flags, self, now)
+		{
+			Err(()) =>
+			{
+				self.aborted(interface, now);
+				return true
+			}
+			
+			Ok(payload_size) => self.congestion_control.segment_retransmitted(payload_size as u32),
+		}
+		
+		// RFC 1122 Section 4.2.3.2: "... an ACK should be piggybacked on a segment being transmitted if possible".
+		self.acknowledgment_sent(interface.alarms());
+		false
+	}
+	
+	/// Our initial SYN is retransmitted with its options written afresh.
+	///
+	/// A SYN-ACK is sent statelessly with a syncookie and so is never in the retransmission queue; should one be, the connection can not make progress and is aborted.
+	#[inline(always)]
+	fn retransmit_synchronize_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp, flags: Flags) -> bool
+	{
+		if unlikely!(flags.contains(Flags::Acknowledgment))
+		{
+			self.aborted(interface, now);
+			return true
+		}
+		
+		if unlikely!(interface.retransmit_synchronize(self, now).is_err())
+		{
+			self.aborted(interface, now);
+			return true
+		}
+		
+		false
+	}
+}
+
+/// Loss detection using RACK and tail loss probes (RFC 8985).
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	/// RACK-TLP is only used if selective acknowledgments have been negotiated, as it relies on them to know which segments have been delivered.
+	#[inline(always)]
+	pub(crate) fn use_recent_acknowledgment_loss_detection_if_selective_acknowledgments_are_permitted(&mut self)
 	{
-		if let Some(measurement_of_round_trip_time) = self.measurement_of_round_trip_time_using_timestamps(now, timestamps_option)
+		if self.selective_acknowledgments_permitted
 		{
-			self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().process_measurement_of_round_trip_time(measurement_of_round_trip_time);
+			self.SND.use_recent_acknowledgment_loss_detection()
+		}
+	}
+	
+	#[inline(always)]
+	pub(crate) fn retransmission_alarm_purpose(&self) -> RetransmissionAlarmPurpose
+	{
+		self.retransmission_and_zero_window_probe_alarm_behaviour_reference().purpose()
+	}
+	
+	/// RFC 8985 Section 6.3: "... the sender SHOULD arm the RACK reordering timer ...".
+	///
+	/// The reordering timer shares the retransmission alarm; when it expires, the retransmission timer is re-armed.
+	#[inline(always)]
+	pub(crate) fn reordering_timed_out(&mut self, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
+	{
+		let now = now.to_milliseconds();
+		
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().set_purpose(RetransmissionAlarmPurpose::RetransmissionTimeOut);
+		
+		if self.detect_losses_using_recent_acknowledgment(interface.alarms(), now)
+		{
+			let failed = if self.congestion_control.is_in_fast_recovery()
+			{
+				self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(0);
+				self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+			}
+			else
+			{
+				self.enter_loss_recovery_because_segments_were_detected_as_lost_returning_true_if_failed(interface, now)
+			};
+			
+			if unlikely!(failed)
+			{
+				return None
+			}
+		}
+		
+		// Transmitting or detecting losses may have already scheduled the alarm.
+		if self.retransmission_and_zero_window_probe_alarm.is_scheduled()
+		{
+			None
 		}
 		else
 		{
-			self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().adjust_retransmission_time_out_based_on_acknowledgments(now, fully_acknowledged_segment_timestamp)
+			self.next_retransmission_or_zero_probe_alarm()
 		}
 	}
 	
+	/// RFC 8985 Section 7.3: The probe is one segment of previously unsent data if the send window permits, otherwise a retransmission of the most recently sent segment.
+	///
+	/// Only one probe is sent; the retransmission timer is then re-armed.
+	#[inline(always)]
+	pub(crate) fn tail_loss_probe(&mut self, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
+	{
+		let now = now.to_milliseconds();
+		
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().set_purpose(RetransmissionAlarmPurpose::RetransmissionTimeOut);
+		
+		// Loss recovery may have started since the probe was scheduled.
+		let SND_UNA_less_one = self.SND.UNA_less_one();
+		if self.congestion_control.is_in_loss_recovery(SND_UNA_less_one)
+		{
+			return self.next_retransmission_or_zero_probe_alarm()
+		}
+		
+		let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+		
+		let mut was_a_retransmission = true;
+		if self.SND.has_data_buffered_but_not_transmitted() && self.SND.retransmission_queue_is_not_full()
+		{
+			let sequence_numbers_in_flight = self.SND.sequence_numbers_in_flight();
+			let rwnd = self.SND.rwnd();
+			if sequence_numbers_in_flight < rwnd
+			{
+				let usable_window = min(rwnd - sequence_numbers_in_flight, sender_maximum_segment_size);
+				
+				// The probe is due now and so is not held back by pacing, but it is charged against the pacing credit so that data transmitted after it is paced.
+				let pacing_rate = self.pacing_rate(interface);
+				if unlikely!(self.transmit_new_data_returning_true_if_failed(interface, now, usable_window, pacing_rate))
+				{
+					return None
+				}
+				self.congestion_control.last_sent_data_at(now);
+				
+				// RFC 1122 Section 4.2.3.2: "... an ACK should be piggybacked on a segment being transmitted if possible".
+				self.acknowledgment_sent(interface.alarms());
+				
+				was_a_retransmission = false;
+			}
+		}
+		
+		if was_a_retransmission
+		{
+			match self.SND.latest_unacknowledged_segment_to_retransmit(now)
+			{
+				None => return self.next_retransmission_or_zero_probe_alarm(),
+				
+				Some((SEQ, data, flags)) => if unlikely!(self.retransmit_segment_returning_true_if_failed(interface, now, SEQ, data, flags))
+				{
+					return None
+				},
+			}
+		}
+		
+		let SND_NXT = self.SND.NXT();
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().tail_loss_probe_transmitted(SND_NXT, was_a_retransmission);
+		
+		self.next_retransmission_or_zero_probe_alarm()
+	}
+	
+	/// RFC 8985 Section 6.2: Returns `true` if any segments were newly marked as lost.
+	///
+	/// If a segment may yet be marked as lost once its reordering window has passed, the reordering timer is armed.
+	#[inline(always)]
+	fn detect_losses_using_recent_acknowledgment(&mut self, alarms: &Alarms<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		if !self.SND.is_using_recent_acknowledgment_loss_detection()
+		{
+			return false
+		}
+		
+		let is_in_loss_recovery = self.congestion_control.is_in_fast_recovery();
+		let (smoothed_round_trip_time, _round_trip_time_variance) = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().smoothed_round_trip_time_and_round_trip_time_variance();
+		
+		let (any_segments_newly_marked_as_lost, reordering_time_out) = self.SND.detect_losses_using_recent_acknowledgment(now, is_in_loss_recovery, smoothed_round_trip_time);
+		
+		if let Some(reordering_time_out) = reordering_time_out
+		{
+			self.reschedule_retransmission_and_zero_window_probe_alarm(alarms, RetransmissionAlarmPurpose::ReorderingTimeOut, TickDuration::milliseconds_to_ticks_rounded_up(reordering_time_out));
+		}
+		
+		any_segments_newly_marked_as_lost
+	}
+	
+	/// RFC 8985 Section 6.2: Loss recovery starts when a segment is marked as lost (RFC 6675 Section 5 Step (4) and RFC 6937).
+	#[inline(always)]
+	fn enter_loss_recovery_because_segments_were_detected_as_lost_returning_true_if_failed(&mut self, interface: &Interface<TCBA>, now: MonotonicMillisecondTimestamp) -> bool
+	{
+		let SND_UNA_less_one = self.SND.UNA_less_one();
+		let SND_NXT = self.SND.NXT();
+		if !self.congestion_control.enter_selective_acknowledgment_loss_recovery_if_appropriate(SND_UNA_less_one, SND_NXT, true, now)
+		{
+			return false
+		}
+		
+		self.proportional_rate_reduction_during_selective_acknowledgment_loss_recovery(0);
+		
+		// Segments marked as lost are retransmitted by NextSeg () rule (1).
+		self.transmit_during_selective_acknowledgment_loss_recovery_returning_true_if_failed(interface, now)
+	}
+	
+	/// RFC 8985 Section 7.2: A probe time out (PTO) replaces the retransmission time out whilst not in loss recovery.
+	#[inline(always)]
+	fn schedule_tail_loss_probe_if_appropriate(&mut self, alarms: &Alarms<TCBA>)
+	{
+		if !self.SND.is_using_recent_acknowledgment_loss_detection()
+		{
+			return
+		}
+		
+		let SND_UNA_less_one = self.SND.UNA_less_one();
+		if self.congestion_control.is_in_loss_recovery(SND_UNA_less_one) || self.all_data_acknowledged()
+		{
+			return
+		}
+		
+		// RFC 8985 Section 7.4: Only one probe is outstanding at a time.
+		if self.retransmission_and_zero_window_probe_alarm_behaviour_reference().tail_loss_probe().is_some()
+		{
+			return
+		}
+		
+		if self.retransmission_and_zero_window_probe_alarm.is_scheduled() && self.retransmission_alarm_purpose() == RetransmissionAlarmPurpose::ReorderingTimeOut
+		{
+			return
+		}
+		
+		let sender_maximum_segment_size = self.congestion_control.sender_maximum_segment_size();
+		let only_one_segment_in_flight = self.SND.sequence_numbers_in_flight() <= sender_maximum_segment_size;
+		let probe_time_out = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().probe_time_out(only_one_segment_in_flight, self.retransmission_time_out());
+		
+		self.reschedule_retransmission_and_zero_window_probe_alarm(alarms, RetransmissionAlarmPurpose::TailLossProbe, TickDuration::milliseconds_to_ticks_rounded_up(probe_time_out));
+	}
+	
+	/// RFC 8985 Section 7.4.2: If the probe was a retransmission, and the acknowledgment covers more than the probe without a D-SACK block having reported it, then the probe repaired a loss and the congestion window is reduced.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	fn tail_loss_probe_acknowledged(&mut self, SEG_ACK: WrappingSequenceNumber, now: MonotonicMillisecondTimestamp)
+	{
+		match self.retransmission_and_zero_window_probe_alarm_behaviour_reference().tail_loss_probe()
+		{
+			Some((tail_loss_probe_ends_at, false)) if SEG_ACK >= tail_loss_probe_ends_at =>
+			{
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().tail_loss_probe_ended();
+			}
+			
+			Some((tail_loss_probe_ends_at, true)) if SEG_ACK > tail_loss_probe_ends_at =>
+			{
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().tail_loss_probe_ended();
+				self.congestion_control.loss_repaired_by_tail_loss_probe(now);
+			}
+			
+			_ => (),
+		}
+	}
+	
+	/// Unlike `cancel_retransmission_and_zero_window_probe_alarm()`, the number of retransmissions is not reset.
+	#[inline(always)]
+	fn reschedule_retransmission_and_zero_window_probe_alarm(&mut self, alarms: &Alarms<TCBA>, purpose: RetransmissionAlarmPurpose, goes_off_in_ticks: TickDuration)
+	{
+		self.retransmission_and_zero_window_probe_alarm.cancel(alarms);
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().set_purpose(purpose);
+		self.retransmission_and_zero_window_probe_alarm.schedule(alarms, goes_off_in_ticks)
+	}
+}
+
+/// User time out.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	#[inline(always)]
+	pub(crate) fn schedule_user_time_out_alarm_for_connection(&mut self, alarms: &Alarms<TCBA>, connection_time_out: MillisecondDuration)
+	{
+		self.debug_assert_action_is_only_valid_in_sychronize_sent_state();
+		self.debug_assert_we_are_the_client();
+		
+		self.user_time_out_alarm.schedule(alarms, TickDuration::milliseconds_to_ticks_rounded_up(connection_time_out))
+	}
+}
+
+/// Retransmission and Zero-Window Probing.
+impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
+{
+	#[inline(always)]
+	fn cancel_retransmission_and_zero_window_probe_alarm(&mut self, alarms: &Alarms<TCBA>)
+	{
+		self.retransmission_and_zero_window_probe_alarm.cancel(alarms);
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().reset_retransmissions();
+	}
+	
+	#[inline(always)]
+	fn start_retransmission_and_zero_window_probe_alarm_if_not_running(&mut self, alarms: &Alarms<TCBA>)
+	{
+		if self.retransmission_and_zero_window_probe_alarm.is_cancelled()
+		{
+			self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().set_purpose(RetransmissionAlarmPurpose::RetransmissionTimeOut);
+			self.retransmission_and_zero_window_probe_alarm.schedule(alarms, self.retransmission_time_out_tick_duration())
+		}
+	}
+	
+	#[inline(always)]
+	pub(crate) fn schedule_or_cancel_retransmission_and_zero_window_probe_alarm_as_appropriate(&mut self, alarms: &Alarms<TCBA>)
+	{
+		if self.retransmission_and_zero_window_probe_alarm.is_scheduled() && self.all_data_acknowledged() && self.send_window_is_non_zero()
+		{
+			self.cancel_retransmission_and_zero_window_probe_alarm(alarms);
+		}
+		else
+		{
+			if self.retransmission_and_zero_window_probe_alarm.is_cancelled()
+			{
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().reset_retransmissions();
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().set_purpose(RetransmissionAlarmPurpose::RetransmissionTimeOut);
+				self.retransmission_and_zero_window_probe_alarm.schedule(alarms, self.retransmission_time_out_tick_duration())
+			}
+		}
+	}
+	
+	/// RFC 3522 and RFC 5682: Is the most recent retransmission time out now known to have been spurious?
+	///
+	/// If so, the congestion response to it has been undone, and the retransmission time out is adapted (RFC 4015 Section 4).
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	fn detect_spurious_retransmission_time_out(&mut self, SEG_ACK: WrappingSequenceNumber, bytes_acknowledged: u32, timestamps_option: Option<&TimestampsOption>, explicit_congestion_echo: bool)
+	{
+		let retransmitted_at = match self.congestion_control.retransmitted_at_if_detecting_spurious_retransmission_time_out()
+		{
+			None => return,
+			
+			Some(retransmitted_at) => retransmitted_at,
+		};
+		
+		let timestamp_echo_reply_is_earlier_than_retransmission = self.timestamp_echo_reply_is_earlier_than(timestamps_option, retransmitted_at);
+		let new_data_is_available = self.SND.has_data_buffered_but_not_transmitted();
+		
+		if self.congestion_control.acknowledgment_after_retransmission_time_out(SEG_ACK, bytes_acknowledged, timestamp_echo_reply_is_earlier_than_retransmission, explicit_congestion_echo, new_data_is_available)
+		{
+			self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().retransmission_time_out_was_spurious();
+		}
+	}
+	
+	/// Processing Incoming Segments 4.5.2.2: "... compute a new estimate of round-trip time.
+	/// If Snd.TS.OK bit is on, use Snd.TSclock - SEG.TSecr; otherwise, use the elapsed time since the first segment in the retransmission queue was sent".
+	#[inline(always)]
+	fn compute_a_new_estimate_of_round_trip_time_for_a_fully_acknowledged_segment(&mut self, now: MonotonicMillisecondTimestamp, fully_acknowledged_segment_timestamp: MonotonicMillisecondTimestamp, timestamps_option: Option<&TimestampsOption>)
+	{
+		let measurement_of_round_trip_time = match self.measurement_of_round_trip_time_using_timestamps(now, timestamps_option)
+		{
+			Some(measurement_of_round_trip_time) => measurement_of_round_trip_time,
+			
+			None =>
+			{
+				debug_assert!(now >= fully_acknowledged_segment_timestamp, "time has run backwards");
+				now - fully_acknowledged_segment_timestamp
+			}
+		};
+		
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().process_measurement_of_round_trip_time(measurement_of_round_trip_time);
+		let (smoothed_round_trip_time, _round_trip_time_variance) = self.retransmission_and_zero_window_probe_alarm_behaviour_reference().smoothed_round_trip_time_and_round_trip_time_variance();
+		self.congestion_control.round_trip_time_measured(measurement_of_round_trip_time, smoothed_round_trip_time, now);
+	}
+	
 	#[inline(always)]
 	pub(crate) fn retransmit_zero_window_probe(&mut self, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
 	{
@@ -735,28 +1708,51 @@ impl<TCBA: TransmissionControlBlockAbstractions> TransmissionControlBlock<TCBA>
 		
 		let number_of_transmissions = increment_retransmissions!(self, interface, now);
 		
-		xxxx;
-		let segment_sent_but_unacknowledged = transmission_control_block.segment_to_retransmit();
+		// Generalized ECN: A Synchronize sent ECN-capable which has not been answered may have been discarded because of its ECN code point, so it is retransmitted not-ECT.
+		//
+		// The fall back is recorded in recent connection data now, rather than when this connection is destroyed, so that connections made in the meantime do not repeat the loss.
+		if self.state() == State::SynchronizeSent
+		{
+			let fell_back = match self.explicit_congestion_notification_state_mutable_reference()
+			{
+				None => false,
+				
+				Some(explicit_congestion_notification_state) => explicit_congestion_notification_state.synchronize_timed_out(),
+			};
+			
+			if fell_back
+			{
+				interface.explicit_congestion_notification_fell_back(self.remote_internet_protocol_address(), now);
+			}
+		}
 		
-		// Congestion Control and Explicit Congestion Notification.
+		// Congestion Control.
 		{
-			self.reset_congestion_window_to_loss_window_because_retransmission_timed_out();
+			let SND_UNA_less_one = self.SND.UNA_less_one();
+			self.congestion_control.save_state_for_undo_if_starting_a_new_loss_recovery_episode(SND_UNA_less_one);
 			
 			let is_first_retransmission = number_of_transmissions == 1;
 			if is_first_retransmission
 			{
-				segment_sent_but_unacknowledged.clear_explicit_congestion_notifications_when_retransmitting();
-				
-				transmission_control_block.rfc_5681_section_7_paragaph_6_set_ssthresh_to_half_of_flight_size_on_first_retransmission();
+				self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().retransmission_timed_out_for_the_first_time();
 			}
+			
+			let SND_NXT = self.SND.NXT();
+			self.reset_congestion_window_to_loss_window_because_retransmission_timed_out(SND_UNA_less_one, SND_NXT, is_first_retransmission, now);
 		}
 		
-		// RFC 6298 Section 5: "(5.4) Retransmit the earliest segment that has not been acknowledged by the TCP receiver".
-		// TODO: Retransmit unack'd packet - involves incrementing refcnt.
+		self.SND.retransmission_timed_out();
 		
-		// TODO: Do we need to reset the timestamp option? Yes.
+		// RFC 8985 Section 7.4: Once the retransmission timer has expired, the outcome of any tail loss probe no longer matters.
+		self.retransmission_and_zero_window_probe_alarm_behaviour_mutable_reference().tail_loss_probe_ended();
 		
-		xxxx;
+		// RFC 6298 Section 5: "(5.4) Retransmit the earliest segment that has not been acknowledged by the TCP receiver".
+		//
+		// Explicit Congestion Notification code points are not set on a retransmission (RFC 3168 Section 6.1.5) unless generalized ECN is in use, and the timestamp option is written afresh when the segment is sent.
+		if unlikely!(self.retransmit_earliest_unacknowledged_segment_returning_true_if_failed(interface, now))
+		{
+			return None
+		}
 		
 		// RFC 6298 Section 5: "(5.1) Every time a packet containing data is sent (including a retransmission), if the timer is not running, start it running so that it will expire after RTO seconds (for the current value of RTO)
 		self.next_retransmission_or_zero_probe_alarm()
@@ -19,12 +19,37 @@ pub(crate) struct TransmissionControlBlockReceive
 	
 	/// RFC 7323, Section 2.
 	Wind: Wind,
+	
+	/// RFC 793 Page 69: "Segments with higher begining sequence numbers may be held for later processing".
+	reassembly_queue: ReassemblyQueue,
+	
+	/// RFC 5681 Section 4.2: "... an ACK SHOULD be generated for at least every second full-sized segment".
+	///
+	/// Only full-sized segments are counted.
+	full_sized_segments_received_but_not_yet_acknowledged: u8,
+	
+	/// An estimate of the remote's sender maximum segment size (SMSS), used to recognise full-sized segments.
+	///
+	/// As for Linux's `rcv_mss`, this is the largest payload received so far, but no less than the RFC 1122 Section 4.2.2.6 default maximum segment size of 536 bytes.
+	remote_sender_maximum_segment_size_estimate: usize,
+	
+	/// Whilst non-zero, received data is acknowledged immediately rather than delayed ('quick acknowledgment mode').
+	quick_acknowledgments_remaining: u8,
+	
+	/// `None` until data has been received.
+	last_data_received_at: Option<MonotonicMillisecondTimestamp>,
+	
+	/// RFC 2883 Section 4: A duplicate segment, entirely below `RCV.NXT`, to be reported in the first block of the `SACK` option of the next acknowledgment sent.
+	duplicate_selective_acknowledgment_block: Option<SelectiveAcknowledgmentBlock>,
 }
 
 impl TransmissionControlBlockReceive
 {
+	/// RFC 1122 Section 4.2.2.6: "If an MSS option is not received at connection setup, TCP MUST assume a default send MSS of 536".
+	const DefaultMaximumSegmentSize: usize = 536;
+	
 	#[inline(always)]
-	pub(crate) fn new_for_closed_to_synchronize_sent() -> Self
+	pub(crate) fn new_for_closed_to_synchronize_sent(reassembly_queue: ReassemblyQueue) -> Self
 	{
 		Self
 		{
@@ -34,11 +59,17 @@ impl TransmissionControlBlockReceive
 			{
 				Shift: InitialWindowSize::Shift
 			},
+			reassembly_queue,
+			full_sized_segments_received_but_not_yet_acknowledged: 0,
+			remote_sender_maximum_segment_size_estimate: Self::DefaultMaximumSegmentSize,
+			quick_acknowledgments_remaining: 0,
+			last_data_received_at: None,
+			duplicate_selective_acknowledgment_block: None,
 		}
 	}
 	
 	#[inline(always)]
-	pub(crate) fn new_for_sychronize_received_to_established(NXT: WrappingSequenceNumber, WND: WindowSize, Wind_Shift: WindowScaleOption) -> Self
+	pub(crate) fn new_for_sychronize_received_to_established(reassembly_queue: ReassemblyQueue, NXT: WrappingSequenceNumber, WND: WindowSize, Wind_Shift: WindowScaleOption) -> Self
 	{
 		Self
 		{
@@ -48,6 +79,12 @@ impl TransmissionControlBlockReceive
 			{
 				Shift: Wind_Shift
 			},
+			reassembly_queue,
+			full_sized_segments_received_but_not_yet_acknowledged: 0,
+			remote_sender_maximum_segment_size_estimate: Self::DefaultMaximumSegmentSize,
+			quick_acknowledgments_remaining: 0,
+			last_data_received_at: None,
+			duplicate_selective_acknowledgment_block: None,
 		}
 	}
 	
@@ -79,6 +116,141 @@ impl TransmissionControlBlockReceive
 		RCV.NXT = IRS + 1;
 	}
 	
+	/// Holds data which arrived out-of-order, ie to the right of `RCV.NXT`; data beyond `RCV.NXT + RCV.WND` is not held.
+	///
+	/// Returns `false` if the data could not be held (because a per-connection or per-Interface limit would be exceeded); the data is then discarded and will need to be retransmitted by the remote.
+	#[inline(always)]
+	pub(crate) fn hold_out_of_order_data(&mut self, SEG_SEQ: WrappingSequenceNumber, data: &[u8]) -> bool
+	{
+		let RCV = self;
+		RCV.reassembly_queue.hold(RCV.NXT, RCV.WND, SEG_SEQ, data)
+	}
+	
+	/// Advances `RCV.NXT` over `data_length` bytes of in-order data, then over any held out-of-order data which has now become contiguous.
+	///
+	/// `deliver` is called for each slice of held data so released.
+	#[inline(always)]
+	pub(crate) fn advance_NXT_over_in_order_data<Deliver: FnMut(&[u8])>(&mut self, data_length: usize, deliver: Deliver)
+	{
+		let RCV = self;
+		RCV.NXT = RCV.NXT + data_length;
+		RCV.NXT = RCV.reassembly_queue.release(RCV.NXT, deliver);
+	}
+	
+	/// RFC 2018 Section 4: SACK blocks for the out-of-order data held, most recently received first, preceded by any duplicate selective acknowledgment (D-SACK) block (RFC 2883 Section 4).
+	///
+	/// RFC 2883 Section 4: Each duplicate contiguous sequence of data received is reported in at most one D-SACK block; the D-SACK block is consumed by this call.
+	#[inline(always)]
+	pub(crate) fn selective_acknowledgment_option(&mut self, maximum_number_of_blocks: usize) -> Option<SelectiveAcknowledgmentOption>
+	{
+		let RCV = self;
+		let duplicate_selective_acknowledgment_block = RCV.duplicate_selective_acknowledgment_block.take();
+		RCV.reassembly_queue.selective_acknowledgment_option(duplicate_selective_acknowledgment_block, maximum_number_of_blocks)
+	}
+	
+	/// RFC 2883 Section 4: A duplicate segment is reported to the sender in a duplicate selective acknowledgment (D-SACK) block.
+	///
+	/// Only a segment which lies entirely below `RCV.NXT` is reported; the next acknowledgment sent will carry it as the first block of its `SACK` option.
+	#[inline(always)]
+	pub(crate) fn duplicate_segment_received<TCBA: TransmissionControlBlockAbstractions>(&mut self, SEG: &ParsedTcpSegment<TCBA>)
+	{
+		let RCV = self;
+		
+		if SEG.LEN == 0
+		{
+			return
+		}
+		
+		let right_edge_of_block = SEG.SEQ + SEG.LEN;
+		if right_edge_of_block <= RCV.NXT
+		{
+			let duplicate_selective_acknowledgment_block = SelectiveAcknowledgmentBlock
+			{
+				left_edge_of_block: SEG.SEQ,
+				right_edge_of_block,
+			};
+			RCV.duplicate_selective_acknowledgment_block = Some(duplicate_selective_acknowledgment_block)
+		}
+	}
+	
+	#[inline(always)]
+	pub(crate) fn has_out_of_order_data(&self) -> bool
+	{
+		let RCV = self;
+		!RCV.reassembly_queue.is_empty()
+	}
+	
+	/// Records the receipt of an in-order data segment and decides if it should be acknowledged immediately rather than delayed.
+	///
+	/// RFC 5681 Section 4.2: "... an ACK SHOULD be generated for at least every second full-sized segment"; a segment is full-sized if its payload is at least as large as the estimate of the remote's sender maximum segment size.
+	///
+	/// After the connection has been idle for longer than the retransmission time out, the remote will have restarted slow start (RFC 5681 Section 4.1); quick acknowledgment mode is entered so that delayed acknowledgments do not slow the growth of its congestion window.
+	/// This is also the case for the first data received on a connection.
+	#[inline(always)]
+	pub(crate) fn acknowledgment_of_received_data_should_not_be_delayed(&mut self, now: MonotonicMillisecondTimestamp, retransmission_time_out: MillisecondDuration, payload_length: usize) -> bool
+	{
+		let RCV = self;
+		
+		let remote_is_in_slow_start = match RCV.last_data_received_at
+		{
+			None => true,
+			Some(last_data_received_at) => now - last_data_received_at > retransmission_time_out,
+		};
+		RCV.last_data_received_at = Some(now);
+		
+		if remote_is_in_slow_start
+		{
+			RCV.enter_quick_acknowledgment_mode();
+		}
+		
+		if payload_length >= RCV.remote_sender_maximum_segment_size_estimate
+		{
+			RCV.remote_sender_maximum_segment_size_estimate = payload_length;
+			RCV.full_sized_segments_received_but_not_yet_acknowledged = RCV.full_sized_segments_received_but_not_yet_acknowledged.saturating_add(1);
+		}
+		
+		if RCV.quick_acknowledgments_remaining != 0
+		{
+			RCV.quick_acknowledgments_remaining -= 1;
+			return true
+		}
+		
+		const AcknowledgeAtLeastEverySecondFullSizedSegment: u8 = 2;
+		RCV.full_sized_segments_received_but_not_yet_acknowledged >= AcknowledgeAtLeastEverySecondFullSizedSegment
+	}
+	
+	/// Used when the remote is in slow start (for the first data received, and after an idle period longer than the retransmission time out) and when out-of-order data arrives, when delaying acknowledgments would slow the remote down.
+	///
+	/// `RCV.WND` does not change once the connection is established, so there are no receive window updates for which to enter it.
+	///
+	/// The number of quick acknowledgments is the same as Linux's `TCP_MAX_QUICKACKS`.
+	#[inline(always)]
+	pub(crate) fn enter_quick_acknowledgment_mode(&mut self)
+	{
+		const QuickAcknowledgments: u8 = 16;
+		
+		let RCV = self;
+		RCV.quick_acknowledgments_remaining = QuickAcknowledgments;
+	}
+	
+	/// An acknowledgment of `RCV.NXT` has been sent, either by itself or piggybacked on a segment containing data or a `FIN`.
+	#[inline(always)]
+	pub(crate) fn acknowledgment_sent(&mut self)
+	{
+		let RCV = self;
+		RCV.full_sized_segments_received_but_not_yet_acknowledged = 0;
+	}
+	
+	/// RFC 793 Page 75: "... advance RCV.NXT over the FIN ...".
+	#[inline(always)]
+	pub(crate) fn advance_NXT_over_finish(&mut self)
+	{
+		const FinishSequenceNumberLength: u32 = 1;
+		
+		let RCV = self;
+		RCV.NXT = RCV.NXT + FinishSequenceNumberLength;
+	}
+	
 	#[inline(always)]
 	pub(crate) fn segment_sequence_number_exactly_matches_next_expected_sequence_number<TCBA: TransmissionControlBlockAbstractions>(&self, SEG: &ParsedTcpSegment<TCBA>) -> bool
 	{
@@ -54,7 +54,17 @@ pub(crate) struct TransmissionControlBlockSend
 	WL2: WrappingSequenceNumber,
 	
 	magic_ring_buffer: MagicRingBuffer,
+	
+	/// The data at the front of the `magic_ring_buffer` which has been transmitted but not yet acknowledged; data after it is buffered but not yet transmitted.
+	bytes_transmitted_but_not_acknowledged: usize,
+	
 	retransmission_queue: RetransmissionQueue,
+	
+	/// RFC 793 Page 60: "Queue this until all preceding SENDs have been segmentized, then form a FIN segment and send it".
+	finish_queued: bool,
+	
+	/// The sequence number our `FIN` occupies, once it has been transmitted.
+	finish_sequence_number: Option<WrappingSequenceNumber>,
 }
 
 impl TransmissionControlBlockSend
@@ -78,7 +88,10 @@ impl TransmissionControlBlockSend
 			MAX_SND_WND: SND_WND,
 			WND_last_updated: now,
 			magic_ring_buffer,
+			bytes_transmitted_but_not_acknowledged: 0,
 			retransmission_queue: RetransmissionQueue::default(),
+			finish_queued: false,
+			finish_sequence_number: None,
 		}
 	}
 	
@@ -99,7 +112,10 @@ impl TransmissionControlBlockSend
 			MAX_SND_WND: SND_WND,
 			WND_last_updated: now,
 			magic_ring_buffer,
+			bytes_transmitted_but_not_acknowledged: 0,
 			retransmission_queue: RetransmissionQueue::default(),
+			finish_queued: false,
+			finish_sequence_number: None,
 		}
 	}
 	
@@ -177,11 +193,11 @@ impl TransmissionControlBlockSend
 	}
 	
 	#[inline(always)]
-	pub(crate) fn move_UNA<TCBA: TransmissionControlBlockAbstractions>(&mut self, SEG: &ParsedTcpSegment<TCBA>, explicit_congestion_echo: bool) -> (u32, Option<MonotonicMillisecondTimestamp>, bool, bool)
+	pub(crate) fn move_UNA<TCBA: TransmissionControlBlockAbstractions>(&mut self, SEG: &ParsedTcpSegment<TCBA>, explicit_congestion_echo: bool, now: MonotonicMillisecondTimestamp, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>) -> (u32, Option<MonotonicMillisecondTimestamp>, bool, bool)
 	{
 		let SEG_ACK = SEG.ACK;
 		
-		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.acknowledged(SEG_ACK, explicit_congestion_echo);
+		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.acknowledged(SEG_ACK, explicit_congestion_echo, now, round_trip_time_measured_using_timestamps);
 		
 		let SND = self;
 		SND.UNA = SEG_ACK;
@@ -247,6 +263,49 @@ impl TransmissionControlBlockSend
 		!self.all_data_acknowledged()
 	}
 	
+	#[inline(always)]
+	pub(crate) fn queue_finish(&mut self)
+	{
+		debug_assert!(!self.finish_queued, "FIN already queued");
+		
+		self.finish_queued = true
+	}
+	
+	#[inline(always)]
+	pub(crate) fn finish_is_queued(&self) -> bool
+	{
+		self.finish_queued
+	}
+	
+	/// A `FIN` is only transmitted once all data buffered before it has been transmitted.
+	#[inline(always)]
+	pub(crate) fn finish_is_ready_to_transmit(&self) -> bool
+	{
+		self.finish_queued && self.finish_sequence_number.is_none() && !self.has_data_buffered_but_not_transmitted() && self.retransmission_queue_is_not_full()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn finish_transmitted(&mut self, finish_sequence_number: WrappingSequenceNumber)
+	{
+		debug_assert!(self.finish_queued, "FIN was not queued");
+		debug_assert!(self.finish_sequence_number.is_none(), "FIN already transmitted");
+		
+		self.finish_sequence_number = Some(finish_sequence_number)
+	}
+	
+	#[inline(always)]
+	pub(crate) fn finish_has_been_acknowledged(&self) -> bool
+	{
+		let SND = self;
+		
+		match SND.finish_sequence_number
+		{
+			None => false,
+			
+			Some(finish_sequence_number) => SND.UNA > finish_sequence_number,
+		}
+	}
+	
 	#[inline(always)]
 	pub(crate) fn retransmission_queue_is_not_full(&self) -> bool
 	{
@@ -269,55 +328,197 @@ impl TransmissionControlBlockSend
 		now - send_window_last_updated
 	}
 	
+	/// The amount of sequence space that has been transmitted but not yet acknowledged, ie `SND.NXT - SND.UNA`.
+	///
+	/// This includes the sequence space occupied by the `SYN` and `FIN` controls.
+	#[inline(always)]
+	pub(crate) fn sequence_numbers_in_flight(&self) -> u32
+	{
+		let SND = self;
+		SND.NXT - SND.UNA
+	}
+	
 	#[inline(always)]
-	pub(crate) fn buffer_data_to_send<DataWriter: Fn(&mut [u8]) -> usize>(&mut self, data_writer: DataWriter)
+	pub(crate) fn buffer_data_to_send<DataWriter: Fn(&mut [u8]) -> usize>(&mut self, data_writer: DataWriter) -> usize
 	{
 		let wrote = data_writer(self.magic_ring_buffer.write_buffer());
-		self.write_commit(wrote.into());
-		
-		
-		
-		
-		
-		
-		// TODO: now what?
-		
-		
-		// TODO: We can immediately send this much data:-
-		transmission_control_block.maximum_data(now)
+		self.magic_ring_buffer.write_commit(wrote);
+		wrote
+	}
+	
+	#[inline(always)]
+	pub(crate) fn send_buffer_space_available(&mut self) -> usize
+	{
+		self.magic_ring_buffer.write_buffer().len()
+	}
+	
+	#[inline(always)]
+	pub(crate) fn has_data_buffered_but_not_transmitted(&self) -> bool
+	{
+		self.magic_ring_buffer.read_buffer().len() > self.bytes_transmitted_but_not_acknowledged
+	}
+	
+	/// Data which has been buffered but not yet transmitted, truncated to `maximum_data` bytes.
+	///
+	/// The returned slice is never empty unless `maximum_data` is zero or there is no such data.
+	#[inline(always)]
+	pub(crate) fn data_to_transmit(&self, maximum_data: u32) -> &[u8]
+	{
+		let buffered_but_not_transmitted = &self.magic_ring_buffer.read_buffer()[self.bytes_transmitted_but_not_acknowledged .. ];
+		let length = min(buffered_but_not_transmitted.len(), maximum_data as usize);
+		&buffered_but_not_transmitted[ .. length]
+	}
+	
+	/// Records that `count` bytes from the front of `data_to_transmit()` have been transmitted.
+	#[inline(always)]
+	pub(crate) fn data_to_transmit_commit(&mut self, count: usize)
+	{
+		debug_assert!(self.bytes_transmitted_but_not_acknowledged + count <= self.magic_ring_buffer.read_buffer().len(), "committed more data than was buffered");
 		
+		self.bytes_transmitted_but_not_acknowledged += count
+	}
+	
+	/// The earliest segment which has not been acknowledged, ie the one starting at `SND.UNA`, which is then marked as having been retransmitted.
+	///
+	/// Returns a tuple of `(SEQ, data, flags)`; the memory of `data` is that of the magic ring buffer, which is not owned by `self`, so it can be aliased whilst `self` is mutably borrowed to send.
+	#[inline(always)]
+	pub(crate) fn earliest_unacknowledged_segment_to_retransmit(&mut self, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, *const [u8], Flags)>
+	{
+		let (starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags) = self.retransmission_queue.oldest_for_retransmission(now)?;
 		
+		let data_length = data_length_excluding_length_of_synchronize_and_finish_controls as usize;
+		debug_assert!(data_length <= self.bytes_transmitted_but_not_acknowledged, "retransmission segment has more data than was transmitted");
 		
+		let data = &self.magic_ring_buffer.read_buffer()[ .. data_length] as *const [u8];
+		Some((starts_at, data, flags))
+	}
+	
+	/// The latest segment which has not been acknowledged, ie the one most recently transmitted for the first time, which is then marked as having been retransmitted.
+	///
+	/// Returns a tuple of `(SEQ, data, flags)` as for `earliest_unacknowledged_segment_to_retransmit()`.
+	#[inline(always)]
+	pub(crate) fn latest_unacknowledged_segment_to_retransmit(&mut self, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, *const [u8], Flags)>
+	{
+		let (starts_at, offset_of_data_from_SND_UNA, data_length_excluding_length_of_synchronize_and_finish_controls, flags) = self.retransmission_queue.newest_for_retransmission(now)?;
 		
+		let data_ends_at = offset_of_data_from_SND_UNA + data_length_excluding_length_of_synchronize_and_finish_controls as usize;
+		debug_assert!(data_ends_at <= self.bytes_transmitted_but_not_acknowledged, "retransmission segment has more data than was transmitted");
 		
+		let data = &self.magic_ring_buffer.read_buffer()[offset_of_data_from_SND_UNA .. data_ends_at] as *const [u8];
+		Some((starts_at, data, flags))
+	}
+	
+	/// Updates the selective acknowledgment scoreboard; returns `true` if the option contained new information.
+	#[inline(always)]
+	pub(crate) fn selectively_acknowledged(&mut self, selective_acknowledgment_option: &SelectiveAcknowledgmentOption, now: MonotonicMillisecondTimestamp, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>) -> bool
+	{
+		let SND = self;
+		SND.retransmission_queue.selectively_acknowledged(SND.UNA, SND.NXT, selective_acknowledgment_option, now, round_trip_time_measured_using_timestamps)
+	}
+	
+	/// Detect losses using RACK (RFC 8985) rather than by counting duplicate acknowledgments.
+	#[inline(always)]
+	pub(crate) fn use_recent_acknowledgment_loss_detection(&mut self)
+	{
+		self.retransmission_queue.use_recent_acknowledgment_loss_detection()
+	}
+	
+	/// Are losses detected using RACK (RFC 8985)?
+	#[inline(always)]
+	pub(crate) fn is_using_recent_acknowledgment_loss_detection(&self) -> bool
+	{
+		self.retransmission_queue.is_using_recent_acknowledgment_loss_detection()
+	}
+	
+	/// RFC 8985 Section 6.2 Step 5; returns a tuple of `(any_segments_newly_marked_as_lost, time_out)`.
+	#[inline(always)]
+	pub(crate) fn detect_losses_using_recent_acknowledgment(&mut self, now: MonotonicMillisecondTimestamp, is_in_loss_recovery: bool, smoothed_round_trip_time: MillisecondDuration) -> (bool, Option<MillisecondDuration>)
+	{
+		self.retransmission_queue.detect_losses_using_recent_acknowledgment(now, is_in_loss_recovery, smoothed_round_trip_time)
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A D-SACK block has been received.
+	#[inline(always)]
+	pub(crate) fn recent_acknowledgment_duplicate_selective_acknowledgment_received(&mut self)
+	{
+		let SND = self;
+		SND.retransmission_queue.recent_acknowledgment_duplicate_selective_acknowledgment_received(SND.UNA, SND.NXT)
+	}
+	
+	/// RFC 8985 Section 6.2 Step 4: A loss recovery episode has ended.
+	#[inline(always)]
+	pub(crate) fn recent_acknowledgment_loss_recovery_exited(&mut self)
+	{
+		self.retransmission_queue.recent_acknowledgment_loss_recovery_exited()
+	}
+	
+	/// RFC 6675 Section 4 IsLost (HighACK + 1).
+	#[inline(always)]
+	pub(crate) fn earliest_unacknowledged_segment_is_lost(&self, sender_maximum_segment_size: u32) -> bool
+	{
+		let SND = self;
+		SND.retransmission_queue.is_lost(SND.UNA, sender_maximum_segment_size)
+	}
+	
+	/// RFC 6937 Section 3: "SACKd", the number of sequence numbers between `SND.UNA` and `SND.NXT` which have been selectively acknowledged.
+	#[inline(always)]
+	pub(crate) fn sequence_numbers_selectively_acknowledged(&self) -> u32
+	{
+		let SND = self;
+		SND.retransmission_queue.number_of_sequence_numbers_selectively_acknowledged(SND.UNA, SND.NXT)
+	}
+	
+	/// RFC 6675 Section 4 SetPipe ().
+	#[inline(always)]
+	pub(crate) fn pipe(&self, sender_maximum_segment_size: u32) -> u32
+	{
+		self.retransmission_queue.pipe(sender_maximum_segment_size)
+	}
+	
+	/// RFC 6675 Section 4 NextSeg () rules (1) and, if `must_be_lost` is `false`, (3); the segment is then marked as having been retransmitted.
+	///
+	/// Returns a tuple of `(SEQ, data, flags)` as for `earliest_unacknowledged_segment_to_retransmit()`.
+	#[inline(always)]
+	pub(crate) fn next_segment_to_retransmit(&mut self, sender_maximum_segment_size: u32, must_be_lost: bool, now: MonotonicMillisecondTimestamp) -> Option<(WrappingSequenceNumber, *const [u8], Flags)>
+	{
+		let (starts_at, offset_of_data_from_SND_UNA, data_length_excluding_length_of_synchronize_and_finish_controls, flags) = self.retransmission_queue.next_segment_to_retransmit(sender_maximum_segment_size, must_be_lost, now)?;
 		
+		let data_ends_at = offset_of_data_from_SND_UNA + data_length_excluding_length_of_synchronize_and_finish_controls as usize;
+		debug_assert!(data_ends_at <= self.bytes_transmitted_but_not_acknowledged, "retransmission segment has more data than was transmitted");
 		
-		xxx
+		let data = &self.magic_ring_buffer.read_buffer()[offset_of_data_from_SND_UNA .. data_ends_at] as *const [u8];
+		Some((starts_at, data, flags))
+	}
+	
+	#[inline(always)]
+	pub(crate) fn retransmission_timed_out(&mut self)
+	{
+		self.retransmission_queue.retransmission_timed_out()
 	}
 	
+	/// A delivery rate sample for the data delivered by the most recent call to `move_UNA()`, if one could be generated.
 	#[inline(always)]
-	pub(crate) fn data_to_transmit(&self, maximum_data: usize) -> &[u8]
+	pub(crate) fn delivery_rate_sample(&mut self) -> Option<DeliveryRateSample>
 	{
-		// TODO
-		xxx
+		self.retransmission_queue.delivery_rate_sample()
 	}
 	
+	/// There is no more data to send although the congestion window and send window would permit it.
 	#[inline(always)]
-	pub(crate) fn data_to_transmit_commit(&self, count: usize)
+	pub(crate) fn application_limited(&mut self)
 	{
-		// TODO
-		xxx
+		let sequence_numbers_in_flight = self.sequence_numbers_in_flight();
+		self.retransmission_queue.application_limited(sequence_numbers_in_flight)
 	}
 	
 	#[inline(always)]
 	pub(crate) fn transmitted(&mut self, now: MonotonicMillisecondTimestamp, starts_at: WrappingSequenceNumber, data_length_excluding_length_of_synchronize_and_finish_controls: u32, flags: Flags)
 	{
-		// TODO: Is this used?
 		self.retransmission_queue.enqueue(now, starts_at, data_length_excluding_length_of_synchronize_and_finish_controls, flags)
 	}
 	
 	#[inline(always)]
-	fn acknowledged(&mut self, SEG_ACK: WrappingSequenceNumber, explicit_congestion_echo: bool) -> (u32, Option<MonotonicMillisecondTimestamp>, bool, bool)
+	fn acknowledged(&mut self, SEG_ACK: WrappingSequenceNumber, explicit_congestion_echo: bool, now: MonotonicMillisecondTimestamp, round_trip_time_measured_using_timestamps: Option<MillisecondDuration>) -> (u32, Option<MonotonicMillisecondTimestamp>, bool, bool)
 	{
 		// TODO: Verify!
 		use self::RetransmissionSegmentDecreaseSequenceNumberLengthOutcome::*;
@@ -329,9 +530,12 @@ impl TransmissionControlBlockSend
 		let sequence_numbers_length = SEG_ACK - SND.UNA;
 		debug_assert_ne!(sequence_numbers_length, 0, "SEG.ACK should not be the same as SND.UNA");
 		
-		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.retransmission_queue.acknowledged(sequence_numbers_length, explicit_congestion_echo).expect("Retransmission queue does not contain as much data as SND.NXT indicates");
+		let (bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo) = self.retransmission_queue.acknowledged(sequence_numbers_length, explicit_congestion_echo, now, round_trip_time_measured_using_timestamps).expect("Retransmission queue does not contain as much data as SND.NXT indicates");
 		
-		self.magic_ring_buffer.read_commit(bytes_acknowledged.into());
+		let bytes_acknowledged_usize = bytes_acknowledged as usize;
+		debug_assert!(bytes_acknowledged_usize <= self.bytes_transmitted_but_not_acknowledged, "acknowledged more data than was transmitted");
+		self.bytes_transmitted_but_not_acknowledged -= bytes_acknowledged_usize;
+		self.magic_ring_buffer.read_commit(bytes_acknowledged_usize);
 		
 		(bytes_acknowledged, unretransmitted_segment_timestamp, a_window_of_data_was_processed, explicit_congestion_echo)
 	}
@@ -55,9 +55,12 @@ impl<AB: AlarmBehaviour<TCBA>, TCBA: TransmissionControlBlockAbstractions> Alarm
 		while expired_alarm_pointer.is_not_null()
 		{
 			let expired_alarm = expired_alarm_pointer.dereference_unchecked();
+			
+			// Must be read before the alarm is expired, as an alarm which is rescheduled (either from within `process_alarm()`, or because it returned a duration or has a remainder) is pushed onto the head of another list and so its `next` then points into that list.
+			let next = expired_alarm.next();
 			expired_alarm.expired(interface, now);
 			
-			expired_alarm_pointer = expired_alarm.next()
+			expired_alarm_pointer = next
 		}
 	}
 	
@@ -11,6 +11,30 @@ pub(crate) struct Alarms<TCBA: TransmissionControlBlockAbstractions>
 	
 	user_time_out_alarm_wheel: AlarmWheel<UserTimeOutAlarmBehaviour, TCBA>,
 	
+	time_wait_alarm_wheel: AlarmWheel<TimeWaitAlarmBehaviour, TCBA>,
+	
+	delayed_acknowledgment_alarm_wheel: AlarmWheel<DelayedAcknowledgmentAlarmBehaviour, TCBA>,
+	
+	pacing_alarm_wheel: AlarmWheel<PacingAlarmBehaviour, TCBA>,
+	
+	/// How long an acknowledgment of received data may be delayed.
+	///
+	/// RFC 1122 Section 4.2.3.2: "... the delay MUST be less than 0.5 seconds".
+	///
+	/// RFC 5681 Section 4.2: "... an ACK SHOULD be generated for at least every second full-sized segment, and MUST be generated within 500 ms of the arrival of the first unacknowledged packet".
+	///
+	/// Defaults to 200 milliseconds, which is rounded up to a whole number of ticks.
+	pub(crate) delayed_acknowledgment_time: TickDuration,
+	
+	/// How long a connection remains in the TimeWait state.
+	///
+	/// RFC 1122 Section 4.2.2.13 Paragraph 4: "When a connection is closed actively, it MUST linger in TIME-WAIT state for a time 2xMSL (Maximum Segment Lifetime)".
+	///
+	/// RFC 793 Page 28 makes MSL two (2) minutes.
+	///
+	/// Defaults to four (4) minutes.
+	pub(crate) time_wait_time: TickDuration,
+	
 	/// After how long a period are keep-alive probes invoked to verify that an idle connection is still alive.
 	///
 	/// After this time expires, up to `inclusive_maximum_number_of_keep_alive_probes` keep-alive probes are sent every `interval`.
@@ -67,6 +91,12 @@ impl<TCBA: TransmissionControlBlockAbstractions> Alarms<TCBA>
 		const keep_alive_interval: TickDuration = TickDuration::milliseconds_to_ticks_rounded_up(MillisecondDuration::OneSecond);
 		assert_ne!(keep_alive_interval, TickDuration::Zero, "keep_alive_interval '{}' should never be zero", keep_alive_interval);
 		
+		const time_wait_time: TickDuration = TickDuration::milliseconds_to_ticks_rounded_up(MillisecondDuration::FourMinutes);
+		assert_ne!(time_wait_time, TickDuration::Zero, "time_wait_time '{}' should never be zero", time_wait_time);
+		
+		const delayed_acknowledgment_time: TickDuration = TickDuration::milliseconds_to_ticks_rounded_up(MillisecondDuration::from_milliseconds(200));
+		assert_ne!(delayed_acknowledgment_time, TickDuration::Zero, "delayed_acknowledgment_time '{}' should never be zero", delayed_acknowledgment_time);
+		
 		const inclusive_maximum_number_of_keep_alive_probes: u8 = 5;
 		assert_ne!(inclusive_maximum_number_of_keep_alive_probes, 0, "inclusive_maximum_number_of_keep_alive_probes '{}' should never be zero", inclusive_maximum_number_of_keep_alive_probes);
 		
@@ -75,7 +105,12 @@ impl<TCBA: TransmissionControlBlockAbstractions> Alarms<TCBA>
 			keep_alive_alarm_wheel: AlarmWheel::new(now),
 			retransmission_and_zero_window_probe_alarm_wheel: AlarmWheel::new(now),
 			user_time_out_alarm_wheel: AlarmWheel::new(now),
+			time_wait_alarm_wheel: AlarmWheel::new(now),
+			delayed_acknowledgment_alarm_wheel: AlarmWheel::new(now),
+			pacing_alarm_wheel: AlarmWheel::new(now),
 			
+			time_wait_time,
+			delayed_acknowledgment_time,
 			keep_alive_time,
 			keep_alive_interval,
 			inclusive_maximum_number_of_keep_alive_probes,
@@ -90,6 +125,9 @@ impl<TCBA: TransmissionControlBlockAbstractions> Alarms<TCBA>
 		self.keep_alive_alarm_wheel.progress(now, interface);
 		self.retransmission_and_zero_window_probe_alarm_wheel.progress(now, interface);
 		self.user_time_out_alarm_wheel.progress(now, interface);
+		self.time_wait_alarm_wheel.progress(now, interface);
+		self.delayed_acknowledgment_alarm_wheel.progress(now, interface);
+		self.pacing_alarm_wheel.progress(now, interface);
 		now
 	}
 	
@@ -116,4 +154,22 @@ impl<TCBA: TransmissionControlBlockAbstractions> Alarms<TCBA>
 	{
 		&self.user_time_out_alarm_wheel
 	}
+	
+	#[inline(always)]
+	pub(crate) fn time_wait_alarm_wheel(&self) -> &AlarmWheel<TimeWaitAlarmBehaviour, TCBA>
+	{
+		&self.time_wait_alarm_wheel
+	}
+	
+	#[inline(always)]
+	pub(crate) fn delayed_acknowledgment_alarm_wheel(&self) -> &AlarmWheel<DelayedAcknowledgmentAlarmBehaviour, TCBA>
+	{
+		&self.delayed_acknowledgment_alarm_wheel
+	}
+	
+	#[inline(always)]
+	pub(crate) fn pacing_alarm_wheel(&self) -> &AlarmWheel<PacingAlarmBehaviour, TCBA>
+	{
+		&self.pacing_alarm_wheel
+	}
 }
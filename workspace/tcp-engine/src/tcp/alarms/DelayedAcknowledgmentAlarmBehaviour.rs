@@ -0,0 +1,37 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// RFC 1122 Section 4.2.3.2: "A TCP SHOULD implement a delayed ACK, but an ACK should not be excessively delayed; in particular, the delay MUST be less than 0.5 seconds".
+///
+/// When this alarm goes off an acknowledgment of all data received so far is sent.
+///
+/// The alarm is cancelled if the acknowledgment is sent earlier, either because a second segment arrived or because it was piggybacked on outgoing data.
+#[derive(Default, Debug)]
+pub(crate) struct DelayedAcknowledgmentAlarmBehaviour<TCBA: TransmissionControlBlockAbstractions>
+{
+	marker: PhantomData<TCBA>,
+}
+
+impl<TCBA: TransmissionControlBlockAbstractions> AlarmBehaviour<TCBA> for DelayedAcknowledgmentAlarmBehaviour<TCBA>
+{
+	#[inline(always)]
+	fn process_alarm(transmission_control_block: &mut TransmissionControlBlock<TCBA>, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
+	{
+		transmission_control_block.send_delayed_acknowledgment(interface, now.to_milliseconds());
+		
+		None
+	}
+	
+	#[inline(always)]
+	fn alarm_wheel(alarms: &Alarms<TCBA>) -> &AlarmWheel<Self, TCBA>
+	{
+		alarms.delayed_acknowledgment_alarm_wheel()
+	}
+	
+	#[inline(always)]
+	fn offset_of_parent_alarm_from_transmission_control_block() -> usize
+	{
+		offset_of!(TransmissionControlBlock<TCBA>, delayed_acknowledgment_alarm)
+	}
+}
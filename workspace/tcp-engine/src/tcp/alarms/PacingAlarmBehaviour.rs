@@ -0,0 +1,113 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Pacing spreads the segments of a window over a round trip time rather than sending them back-to-back, so that bursts do not overflow the buffers of switches and routers.
+///
+/// Pacing uses a credit of bytes which is earned at the pacing rate; a segment may be transmitted whilst the credit is positive.
+/// Only a small burst's worth of credit can be saved up, so a connection which has been idle does not then burst.
+///
+/// This alarm is only scheduled whilst transmission of buffered data is being held back; when it goes off, the held back data is transmitted as far as the credit then permits.
+///
+/// The alarm can only go off in whole ticks (128 milliseconds), so a connection which is held back transmits at most a burst per tick; pacing is therefore ineffective, and limits throughput, for round trip times shorter than a tick.
+#[derive(Debug)]
+pub(crate) struct PacingAlarmBehaviour<TCBA: TransmissionControlBlockAbstractions>
+{
+	/// Bytes which may yet be transmitted; negative if more has been transmitted than the pacing rate has so far permitted.
+	credit: i64,
+	
+	credit_last_earned_at: Option<MonotonicMillisecondTimestamp>,
+	
+	marker: PhantomData<TCBA>,
+}
+
+impl<TCBA: TransmissionControlBlockAbstractions> Default for PacingAlarmBehaviour<TCBA>
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		Self
+		{
+			credit: 0,
+			credit_last_earned_at: None,
+			marker: PhantomData,
+		}
+	}
+}
+
+impl<TCBA: TransmissionControlBlockAbstractions> AlarmBehaviour<TCBA> for PacingAlarmBehaviour<TCBA>
+{
+	#[inline(always)]
+	fn process_alarm(transmission_control_block: &mut TransmissionControlBlock<TCBA>, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
+	{
+		// If transmission is still being held back, the alarm is scheduled again.
+		transmission_control_block.transmit_buffered_data_returning_true_if_failed(interface, now.to_milliseconds());
+		
+		None
+	}
+	
+	#[inline(always)]
+	fn alarm_wheel(alarms: &Alarms<TCBA>) -> &AlarmWheel<Self, TCBA>
+	{
+		alarms.pacing_alarm_wheel()
+	}
+	
+	#[inline(always)]
+	fn offset_of_parent_alarm_from_transmission_control_block() -> usize
+	{
+		offset_of!(TransmissionControlBlock<TCBA>, pacing_alarm)
+	}
+}
+
+impl<TCBA: TransmissionControlBlockAbstractions> PacingAlarmBehaviour<TCBA>
+{
+	/// As for Linux, at least two segments may be transmitted together.
+	const MinimumBurstInSegments: i64 = 2;
+	
+	/// As for Linux, a burst may be as large as a millisecond's worth of data at the pacing rate.
+	const MaximumBurstInMilliseconds: i64 = 1;
+	
+	/// Earns credit for the time elapsed since credit was last earned, and returns `true` if a segment may be transmitted now.
+	///
+	/// The first time pacing is used, a burst's worth of credit is earned, so that a connection is not held back before it has transmitted anything.
+	#[inline(always)]
+	pub(crate) fn may_transmit(&mut self, now: MonotonicMillisecondTimestamp, pacing_rate: u64, sender_maximum_segment_size: u32) -> bool
+	{
+		let maximum_burst = max(Self::MinimumBurstInSegments * (sender_maximum_segment_size as i64), (pacing_rate as i64) * Self::MaximumBurstInMilliseconds / 1000);
+		
+		let earned = match self.credit_last_earned_at
+		{
+			None => maximum_burst,
+			Some(credit_last_earned_at) =>
+			{
+				debug_assert!(now >= credit_last_earned_at, "time has run backwards");
+				let elapsed: u64 = (now - credit_last_earned_at).into();
+				(pacing_rate.saturating_mul(elapsed) / 1000) as i64
+			}
+		};
+		self.credit_last_earned_at = Some(now);
+		
+		// Credit is capped at a burst even whilst transmission is held back; keeping all credit earned would let a whole alarm tick's worth of data be transmitted back-to-back.
+		self.credit = min(self.credit.saturating_add(earned), maximum_burst);
+		
+		self.credit > 0
+	}
+	
+	#[inline(always)]
+	pub(crate) fn transmitted(&mut self, payload_size: usize)
+	{
+		self.credit = self.credit.saturating_sub(payload_size as i64)
+	}
+	
+	/// Transmission is being held back; returns how long until a segment may be transmitted.
+	#[inline(always)]
+	pub(crate) fn holding_back(&self, pacing_rate: u64) -> MillisecondDuration
+	{
+		debug_assert!(self.credit <= 0, "transmission is being held back despite there being credit");
+		debug_assert_ne!(pacing_rate, 0, "pacing_rate can not be zero");
+		
+		let credit_needed = (1 - self.credit) as u64;
+		let milliseconds = (credit_needed.saturating_mul(1000) + pacing_rate - 1) / pacing_rate;
+		MillisecondDuration::from_milliseconds(milliseconds)
+	}
+}
@@ -0,0 +1,28 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// Why the retransmission alarm is scheduled whilst there is data which has not been acknowledged.
+///
+/// Only one of these timers runs at once; RFC 8985 Section 7.2 replaces the retransmission timer with the tail loss probe timer, and Section 6.3 the retransmission timer with the reordering timer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum RetransmissionAlarmPurpose
+{
+	/// RFC 6298: The retransmission timer.
+	RetransmissionTimeOut,
+	
+	/// RFC 8985 Section 7: The probe timeout (PTO) timer, after which a tail loss probe is sent.
+	TailLossProbe,
+	
+	/// RFC 8985 Section 6.3: The reordering timer, after which segments which have not been delivered may be marked as lost.
+	ReorderingTimeOut,
+}
+
+impl Default for RetransmissionAlarmPurpose
+{
+	#[inline(always)]
+	fn default() -> Self
+	{
+		RetransmissionAlarmPurpose::RetransmissionTimeOut
+	}
+}
@@ -6,6 +6,18 @@
 pub(crate) struct RetransmissionAndZeroWindowProbeAlarmBehaviour<TCBA: TransmissionControlBlockAbstractions>
 {
 	retransmission_time_out_data: RetransmissionTimeOutData,
+	
+	/// RFC 4015 Section 4 'SRTT_prev' and 'RTTVAR_prev': `SRTT` (plus twice the clock granularity) and `RTTVAR` when the retransmission timer last expired for the first time for a segment.
+	prior_smoothed_round_trip_time_and_round_trip_time_variance: (MillisecondDuration, MillisecondDuration),
+	
+	/// RFC 4015 Section 4: Should the next measurement of round trip time adapt the retransmission time out to a spurious retransmission time out?
+	adapt_to_spurious_retransmission_time_out: bool,
+	
+	/// Only meaningful when scheduled whilst there is data which has not been acknowledged.
+	purpose: RetransmissionAlarmPurpose,
+	
+	/// RFC 8985 Section 7.1 "TLP.end_seq" and "TLP.is_retrans": `SND.NXT` after the tail loss probe outstanding, if any, was sent, and whether it was a retransmission (rather than new data).
+	tail_loss_probe: Option<(WrappingSequenceNumber, bool)>,
 }
 
 impl<TCBA: TransmissionControlBlockAbstractions> AlarmBehaviour<TCBA> for RetransmissionAndZeroWindowProbeAlarmBehaviour<TCBA>
@@ -26,7 +38,16 @@ impl<TCBA: TransmissionControlBlockAbstractions> AlarmBehaviour<TCBA> for Retran
 		}
 		else
 		{
-			transmission_control_block.retransmit_data(interface, now)
+			use self::RetransmissionAlarmPurpose::*;
+			
+			match transmission_control_block.retransmission_alarm_purpose()
+			{
+				RetransmissionTimeOut => transmission_control_block.retransmit_data(interface, now),
+				
+				TailLossProbe => transmission_control_block.tail_loss_probe(interface, now),
+				
+				ReorderingTimeOut => transmission_control_block.reordering_timed_out(interface, now),
+			}
 		}
 	}
 	
@@ -51,22 +72,112 @@ impl<TCBA: TransmissionControlBlockAbstractions> RetransmissionAndZeroWindowProb
 		Self
 		{
 			retransmission_time_out_data: recent_connection_data.retransmission_time_out_data(),
+			prior_smoothed_round_trip_time_and_round_trip_time_variance: (MillisecondDuration::from_milliseconds(0), MillisecondDuration::from_milliseconds(0)),
+			adapt_to_spurious_retransmission_time_out: false,
+			purpose: RetransmissionAlarmPurpose::RetransmissionTimeOut,
+			tail_loss_probe: None,
 		}
 	}
 	
 	#[inline(always)]
-	pub(crate) fn adjust_retransmission_time_out_based_on_acknowledgments(&mut self, now: MonotonicMillisecondTimestamp, timestamp: MonotonicMillisecondTimestamp)
+	pub(crate) fn purpose(&self) -> RetransmissionAlarmPurpose
+	{
+		self.purpose
+	}
+	
+	#[inline(always)]
+	pub(crate) fn set_purpose(&mut self, purpose: RetransmissionAlarmPurpose)
+	{
+		self.purpose = purpose
+	}
+	
+	/// RFC 8985 Section 7.2 "TLP_calc_PTO()": The probe timeout is twice `SRTT`, allowing for a delayed acknowledgment if only one segment is in flight, but is never more than the retransmission time out.
+	///
+	/// Without a measurement of round trip time, it is one second.
+	#[inline(always)]
+	pub(crate) fn probe_time_out(&self, only_one_segment_in_flight: bool, retransmission_time_out: MillisecondDuration) -> MillisecondDuration
 	{
-		debug_assert!(now >= timestamp, "time has run backwards");
+		// RFC 8985 Section 7.2 "TLP.max_ack_delay"; the same as the time acknowledgments are delayed by.
+		const MaximumAcknowledgmentDelay: u64 = 200;
+		
+		let (smoothed_round_trip_time, _round_trip_time_variance) = self.smoothed_round_trip_time_and_round_trip_time_variance();
+		let smoothed_round_trip_time: u64 = smoothed_round_trip_time.into();
+		
+		let probe_time_out = if smoothed_round_trip_time == 0
+		{
+			MillisecondDuration::OneSecond
+		}
+		else if only_one_segment_in_flight
+		{
+			MillisecondDuration::from_milliseconds(2 * smoothed_round_trip_time + MaximumAcknowledgmentDelay)
+		}
+		else
+		{
+			MillisecondDuration::from_milliseconds(2 * smoothed_round_trip_time)
+		};
 		
-		let measurement_of_round_trip_time = now - timestamp;
-		self.process_measurement_of_round_trip_time(measurement_of_round_trip_time)
+		min(probe_time_out, retransmission_time_out)
+	}
+	
+	/// RFC 8985 Section 7.1 "TLP.end_seq" and "TLP.is_retrans", if a tail loss probe is outstanding.
+	#[inline(always)]
+	pub(crate) fn tail_loss_probe(&self) -> Option<(WrappingSequenceNumber, bool)>
+	{
+		self.tail_loss_probe
+	}
+	
+	/// RFC 8985 Section 7.3: A tail loss probe has been sent; `SND_NXT` is after it was sent.
+	#[allow(non_snake_case)]
+	#[inline(always)]
+	pub(crate) fn tail_loss_probe_transmitted(&mut self, SND_NXT: WrappingSequenceNumber, was_a_retransmission: bool)
+	{
+		self.tail_loss_probe = Some((SND_NXT, was_a_retransmission))
 	}
 	
+	/// RFC 8985 Section 7.4: The outcome of the outstanding tail loss probe, if any, is known (or no longer matters, eg because the retransmission timer expired).
+	#[inline(always)]
+	pub(crate) fn tail_loss_probe_ended(&mut self)
+	{
+		self.tail_loss_probe = None
+	}
+	
+	/// RFC 4015 Section 4: After a spurious retransmission time out, the first measurement of round trip time makes `SRTT` and `RTTVAR` at least as large as they were before the retransmission time out, and at least as large as the measurement (and half of it) respectively, so that the retransmission time out is no less conservative than it needs to be to avoid another.
 	#[inline(always)]
 	pub(crate) fn process_measurement_of_round_trip_time(&mut self, measurement_of_round_trip_time: MillisecondDuration)
 	{
-		self.retransmission_time_out_data.process_measurement_of_round_trip_time(measurement_of_round_trip_time)
+		if self.adapt_to_spurious_retransmission_time_out
+		{
+			self.adapt_to_spurious_retransmission_time_out = false;
+			
+			let (prior_smoothed_round_trip_time, prior_round_trip_time_variance) = self.prior_smoothed_round_trip_time_and_round_trip_time_variance;
+			let smoothed_round_trip_time = max(prior_smoothed_round_trip_time, measurement_of_round_trip_time);
+			let round_trip_time_variance = max(prior_round_trip_time_variance, measurement_of_round_trip_time / 2);
+			self.retransmission_time_out_data.set_smoothed_round_trip_time_and_round_trip_time_variance(smoothed_round_trip_time, round_trip_time_variance)
+		}
+		else
+		{
+			self.retransmission_time_out_data.process_measurement_of_round_trip_time(measurement_of_round_trip_time)
+		}
+	}
+	
+	/// RFC 4015 Section 4: Records 'SRTT_prev' and 'RTTVAR_prev' when the retransmission timer expires for the first time for a segment, in case the retransmission time out is later found to be spurious.
+	#[inline(always)]
+	pub(crate) fn retransmission_timed_out_for_the_first_time(&mut self)
+	{
+		// The clock granularity, `G`, is one millisecond.
+		let clock_granularity = MillisecondDuration::from_milliseconds(1);
+		
+		let (smoothed_round_trip_time, round_trip_time_variance) = self.smoothed_round_trip_time_and_round_trip_time_variance();
+		self.prior_smoothed_round_trip_time_and_round_trip_time_variance = (smoothed_round_trip_time + clock_granularity + clock_granularity, round_trip_time_variance);
+		self.adapt_to_spurious_retransmission_time_out = false;
+	}
+	
+	/// RFC 4015 Section 4: The retransmission time out was spurious; it is no longer inflated (backed off), and it is adapted by the next measurement of round trip time.
+	#[inline(always)]
+	pub(crate) fn retransmission_time_out_was_spurious(&mut self)
+	{
+		self.reset_retransmissions();
+		self.adapt_to_spurious_retransmission_time_out = true;
 	}
 	
 	#[inline(always)]
@@ -0,0 +1,40 @@
+// This file is part of tcp-engine. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT. No part of tcp-engine, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2017 The developers of tcp-engine. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/tcp-engine/master/COPYRIGHT.
+
+
+/// RFC 793 Page 22: TIME-WAIT "represents waiting for enough time to pass to be sure the remote TCP received the acknowledgment of its connection termination request".
+///
+/// When this alarm goes off the connection is closed.
+///
+/// RFC 793 Page 73: "... restart the 2 MSL timeout" occurs if the remote retransmits its `FIN`.
+#[derive(Default, Debug)]
+pub(crate) struct TimeWaitAlarmBehaviour<TCBA: TransmissionControlBlockAbstractions>
+{
+	marker: PhantomData<TCBA>,
+}
+
+impl<TCBA: TransmissionControlBlockAbstractions> AlarmBehaviour<TCBA> for TimeWaitAlarmBehaviour<TCBA>
+{
+	#[inline(always)]
+	fn process_alarm(transmission_control_block: &mut TransmissionControlBlock<TCBA>, interface: &Interface<TCBA>, now: Tick) -> Option<TickDuration>
+	{
+		debug_assert_eq!(transmission_control_block.state(), State::TimeWait, "state is not TimeWait");
+		
+		// RFC 793 Page 22: "When the time-wait timeout expires, delete the TCB, enter the CLOSED state and return".
+		transmission_control_block.closed(interface, now.to_milliseconds());
+		
+		None
+	}
+	
+	#[inline(always)]
+	fn alarm_wheel(alarms: &Alarms<TCBA>) -> &AlarmWheel<Self, TCBA>
+	{
+		alarms.time_wait_alarm_wheel()
+	}
+	
+	#[inline(always)]
+	fn offset_of_parent_alarm_from_transmission_control_block() -> usize
+	{
+		offset_of!(TransmissionControlBlock<TCBA>, time_wait_alarm)
+	}
+}
@@ -7,7 +7,7 @@
 /// * Before the state becomes synchronized (ie SynchronizeSent), it aborts connections that have exceed an user time out but does not send a Reset;
 /// * When the state is Established, FinishWait1, FinishWait2 or CloseWait (and, the phantom state, SynchronizeRecevied), it aborts connection that have exceeded an user time out and sends a Reset;
 /// * When the state is Closing or LastAcknowledgment it aborts connections that have exceed the last measured retransmission time out but does not send a Reset;
-/// * When the state is TimeWait, it closes connections when the TimeWait time has expired (this is supposed to be 2 * MSL, the Maximum Segment Lifetime, which is 2 minutes, but that is far too long in a modern network)
+/// * When the state is TimeWait, it is not used; the `TimeWaitAlarmBehaviour` closes connections when the TimeWait time has expired.
 ///
 /// This timer is complementary to the keep-alive timer.
 #[derive(Default, Debug)]
@@ -12,6 +12,10 @@ include!("AlarmWheel.rs");
 include!("DereferenceUnchecked.rs");
 
 include!("Alarms.rs");
+include!("DelayedAcknowledgmentAlarmBehaviour.rs");
 include!("KeepAliveAlarmBehaviour.rs");
+include!("PacingAlarmBehaviour.rs");
+include!("RetransmissionAlarmPurpose.rs");
 include!("RetransmissionAndZeroWindowProbeAlarmBehaviour.rs");
+include!("TimeWaitAlarmBehaviour.rs");
 include!("UserTimeOutAlarmBehaviour.rs");
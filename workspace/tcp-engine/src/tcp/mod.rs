@@ -7,6 +7,7 @@ use self::alarms::*;
 use self::api::*;
 use self::authentication::*;
 use self::congestion_control::*;
+use self::reassembly::*;
 use self::retransmission::*;
 #[macro_use] use self::segment::*;
 use self::syn_cookies::*;
@@ -27,9 +28,11 @@ include!("ParsedTcpSegment.rfc_5961_5_2_acknowledgment_is_acceptable.rs");
 include!("ParsedTcpSegment.unreachable_synthetic_state.rs");
 include!("ParsedTcpSegment.validate_authentication.rs");
 include!("ParsedTcpSegment.validate_authentication_when_synchronized.rs");
+include!("ParsedTcpSegment.validate_synchronize_for_syncookie.rs");
 include!("TransmissionControlBlock.increment_retransmissions.rs");
 
 
+include!("ListeningServerCongestionControlAlgorithms.rs");
 include!("ParsedTcpSegment.rs");
 include!("TransmissionControlBlock.rs");
 include!("TransmissionControlBlockReceive.rs");